@@ -20,6 +20,8 @@ pub struct Model {
     pub author_id: Uuid,
     pub created_at: Option<DateTime>,
     pub updated_at: Option<DateTime>,
+    pub published: bool,
+    pub deleted_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -30,6 +32,8 @@ pub enum Relation {
     Comment,
     #[sea_orm(has_many = "super::favorited_article::Entity")]
     FavoritedArticle,
+    #[sea_orm(has_many = "super::reaction::Entity")]
+    Reaction,
     #[sea_orm(
         belongs_to = "super::user::Entity",
         from = "Column::AuthorId",
@@ -58,6 +62,12 @@ impl Related<super::favorited_article::Entity> for Entity {
     }
 }
 
+impl Related<super::reaction::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Reaction.def()
+    }
+}
+
 impl Related<super::tag::Entity> for Entity {
     fn to() -> RelationDef {
         super::article_tag::Relation::Tag.def()
@@ -4,8 +4,10 @@ pub mod prelude;
 
 pub mod article;
 pub mod article_tag;
+pub mod block;
 pub mod comment;
 pub mod favorited_article;
 pub mod follower;
+pub mod reaction;
 pub mod tag;
 pub mod user;
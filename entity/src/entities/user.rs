@@ -29,6 +29,8 @@ pub enum Relation {
     Comment,
     #[sea_orm(has_many = "super::favorited_article::Entity")]
     FavoritedArticle,
+    #[sea_orm(has_many = "super::reaction::Entity")]
+    Reaction,
 }
 
 impl Related<super::comment::Entity> for Entity {
@@ -43,6 +45,12 @@ impl Related<super::favorited_article::Entity> for Entity {
     }
 }
 
+impl Related<super::reaction::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Reaction.def()
+    }
+}
+
 impl Related<super::article::Entity> for Entity {
     fn to() -> RelationDef {
         super::favorited_article::Relation::Article.def()
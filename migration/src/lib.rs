@@ -8,6 +8,13 @@ mod m20231030_000005_create_article_tag_table;
 mod m20231101_000006_create_follower_table;
 mod m20231104_000007_create_favorited_article_table;
 mod m20231112_000008_add_user_password;
+mod m20231201_000009_create_reaction_table;
+mod m20231215_000010_add_favorited_article_created_at;
+mod m20231220_000011_add_article_tag_created_at;
+mod m20231228_000012_add_article_published;
+mod m20240102_000013_add_tag_name_ci;
+mod m20240105_000014_create_block_table;
+mod m20240110_000015_add_article_deleted_at;
 
 pub struct Migrator;
 
@@ -23,6 +30,13 @@ impl MigratorTrait for Migrator {
             Box::new(m20231101_000006_create_follower_table::Migration),
             Box::new(m20231104_000007_create_favorited_article_table::Migration),
             Box::new(m20231112_000008_add_user_password::Migration),
+            Box::new(m20231201_000009_create_reaction_table::Migration),
+            Box::new(m20231215_000010_add_favorited_article_created_at::Migration),
+            Box::new(m20231220_000011_add_article_tag_created_at::Migration),
+            Box::new(m20231228_000012_add_article_published::Migration),
+            Box::new(m20240102_000013_add_tag_name_ci::Migration),
+            Box::new(m20240105_000014_create_block_table::Migration),
+            Box::new(m20240110_000015_add_article_deleted_at::Migration),
         ]
     }
 }
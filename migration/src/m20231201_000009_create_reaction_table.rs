@@ -0,0 +1,67 @@
+use crate::m20231030_000001_create_user_table::User;
+use crate::m20231030_000002_create_article_table::Article;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Reaction::Table)
+                    .if_not_exists()
+                    .primary_key(
+                        Index::create()
+                            .name("idx-reaction")
+                            .if_not_exists()
+                            .table(Reaction::Table)
+                            .col(Reaction::ArticleId)
+                            .col(Reaction::UserId)
+                            .col(Reaction::Kind),
+                    )
+                    .col(ColumnDef::new(Reaction::ArticleId).uuid().not_null())
+                    .col(ColumnDef::new(Reaction::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(Reaction::Kind)
+                            .string()
+                            .check(Expr::col(Reaction::Kind).ne(""))
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("FK_reaction-article")
+                            .from(Reaction::Table, Reaction::ArticleId)
+                            .to(Article::Table, Article::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("FK_reaction-user")
+                            .from(Reaction::Table, Reaction::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Reaction::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Reaction {
+    Table,
+    ArticleId,
+    UserId,
+    Kind,
+}
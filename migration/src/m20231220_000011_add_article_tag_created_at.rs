@@ -0,0 +1,37 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Nullable without default, as sqlite rejects non-constant default on added column.
+        // Taggings made before migration remain without timestamp.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ArticleTag::Table)
+                    .add_column(ColumnDef::new(ArticleTag::CreatedAt).timestamp())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ArticleTag::Table)
+                    .drop_column(ArticleTag::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ArticleTag {
+    Table,
+    CreatedAt,
+}
@@ -0,0 +1,100 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::DbBackend;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // Collapse existing case variants into the lexicographically smallest name,
+        // links of articles are moved to it (dropping those that became duplicates)
+        if manager.has_table("article_tag").await? {
+            db.execute_unprepared(
+                r#"DELETE FROM article_tag WHERE EXISTS (
+                    SELECT 1 FROM tag t, article_tag other, tag other_tag
+                    WHERE t.id = article_tag.tag_id
+                    AND other.article_id = article_tag.article_id
+                    AND other_tag.id = other.tag_id
+                    AND lower(other_tag.tag_name) = lower(t.tag_name)
+                    AND other_tag.tag_name < t.tag_name
+                )"#,
+            )
+            .await?;
+            db.execute_unprepared(
+                r#"UPDATE article_tag SET tag_id = (
+                    SELECT canonical.id FROM tag t, tag canonical
+                    WHERE t.id = article_tag.tag_id
+                    AND lower(canonical.tag_name) = lower(t.tag_name)
+                    AND NOT EXISTS (
+                        SELECT 1 FROM tag smaller
+                        WHERE lower(smaller.tag_name) = lower(canonical.tag_name)
+                        AND smaller.tag_name < canonical.tag_name
+                    )
+                )"#,
+            )
+            .await?;
+        }
+        db.execute_unprepared(
+            r#"DELETE FROM tag WHERE EXISTS (
+                SELECT 1 FROM tag smaller
+                WHERE lower(smaller.tag_name) = lower(tag.tag_name)
+                AND smaller.tag_name < tag.tag_name
+            )"#,
+        )
+        .await?;
+
+        // Column maintained by database, sqlite can't add stored generated column
+        let generated = match manager.get_database_backend() {
+            DbBackend::Sqlite => "GENERATED ALWAYS AS (lower(tag_name)) VIRTUAL",
+            _ => "GENERATED ALWAYS AS (lower(tag_name)) STORED",
+        };
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tag::Table)
+                    .add_column(ColumnDef::new(Tag::TagNameCi).text().extra(generated))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-tag-tag_name_ci")
+                    .table(Tag::Table)
+                    .col(Tag::TagNameCi)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-tag-tag_name_ci")
+                    .table(Tag::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Tag::Table)
+                    .drop_column(Tag::TagNameCi)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Tag {
+    Table,
+    TagNameCi,
+}
@@ -0,0 +1,64 @@
+use crate::m20231030_000001_create_user_table::User;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Block::Table)
+                    .if_not_exists()
+                    .primary_key(
+                        Index::create()
+                            .name("idx-block")
+                            .if_not_exists()
+                            .table(Block::Table)
+                            .col(Block::UserId)
+                            .col(Block::BlockedId),
+                    )
+                    .col(ColumnDef::new(Block::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(Block::BlockedId).uuid().not_null().check(
+                            Expr::col(Block::UserId)
+                                .eq(Expr::col(Block::BlockedId))
+                                .not(),
+                        ),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("FK_block-user")
+                            .from(Block::Table, Block::UserId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("FK_block-blocked")
+                            .from(Block::Table, Block::BlockedId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Block::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Block {
+    Table,
+    UserId,
+    BlockedId,
+}
@@ -0,0 +1,83 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Article::Table)
+                    .add_column(ColumnDef::new(Article::DeletedAt).timestamp().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Title of deleted article could be used by its author again, thus unique author
+        // and title required only among not deleted articles (partial index, not supported
+        // by query builder)
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-article")
+                    .table(Article::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"CREATE UNIQUE INDEX "idx-article" ON "article" ("author_id", "title")
+                WHERE "deleted_at" IS NULL"#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Deleted articles are removed, so unique author and title could be restored
+        manager
+            .get_connection()
+            .execute_unprepared(r#"DELETE FROM "article" WHERE "deleted_at" IS NOT NULL"#)
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-article")
+                    .table(Article::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-article")
+                    .table(Article::Table)
+                    .col(Article::AuthorId)
+                    .col(Article::Title)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Article::Table)
+                    .drop_column(Article::DeletedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Article {
+    Table,
+    AuthorId,
+    Title,
+    DeletedAt,
+}
@@ -1,137 +1,604 @@
-use crate::middleware::auth::Token;
+use crate::middleware::{auth::Token, version::ApiVersion};
 use crate::repo::{
     article::{
         create_article as repo_create_article, delete_article as repo_delete_article,
-        get_article_by_id, get_article_by_slug, get_article_model_by_slug, get_articles_count,
-        get_articles_feed, get_articles_with_filters, update_article as repo_update_article,
-        ArticleWithAuthor,
+        generate_unique_slug, get_article_by_id, get_article_by_slug, get_article_model_by_slug,
+        get_articles_after, get_articles_by_month, get_articles_by_month_count,
+        get_articles_by_slugs, get_articles_count, get_articles_feed, get_articles_page,
+        get_drafts, get_drafts_count, get_feed_count, get_own_articles, get_own_articles_count,
+        get_recent_duplicate_article_id, get_recently_tagged_articles, get_recently_tagged_count,
+        get_related_articles, get_related_count, get_trending_articles, get_trending_count,
+        patch_article, transfer_article, ArticlePatch, ArticleSort, ArticleWithAuthor, Page,
+        TagMatch,
+    },
+    article_tag::{
+        add_article_tag as repo_add_article_tag, create_article_tags, get_article_tag_ids,
+        remove_article_tag,
     },
-    article_tag::create_article_tags,
     favorited_article::{
-        favorite_article as repo_favorite_article, unfavorite_article as repo_unfavorite_article,
+        favorite_article as repo_favorite_article, get_favorited_articles,
+        unfavorite_article as repo_unfavorite_article, FavoriteOutcome,
     },
-    tag::{create_tags, get_tags_ids},
+    tag::{create_and_get_tags_ids, delete_tag_if_orphaned, get_or_create_tag, get_tags_ids},
+    user::{get_user_by_id, get_user_by_username},
+    user_cache::UserCache,
 };
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Query, State},
+    response::{IntoResponse, Response},
     Extension, Json,
 };
-use chrono::Local;
-use entity::entities::{article, article_tag, favorited_article, tag};
-use sea_orm::{prelude::DateTime, ActiveValue::Set, DatabaseConnection};
-use serde::{Deserialize, Serialize};
-use slug::slugify;
+use chrono::{Duration, Utc};
+use entity::entities::{article, article_tag, favorited_article};
+use sea_orm::{
+    prelude::DateTime, ActiveValue::Set, ConnectionTrait, DatabaseConnection, DbErr,
+    TransactionTrait,
+};
+use serde::{ser::Error, Deserialize, Serialize, Serializer};
+use serde_json::Value;
 use std::collections::HashMap;
+use std::env;
+use std::ops::Deref;
+use std::sync::Arc;
 use uuid::Uuid;
 
-use super::error::ApiErr;
+use super::{
+    error::ApiErr,
+    extract::Path,
+    id::new_id,
+    limits::{get_max_body_length, get_max_tags_per_article, PageConfig},
+    live::{FavoritesHub, Notification, NotificationsHub},
+    sanitize::sanitize_body,
+};
+
+const DEFAULT_ARTICLE_SORT: &str = "DEFAULT_ARTICLE_SORT";
+const DEFAULT_TRENDING_DAYS: i64 = 7;
+const MAX_SLUGS_LEN: usize = 100;
+const ARTICLE_DEDUP_WINDOW_SECS: &str = "ARTICLE_DEDUP_WINDOW_SECS";
+const ANON_LIST_ALLOWED: &str = "ANON_LIST_ALLOWED";
 
 /// Axum handler for Fetch `articles` with additional info (see ArticleWithAuthor for details).
-/// Query parameters used for filter records by tag name, author name, user who liked aticle.
-/// Limit response by limit and offset parameters. Ordered by most recent first.
-/// Returns `articles` object on success, otherwise returns an `database error`.
+/// Query parameters used for filter records by tag names (comma separated or repeated `tag`
+/// parameters), author name, user who liked aticle, text in title, description or body (`search`,
+/// case-insensitive). `tagMatch=any` selects articles tagged by
+/// any of the tags, by default all of them required. Limit response by limit (capped by
+/// APP_MAX_PAGE_SIZE) and offset parameters. Ordered by most recently updated (`sort=updated`)
+/// or created (`sort=created`) first, configured default applies when absent. `cursor` (see
+/// encode_cursor) selects page after the one it was returned with, instead of offset, ordered by
+/// most recently updated first, `nextCursor` of last page is absent.
+/// `fields` (comma separated) restricts serialized articles to requested fields (see Sparse).
+/// Anonymous users are rejected with `Unauthorized` when ANON_LIST_ALLOWED is disabled.
+/// Returns `articles` object on success, otherwise returns an `api error`.
 pub async fn list_articles(
-    Query(params): Query<HashMap<String, String>>,
+    Query(pairs): Query<Vec<(String, String)>>,
     maybe_token: Option<Extension<Token>>,
     State(db): State<DatabaseConnection>,
-) -> Result<Json<ArticlesDto>, ApiErr> {
-    // Filter by tag:
-    let tag_name = params.get(&"tag".to_string()).filter(|str| !str.is_empty());
+    Extension(page_config): Extension<PageConfig>,
+) -> Result<Json<Sparse<ArticlesDto>>, ApiErr> {
+    require_token_unless_anon_allowed(&maybe_token, anon_list_allowed())?;
+
+    // Filter by tags, all (default) or any of tags required:
+    let (tag_names, tag_match) = tag_filter(pairs.iter().map(|(key, val)| (key, val)));
+
+    // Other parameters are single valued, the last one applies when repeated:
+    let params: HashMap<String, String> = pairs.into_iter().collect();
 
     // Filter by author:
-    let author_name = params
-        .get(&"author".to_string())
-        .filter(|str| !str.is_empty());
+    let author_name = params.get("author").filter(|str| !str.is_empty());
 
     // Favorited by user:
-    let user_who_liked_it = params
-        .get(&"favorited".to_string())
-        .filter(|str| !str.is_empty());
+    let user_who_liked_it = params.get("favorited").filter(|str| !str.is_empty());
 
-    // Limit number of articles (default is 20):
-    let limit = params
-        .get(&"limit".to_string())
+    // Search text in title, description or body:
+    let search = params.get("search").filter(|str| !str.is_empty());
+
+    // Sort order (default is configured by DEFAULT_ARTICLE_SORT):
+    let sort = params
+        .get("sort")
+        .and_then(|srt| srt.parse::<ArticleSort>().ok())
+        .unwrap_or_else(get_default_article_sort);
+
+    // Limit number of articles (default is configured by DEFAULT_PAGE_LIMIT, capped by
+    // APP_MAX_PAGE_SIZE):
+    let limit = page_config.page_limit(params.get("limit"));
+
+    // Offset/skip number of articles (default is 0):
+    let offset = params
+        .get("offset")
         .map(|lm| lm.parse::<u64>())
         .filter(|res| res.is_ok())
         .map(|res| res.unwrap());
 
+    let current_user_id = maybe_token.map(|tkn| tkn.id);
+
+    // Cursor of previous page takes precedence over offset, empty cursor requests first page:
+    let articles_dto = match params.get("cursor") {
+        Some(cursor) => {
+            let cursor = match cursor.is_empty() {
+                true => None,
+                false => Some(decode_cursor(cursor).ok_or(ApiErr::InvalidInput("Invalid cursor"))?),
+            };
+            let page = get_articles_after(
+                &db,
+                &tag_names,
+                tag_match,
+                author_name,
+                user_who_liked_it,
+                search,
+                cursor,
+                limit,
+                current_user_id,
+            )
+            .await?;
+            let articles_count = get_articles_count(
+                &db,
+                &tag_names,
+                tag_match,
+                author_name,
+                user_who_liked_it,
+                search,
+                current_user_id,
+            )
+            .await?;
+            ArticlesDto {
+                articles: page.items,
+                articles_count,
+                next_cursor: page.next_cursor.map(encode_cursor),
+            }
+        }
+        None => {
+            let page = get_articles_page(
+                &db,
+                &tag_names,
+                tag_match,
+                author_name,
+                user_who_liked_it,
+                search,
+                sort,
+                limit,
+                offset,
+                current_user_id,
+            )
+            .await?;
+            ArticlesDto::from(page)
+        }
+    };
+
+    Ok(Json(Sparse::new(articles_dto, requested_fields(&params))))
+}
+
+/// Encode cursor of articles page (time of update in microseconds and id of last article)
+/// as opaque `cursor` query parameter.
+fn encode_cursor((updated_at, id): (DateTime, Uuid)) -> String {
+    format!("{}_{}", updated_at.timestamp_micros(), id)
+}
+
+/// Decode `cursor` query parameter (see encode_cursor). Returns `None` for malformed cursor.
+fn decode_cursor(cursor: &str) -> Option<(DateTime, Uuid)> {
+    let (micros, id) = cursor.split_once('_')?;
+    let updated_at = DateTime::from_timestamp_micros(micros.parse().ok()?)?;
+    Some((updated_at, id.parse().ok()?))
+}
+
+/// Parse comma separated names of article fields requested by `fields` query parameter.
+/// Returns `None` when parameter is absent or empty, thus all fields are requested.
+fn requested_fields(params: &HashMap<String, String>) -> Option<Vec<String>> {
+    params
+        .get("fields")
+        .map(|flds| {
+            flds.split(',')
+                .filter(|str| !str.is_empty())
+                .map(|str| str.to_owned())
+                .collect::<Vec<String>>()
+        })
+        .filter(|flds| !flds.is_empty())
+}
+
+/// Reject anonymous request with `Unauthorized` error when anonymous access is not allowed
+/// (see ANON_LIST_ALLOWED).
+fn require_token_unless_anon_allowed(
+    maybe_token: &Option<Extension<Token>>,
+    anon_allowed: bool,
+) -> Result<(), ApiErr> {
+    match maybe_token.is_none() && !anon_allowed {
+        true => Err(ApiErr::Unauthorized),
+        false => Ok(()),
+    }
+}
+
+/// Return ANON_LIST_ALLOWED from environment varibles, enabled by default
+fn anon_list_allowed() -> bool {
+    env::var(ANON_LIST_ALLOWED).map_or(true, |value| {
+        !matches!(value.to_lowercase().as_str(), "false" | "0" | "off")
+    })
+}
+
+/// Return DEFAULT_ARTICLE_SORT from environment varibles or default sort (updated)
+fn get_default_article_sort() -> ArticleSort {
+    env::var(DEFAULT_ARTICLE_SORT)
+        .ok()
+        .and_then(|srt| srt.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Return tag names (`tag`, comma separated, names of repeated parameters are combined) and
+/// `tagMatch` query parameters. Tag names are trimmed, blank names are dropped.
+fn tag_filter<'a>(
+    params: impl IntoIterator<Item = (&'a String, &'a String)>,
+) -> (Vec<String>, TagMatch) {
+    let mut tag_names: Vec<String> = Vec::new();
+    let mut tag_match = TagMatch::All;
+
+    for (key, val) in params {
+        match key.as_str() {
+            "tag" => tag_names.extend(
+                val.split(',')
+                    .map(|str| str.trim())
+                    .filter(|str| !str.is_empty())
+                    .map(|str| str.to_owned()),
+            ),
+            "tagMatch" if val == "any" => tag_match = TagMatch::Any,
+            "tagMatch" => tag_match = TagMatch::All,
+            _ => {}
+        }
+    }
+
+    (tag_names, tag_match)
+}
+
+/// Axum handler for fetch `articles` created by followed users. Query parameters used for
+/// filter records by tag names (comma separated), `tagMatch=any` selects articles tagged by any
+/// of the tags. Limit response by limit (capped by APP_MAX_PAGE_SIZE) and offset parameters.
+/// Count matches the same filter. Returns `articles` object on success, otherwise returns an
+/// `database error`.
+pub async fn feed_articles(
+    Query(params): Query<HashMap<String, String>>,
+    Extension(token): Extension<Token>,
+    State(db): State<DatabaseConnection>,
+    Extension(page_config): Extension<PageConfig>,
+) -> Result<Json<ArticlesDto>, ApiErr> {
+    // Limit number of articles (default is configured by DEFAULT_PAGE_LIMIT, capped by
+    // APP_MAX_PAGE_SIZE):
+    let limit = page_config.page_limit(params.get("limit"));
+
     // Offset/skip number of articles (default is 0):
     let offset = params
-        .get(&"offset".to_string())
+        .get("offset")
         .map(|lm| lm.parse::<u64>())
         .filter(|res| res.is_ok())
         .map(|res| res.unwrap());
 
-    let articles = get_articles_with_filters(
-        &db,
-        tag_name,
-        author_name,
-        user_who_liked_it,
-        limit,
-        offset,
-        maybe_token.clone().map(|tkn| tkn.id),
-    )
-    .await?;
+    // Filter by tags, all (default) or any of tags required:
+    let (tag_names, tag_match) = tag_filter(&params);
 
-    let articles_count =
-        get_articles_count(&db, tag_name, author_name, user_who_liked_it, None).await?;
+    let current_user_id = token.id;
+
+    let articles =
+        get_articles_feed(&db, &tag_names, tag_match, limit, offset, current_user_id).await?;
+    let articles_count = get_feed_count(&db, &tag_names, tag_match, current_user_id).await?;
 
     let articles_dto = ArticlesDto {
         articles,
         articles_count,
+        next_cursor: None,
     };
 
     Ok(Json(articles_dto))
 }
 
-/// Axum handler for fetch `articles` created by followed users. Limit response by limit and offset parameters.
-/// Returns `articles` object on success, otherwise returns an `database error`.
-pub async fn feed_articles(
+/// Axum handler for fetch unpublished `articles` (drafts) of logged user. Limit response by limit
+/// and offset parameters. Returns `articles` object on success, otherwise returns an `api error`.
+pub async fn list_drafts(
+    Query(params): Query<HashMap<String, String>>,
+    Extension(token): Extension<Token>,
+    State(db): State<DatabaseConnection>,
+    Extension(page_config): Extension<PageConfig>,
+) -> Result<Json<ArticlesDto>, ApiErr> {
+    // Limit number of articles (default is configured by DEFAULT_PAGE_LIMIT, capped by
+    // APP_MAX_PAGE_SIZE):
+    let limit = page_config.page_limit(params.get("limit"));
+
+    // Offset/skip number of articles (default is 0):
+    let offset = params
+        .get("offset")
+        .map(|lm| lm.parse::<u64>())
+        .filter(|res| res.is_ok())
+        .map(|res| res.unwrap());
+
+    let articles = get_drafts(&db, token.id, limit, offset).await?;
+    let articles_count = get_drafts_count(&db, token.id).await?;
+
+    let articles_dto = ArticlesDto {
+        articles,
+        articles_count,
+        next_cursor: None,
+    };
+
+    Ok(Json(articles_dto))
+}
+
+/// Axum handler for fetch all `articles` of logged user, both published and drafts. Limit response
+/// by limit and offset parameters. Returns `articles` object on success, otherwise returns an `api error`.
+pub async fn list_own_articles(
     Query(params): Query<HashMap<String, String>>,
     Extension(token): Extension<Token>,
     State(db): State<DatabaseConnection>,
+    Extension(page_config): Extension<PageConfig>,
 ) -> Result<Json<ArticlesDto>, ApiErr> {
-    // Limit number of articles (default is 20):
-    let limit = params
-        .get(&"limit".to_string())
+    // Limit number of articles (default is configured by DEFAULT_PAGE_LIMIT, capped by
+    // APP_MAX_PAGE_SIZE):
+    let limit = page_config.page_limit(params.get("limit"));
+
+    // Offset/skip number of articles (default is 0):
+    let offset = params
+        .get("offset")
         .map(|lm| lm.parse::<u64>())
         .filter(|res| res.is_ok())
         .map(|res| res.unwrap());
 
+    let articles = get_own_articles(&db, token.id, limit, offset).await?;
+    let articles_count = get_own_articles_count(&db, token.id).await?;
+
+    let articles_dto = ArticlesDto {
+        articles,
+        articles_count,
+        next_cursor: None,
+    };
+
+    Ok(Json(articles_dto))
+}
+
+/// Axum handler for fetch `articles` favorited by logged user. Limit response by limit and offset
+/// parameters. Count is not affected by limit and offset.
+/// Returns `articles` object on success, otherwise returns an `api error`.
+pub async fn list_favorite_articles(
+    Query(params): Query<HashMap<String, String>>,
+    Extension(token): Extension<Token>,
+    State(db): State<DatabaseConnection>,
+    Extension(page_config): Extension<PageConfig>,
+    Extension(user_cache): Extension<Arc<UserCache>>,
+) -> Result<Json<ArticlesDto>, ApiErr> {
+    // Limit number of articles (default is configured by DEFAULT_PAGE_LIMIT, capped by
+    // APP_MAX_PAGE_SIZE):
+    let limit = page_config.page_limit(params.get("limit"));
+
     // Offset/skip number of articles (default is 0):
     let offset = params
-        .get(&"offset".to_string())
+        .get("offset")
         .map(|lm| lm.parse::<u64>())
         .filter(|res| res.is_ok())
         .map(|res| res.unwrap());
 
-    let current_user_id = token.id;
+    let (articles, articles_count) =
+        get_favorited_articles(&db, &user_cache, token.id, limit, offset, Some(token.id)).await?;
+
+    let articles_dto = ArticlesDto {
+        articles,
+        articles_count,
+        next_cursor: None,
+    };
+
+    Ok(Json(articles_dto))
+}
+
+/// Axum handler for fetch `articles` ranked by favorites gained during last `days` (default 7).
+/// Limit response by limit parameter. Optional token used to determine whether the logged in user
+/// is a follower of the author and whether the article is liked by that user. Window reaching
+/// out of supported dates is rejected with `InvalidInput`.
+/// Returns `articles` object on success, otherwise returns an `api error`.
+pub async fn trending_articles(
+    Query(params): Query<HashMap<String, String>>,
+    maybe_token: Option<Extension<Token>>,
+    State(db): State<DatabaseConnection>,
+    Extension(page_config): Extension<PageConfig>,
+) -> Result<Json<ArticlesDto>, ApiErr> {
+    // Time window in days (default is 7):
+    let days = params
+        .get("days")
+        .map(|dys| dys.parse::<u32>())
+        .filter(|res| res.is_ok())
+        .map(|res| res.unwrap() as i64)
+        .unwrap_or(DEFAULT_TRENDING_DAYS);
+
+    // Limit number of articles (default is configured by DEFAULT_PAGE_LIMIT, capped by
+    // APP_MAX_PAGE_SIZE):
+    let limit = page_config.page_limit(params.get("limit"));
+
+    let since = Utc::now()
+        .naive_utc()
+        .checked_sub_signed(Duration::days(days))
+        .ok_or(ApiErr::InvalidInput("Invalid days"))?;
+    let current_user_id = maybe_token.map(|tkn| tkn.id);
+    let articles = get_trending_articles(&db, since, limit, current_user_id).await?;
+    let articles_count = get_trending_count(&db, since, current_user_id).await?;
+
+    let articles_dto = ArticlesDto {
+        articles,
+        articles_count,
+        next_cursor: None,
+    };
+    Ok(Json(articles_dto))
+}
+
+/// Axum handler for fetch `articles` tagged with provided tag name, most recently tagged first.
+/// Limit response by limit parameter. Optional token used to determine whether the logged in user
+/// is a follower of the author and whether the article is liked by that user.
+/// Returns `articles` object on success, otherwise returns an `api error`.
+pub async fn recently_tagged_articles(
+    Query(params): Query<HashMap<String, String>>,
+    maybe_token: Option<Extension<Token>>,
+    State(db): State<DatabaseConnection>,
+    Extension(page_config): Extension<PageConfig>,
+    Path(tag_name): Path<String>,
+) -> Result<Json<ArticlesDto>, ApiErr> {
+    // Limit number of articles (default is configured by DEFAULT_PAGE_LIMIT, capped by
+    // APP_MAX_PAGE_SIZE):
+    let limit = page_config.page_limit(params.get("limit"));
+
+    let current_user_id = maybe_token.map(|tkn| tkn.id);
+    let articles = get_recently_tagged_articles(&db, &tag_name, limit, current_user_id).await?;
+    let articles_count = get_recently_tagged_count(&db, &tag_name, current_user_id).await?;
+
+    let articles_dto = ArticlesDto {
+        articles,
+        articles_count,
+        next_cursor: None,
+    };
+    Ok(Json(articles_dto))
+}
+
+/// Axum handler for fetch `articles` created within provided month of year (archive), most
+/// recent first. Limit response by limit and offset parameters. Optional token used to determine
+/// whether the logged in user is a follower of the author and whether the article is liked
+/// by that user. Month out of 1..=12 range is rejected with `InvalidInput`.
+/// Returns `articles` object on success, otherwise returns an `api error`.
+pub async fn archive_articles(
+    Path((year, month)): Path<(i32, u32)>,
+    Query(params): Query<HashMap<String, String>>,
+    maybe_token: Option<Extension<Token>>,
+    State(db): State<DatabaseConnection>,
+    Extension(page_config): Extension<PageConfig>,
+) -> Result<Json<ArticlesDto>, ApiErr> {
+    if !(1..=12).contains(&month) {
+        return Err(ApiErr::InvalidInput("Month must be between 1 and 12"));
+    }
+
+    // Limit number of articles (default is configured by DEFAULT_PAGE_LIMIT, capped by
+    // APP_MAX_PAGE_SIZE):
+    let limit = page_config.page_limit(params.get("limit"));
 
-    let articles = get_articles_feed(&db, limit, offset, current_user_id).await?;
-    let articles_count = get_articles_count(&db, None, None, None, Some(current_user_id)).await?;
+    // Offset/skip number of articles (default is 0):
+    let offset = params.get("offset").and_then(|ofs| ofs.parse::<u64>().ok());
+
+    let current_user_id = maybe_token.map(|tkn| tkn.id);
+    let articles = get_articles_by_month(&db, year, month, limit, offset, current_user_id).await?;
+    let articles_count = get_articles_by_month_count(&db, year, month, current_user_id).await?;
+
+    let articles_dto = ArticlesDto {
+        articles,
+        articles_count,
+        next_cursor: None,
+    };
+    Ok(Json(articles_dto))
+}
+
+/// Axum handler for fetch `articles` sharing tags with article of provided slug, ranked by number
+/// of shared tags. Limit response by limit parameter. Optional token used to determine whether
+/// the logged in user is a follower of the author and whether the article is liked by that user.
+/// Returns `articles` object on success, otherwise returns an `api error`.
+pub async fn related_articles(
+    Path(slug): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+    maybe_token: Option<Extension<Token>>,
+    State(db): State<DatabaseConnection>,
+    Extension(page_config): Extension<PageConfig>,
+) -> Result<Json<ArticlesDto>, ApiErr> {
+    // Limit number of articles (default is configured by DEFAULT_PAGE_LIMIT, capped by
+    // APP_MAX_PAGE_SIZE):
+    let limit = page_config.page_limit(params.get("limit"));
+
+    let current_user_id = maybe_token.map(|tkn| tkn.id);
+    let article = get_article_model_by_slug(&db, &slug, current_user_id)
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+
+    let articles = get_related_articles(&db, article.id, limit, current_user_id).await?;
+    let articles_count = get_related_count(&db, article.id, current_user_id).await?;
 
     let articles_dto = ArticlesDto {
         articles,
         articles_count,
+        next_cursor: None,
     };
+    Ok(Json(articles_dto))
+}
+
+/// Axum handler for fetch `articles` for the provided list of slugs in single request.
+/// Missing slugs are skipped, found articles returned in requested order. Optional token used
+/// to determine whether the logged in user is a follower of the author and whether the article
+/// is liked by that user. Returns `articles` object on success, otherwise returns an `api error`,
+/// `InvalidInput` for list longer than MAX_SLUGS_LEN.
+pub async fn articles_by_slugs(
+    maybe_token: Option<Extension<Token>>,
+    State(db): State<DatabaseConnection>,
+    Json(payload): Json<ArticlesBySlugsDto>,
+) -> Result<Json<ArticlesDto>, ApiErr> {
+    if payload.slugs.len() > MAX_SLUGS_LEN {
+        return Err(ApiErr::InvalidInput("Too many slugs"));
+    }
 
+    let articles =
+        get_articles_by_slugs(&db, &payload.slugs, maybe_token.map(|tkn| tkn.id)).await?;
+
+    // Not paginated, so all found articles are returned and make the total
+    let articles_dto = ArticlesDto {
+        articles_count: articles.len() as u64,
+        articles,
+        next_cursor: None,
+    };
     Ok(Json(articles_dto))
 }
 
 /// Axum handler for retrieve information about article with provided title. Optional
 /// token used to determine whether the logged in user is a follower of the article author.
-/// Returns json object with article on success, otherwise returns an `api error`.
+/// `fields` query parameter (comma separated) restricts serialized article to requested fields.
+/// Returns json object with article on success, `ArticleNotExist` for unknown slug, otherwise
+/// returns an `api error`.
 pub async fn get_article(
     State(db): State<DatabaseConnection>,
     maybe_token: Option<Extension<Token>>,
     Path(slug): Path<String>,
-) -> Result<Json<ArticleDto>, ApiErr> {
-    let article = get_article_by_slug(&db, &slug, maybe_token.map(|tkn| tkn.id)).await?;
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<Sparse<ArticleDto>>, ApiErr> {
+    let article = get_article_by_slug(&db, &slug, maybe_token.map(|tkn| tkn.id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
 
     let article_dto = ArticleDto { article };
+    Ok(Json(Sparse::new(article_dto, requested_fields(&params))))
+}
+
+/// Version 2 of `get_article` handler. Article additionally contains author identifier.
+/// Returns json object with article on success, `ArticleNotExist` for unknown slug, otherwise
+/// returns an `api error`.
+pub async fn get_article_v2(
+    State(db): State<DatabaseConnection>,
+    maybe_token: Option<Extension<Token>>,
+    Path(slug): Path<String>,
+) -> Result<Json<ArticleV2Dto>, ApiErr> {
+    let article = get_article_by_slug(&db, &slug, maybe_token.map(|tkn| tkn.id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+
+    let article_dto = ArticleV2Dto {
+        article: article.into(),
+    };
     Ok(Json(article_dto))
 }
 
+/// Axum handler dispatching article retrieval to handler of requested api version
+/// (see ApiVersion for details). Defaults to v1 when version not provided. Anonymous users are
+/// rejected with `Unauthorized` when ANON_LIST_ALLOWED is disabled.
+pub async fn get_article_versioned(
+    maybe_version: Option<Extension<ApiVersion>>,
+    state: State<DatabaseConnection>,
+    maybe_token: Option<Extension<Token>>,
+    path: Path<String>,
+    query: Query<HashMap<String, String>>,
+) -> Result<Response, ApiErr> {
+    require_token_unless_anon_allowed(&maybe_token, anon_list_allowed())?;
+
+    let version = maybe_version.map(|Extension(ver)| ver).unwrap_or_default();
+    match version {
+        ApiVersion::V1 => Ok(get_article(state, maybe_token, path, query)
+            .await?
+            .into_response()),
+        ApiVersion::V2 => Ok(get_article_v2(state, maybe_token, path)
+            .await?
+            .into_response()),
+    }
+}
+
 /// Axum handler for creating article. Only for authenticated users, thus token is required.
 /// Returns json object with article on success, otherwise returns an `api error`.
 pub async fn create_article(
@@ -139,57 +606,167 @@ pub async fn create_article(
     Extension(token): Extension<Token>,
     Json(payload): Json<CreateArticleDto>,
 ) -> Result<Json<ArticleDto>, ApiErr> {
-    let current_user_id = token.id;
-    let input = payload.article;
+    let article = insert_article(&db, token.id, payload.article).await?;
+
+    let article_dto = ArticleDto { article };
+    Ok(Json(article_dto))
+}
+
+/// Axum handler for importing article from Markdown document with YAML front matter (see
+/// parse_markdown_article for details), created the same way as by `create_article`. Only for
+/// authenticated users, thus token is required.
+/// Returns json object with article on success, otherwise returns an `api error`.
+pub async fn import_article(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<Token>,
+    document: String,
+) -> Result<Json<ArticleDto>, ApiErr> {
+    let input = parse_markdown_article(&document)?;
+    let article = insert_article(&db, token.id, input).await?;
+
+    let article_dto = ArticleDto { article };
+    Ok(Json(article_dto))
+}
+
+/// Create `article` with its tags by provided author, shared by create and import handlers.
+/// Article and tags are stored in single transaction, so failed request leaves nothing behind.
+/// Same submission repeated within dedup window returns existing article.
+/// Returns created `article` on success, otherwise returns an `api error`.
+async fn insert_article(
+    db: &DatabaseConnection,
+    current_user_id: Uuid,
+    input: CreateArticle,
+) -> Result<ArticleWithAuthor, ApiErr> {
+    let title = normalize_title(&input.title)?;
+    validate_body(&input.body)?;
+    let body = sanitize_body(input.body)?;
+    // Validate before any write, so rejected request leaves no article behind
+    let tag_list = normalize_tag_list(input.tag_list.unwrap_or_default())?;
+    let created_at = Utc::now().naive_utc();
+
+    // Same submission repeated within dedup window (e.g. double click) returns existing article
+    if let Some(window) = get_article_dedup_window() {
+        let duplicate_id = get_recent_duplicate_article_id(
+            db,
+            current_user_id,
+            &title,
+            &body,
+            created_at - window,
+        )
+        .await?;
+        if let Some(id) = duplicate_id {
+            let article = get_article_by_id(db, id, Some(current_user_id))
+                .await?
+                .ok_or(ApiErr::ArticleNotExist)?;
+            return Ok(article);
+        }
+    }
 
+    let slug = generate_unique_slug(db, &title, None).await?;
     let article_model = article::ActiveModel {
-        id: Set(Uuid::new_v4()),
-        slug: Set(slugify(
-            format! {"{}{}", input.title, current_user_id.simple()},
-        )),
-        title: Set(input.title),
-        description: Set(input.description),
-        body: Set(input.body),
+        id: Set(new_id()),
+        slug: Set(slug),
+        title: Set(title),
+        description: Set(input.description.trim().to_owned()),
+        body: Set(body),
         author_id: Set(current_user_id),
-        ..Default::default()
+        created_at: Set(Some(created_at)),
+        updated_at: Set(Some(created_at)),
+        published: Set(input.published.unwrap_or(true)),
+        deleted_at: Set(None),
     };
 
-    let art_res = repo_create_article(&db, article_model).await?;
-
-    // Insert new tags
-    if let Some(tgs) = &input.tag_list {
-        let tag_models = tgs
-            .iter()
-            .map(|tg| tag::ActiveModel {
-                id: Set(Uuid::new_v4()),
-                tag_name: Set(tg.to_owned()),
+    // Article stored together with all its tags or not at all
+    let article_id = db
+        .transaction::<_, Uuid, DbErr>(|txn| {
+            Box::pin(async move {
+                let art_res = repo_create_article(txn, article_model).await?;
+
+                // Insert new tags and find ids of all requested tags
+                let tags_ids = create_and_get_tags_ids(txn, tag_list).await?;
+
+                let article_tag_models = tags_ids
+                    .iter()
+                    .map(|&id| article_tag::ActiveModel {
+                        tag_id: Set(id),
+                        article_id: Set(art_res.last_insert_id),
+                        ..Default::default()
+                    })
+                    .collect::<Vec<article_tag::ActiveModel>>();
+
+                create_article_tags(txn, article_tag_models).await?;
+                Ok(art_res.last_insert_id)
             })
-            .collect();
+        })
+        .await?;
 
-        create_tags(&db, tag_models).await?;
-    };
+    inserted_article(get_article_by_id(db, article_id, Some(current_user_id)).await?)
+}
 
-    // Find existing tag ids
-    let tags_ids = get_tags_ids(&db, input.tag_list.clone().unwrap_or_default()).await?;
+/// Unwrap article refetched right after insert. Missing article means database
+/// inconsistency, so it is reported as internal `database error`, not `ArticleNotExist`.
+fn inserted_article(article: Option<ArticleWithAuthor>) -> Result<ArticleWithAuthor, ApiErr> {
+    article.ok_or(ApiErr::DbErr(DbErr::RecordNotFound(
+        "Inserted article not found".to_string(),
+    )))
+}
 
-    let article_tag_models = tags_ids
-        .iter()
-        .map(|&id| article_tag::ActiveModel {
-            tag_id: Set(id),
-            article_id: Set(art_res.last_insert_id),
-        })
-        .collect::<Vec<article_tag::ActiveModel>>();
+/// Parse Markdown document into article data. Document starts with YAML front matter between
+/// `---` lines, containing required `title` and `description`, optional `tags` list and
+/// `published` flag, followed by article body.
+/// Returns article data on success, otherwise returns an `api error`.
+fn parse_markdown_article(document: &str) -> Result<CreateArticle, ApiErr> {
+    let document = document.trim_start_matches('\u{feff}');
+    let mut lines = document.split_inclusive('\n');
+    if lines.next().map(str::trim_end) != Some("---") {
+        return Err(ApiErr::InvalidInput("Front matter is required"));
+    }
 
-    create_article_tags(&db, article_tag_models).await?;
+    let mut front_matter = String::new();
+    let mut closed = false;
+    for line in lines.by_ref() {
+        if line.trim_end() == "---" {
+            closed = true;
+            break;
+        }
+        front_matter.push_str(line);
+    }
+    if !closed {
+        return Err(ApiErr::InvalidInput("Front matter is not closed"));
+    }
+    let body = lines.collect::<String>().trim().to_owned();
 
-    let article = get_article_by_id(&db, art_res.last_insert_id, Some(current_user_id)).await?;
+    let front_matter: FrontMatter = match front_matter.trim().is_empty() {
+        true => FrontMatter::default(),
+        false => serde_yaml::from_str(&front_matter)
+            .map_err(|_| ApiErr::InvalidInput("Invalid front matter"))?,
+    };
 
-    let article_dto = ArticleDto { article };
-    Ok(Json(article_dto))
+    Ok(CreateArticle {
+        title: front_matter
+            .title
+            .ok_or(ApiErr::InvalidInput("Front matter title is required"))?,
+        description: front_matter
+            .description
+            .ok_or(ApiErr::InvalidInput("Front matter description is required"))?,
+        body,
+        tag_list: front_matter.tags,
+        published: front_matter.published,
+    })
 }
 
-/// Axum handler for updating article. Only for authenticated users, thus token is required.
-/// Returns json object with article on success, otherwise returns an `api error`.
+/// Return ARTICLE_DEDUP_WINDOW_SECS from environment varibles, dedup disabled when not set or zero
+fn get_article_dedup_window() -> Option<Duration> {
+    env::var(ARTICLE_DEDUP_WINDOW_SECS)
+        .ok()
+        .and_then(|secs| secs.trim().parse::<i64>().ok())
+        .filter(|&secs| secs > 0)
+        .map(Duration::seconds)
+}
+
+/// Axum handler for updating article. Only author of article allowed to update it, thus token
+/// is required. Returns json object with article on success, `Forbidden` for other users,
+/// otherwise returns an `api error`.
 pub async fn update_article(
     Path(slug): Path<String>,
     State(db): State<DatabaseConnection>,
@@ -199,120 +776,422 @@ pub async fn update_article(
     let current_user_id = token.id;
     let input = payload.article;
 
-    let updated_article = get_article_model_by_slug(&db, &slug)
+    let updated_article = get_article_model_by_slug(&db, &slug, Some(current_user_id))
         .await?
         .ok_or(ApiErr::ArticleNotExist)?;
+    if updated_article.author_id != current_user_id {
+        return Err(ApiErr::Forbidden);
+    }
 
-    let mut article_model: article::ActiveModel = updated_article.into();
+    let mut patch = ArticlePatch::default();
 
-    if input.title.is_some() {
-        article_model.slug = Set(slugify(input.title.as_ref().unwrap()));
-        article_model.title = Set(input.title.to_owned().unwrap());
+    if let Some(title) = &input.title {
+        let title = normalize_title(title)?;
+        patch.slug = Some(generate_unique_slug(&db, &title, Some(updated_article.id)).await?);
+        patch.title = Some(title);
     }
-    if input.description.is_some() {
-        article_model.description = Set(input.description.to_owned().unwrap());
+    if let Some(description) = &input.description {
+        patch.description = Some(description.trim().to_owned());
     }
-    if input.body.is_some() {
-        article_model.body = Set(input.body.to_owned().unwrap());
+    if let Some(body) = &input.body {
+        validate_body(body)?;
+        patch.body = Some(sanitize_body(body.to_owned())?);
     }
+    patch.published = input.published;
 
     if [&input.title, &input.description, &input.body]
         .iter()
         .any(|fld| fld.is_some())
+        || input.tag_list.is_some()
     {
-        let time = DateTime::from_timestamp_millis(Local::now().timestamp_millis()).unwrap();
-        article_model.updated_at = Set(Some(time));
+        patch.updated_at = Some(Utc::now().naive_utc());
     }
 
-    let art_res = repo_update_article(&db, article_model).await?;
+    // Validate tags before article is changed
+    let tag_list = input.tag_list.map(normalize_tag_list).transpose()?;
 
-    let article = get_article_by_id(&db, art_res.id, Some(current_user_id)).await?;
+    // Article changed together with its tags or not at all
+    let article_id = db
+        .transaction::<_, Uuid, DbErr>(|txn| {
+            Box::pin(async move {
+                let art_res = patch_article(txn, updated_article.id, patch).await?;
 
-    let article_dto = ArticleDto { article };
-    Ok(Json(article_dto))
-}
+                if let Some(tag_list) = tag_list {
+                    replace_article_tags(txn, art_res.id, tag_list).await?;
+                }
+                Ok(art_res.id)
+            })
+        })
+        .await?;
 
-/// Axum handler for delete article by provided article slug. Only for authenticated users,
-/// thus token is required. Returns empty json object on success, otherwise returns an `api error`.
-pub async fn delete_article(
-    Path(slug): Path<String>,
-    State(db): State<DatabaseConnection>,
-) -> Result<Json<()>, ApiErr> {
-    let deleted_article = get_article_model_by_slug(&db, &slug)
+    let article = get_article_by_id(&db, article_id, Some(current_user_id))
         .await?
         .ok_or(ApiErr::ArticleNotExist)?;
 
-    let article_model: article::ActiveModel = deleted_article.into();
-
-    repo_delete_article(&db, article_model).await?;
-
-    Ok(Json(()))
+    let article_dto = ArticleDto { article };
+    Ok(Json(article_dto))
 }
 
-/// Axum handler for favorite article by logged user.
+/// Axum handler for move article to another author (provided by username). Intended for
+/// moderation, only admin allowed to transfer articles (see require_admin).
 /// Returns json object with article on success, otherwise returns an `api error`.
-pub async fn favorite_article(
+pub async fn transfer_article_author(
     Path(slug): Path<String>,
-    Extension(token): Extension<Token>,
     State(db): State<DatabaseConnection>,
+    Extension(token): Extension<Token>,
+    Extension(user_cache): Extension<Arc<UserCache>>,
+    Json(payload): Json<TransferArticleDto>,
 ) -> Result<Json<ArticleDto>, ApiErr> {
-    let current_user_id = token.id;
-
-    let finded = get_article_model_by_slug(&db, &slug)
+    let transferred_article = get_article_model_by_slug(&db, &slug, Some(token.id))
         .await?
         .ok_or(ApiErr::ArticleNotExist)?;
+    let new_author = get_user_by_username(&db, &payload.author.username)
+        .await?
+        .ok_or(ApiErr::UserNotExist)?;
 
-    let favorite_article_model = favorited_article::ActiveModel {
-        article_id: Set(finded.id),
-        user_id: Set(current_user_id),
-    };
-
-    repo_favorite_article(&db, favorite_article_model).await?;
+    let art_res = transfer_article(&db, &user_cache, transferred_article.id, new_author.id)
+        .await
+        .map_err(|err| match err {
+            DbErr::RecordNotFound(_) => ApiErr::UserNotExist,
+            err => ApiErr::DbErr(err),
+        })?;
 
-    let article = get_article_by_id(&db, finded.id, Some(current_user_id)).await?;
+    let article = get_article_by_id(&db, art_res.id, Some(token.id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
 
     let article_dto = ArticleDto { article };
     Ok(Json(article_dto))
 }
 
-/// Axum handler for unfavorite article by logged user.
-/// Returns json object with article on success, otherwise returns an `api error`.
-pub async fn unfavorite_article(
-    Path(slug): Path<String>,
-    Extension(token): Extension<Token>,
-    State(db): State<DatabaseConnection>,
-) -> Result<Json<ArticleDto>, ApiErr> {
+/// Replace tags of article with provided `tag list`: attach new tags (creating not existing
+/// ones) and detach tags missing from the list, detached tags are deleted when orphaned.
+/// Returns nothing on success, otherwise returns an `database error`.
+async fn replace_article_tags<C: ConnectionTrait>(
+    db: &C,
+    article_id: Uuid,
+    tag_list: Vec<String>,
+) -> Result<(), DbErr> {
+    let existing_ids = get_article_tag_ids(db, article_id).await?;
+    let tags_ids = create_and_get_tags_ids(db, tag_list).await?;
+
+    for &tag_id in existing_ids.iter().filter(|id| !tags_ids.contains(id)) {
+        remove_article_tag(db, article_id, tag_id).await?;
+        delete_tag_if_orphaned(db, tag_id).await?;
+    }
+
+    let article_tag_models = tags_ids
+        .iter()
+        .filter(|id| !existing_ids.contains(id))
+        .map(|&id| article_tag::ActiveModel {
+            tag_id: Set(id),
+            article_id: Set(article_id),
+            ..Default::default()
+        })
+        .collect::<Vec<article_tag::ActiveModel>>();
+    create_article_tags(db, article_tag_models).await?;
+
+    Ok(())
+}
+
+/// Trim `title` and collapse internal runs of whitespace into single space.
+/// Returns normalized title on success, otherwise returns `InvalidInput` error for empty title.
+fn normalize_title(title: &str) -> Result<String, ApiErr> {
+    let title = title.split_whitespace().collect::<Vec<&str>>().join(" ");
+    if title.is_empty() {
+        return Err(ApiErr::InvalidInput("Title must not be empty"));
+    }
+    Ok(title)
+}
+
+/// Check length of article `body` (in characters) against APP_MAX_BODY_LENGTH.
+/// Returns `InvalidInput` error for too long body.
+fn validate_body(body: &str) -> Result<(), ApiErr> {
+    if body.chars().count() > get_max_body_length() {
+        return Err(ApiErr::InvalidInput("Body too long"));
+    }
+    Ok(())
+}
+
+/// Trim tag names of `tag list`. Shared by article creation and editing of article tags.
+/// Returns trimmed tag names on success, otherwise returns `InvalidInput` error for empty
+/// (or whitespace only) tag name and for list longer than APP_MAX_TAGS_PER_ARTICLE.
+fn normalize_tag_list(tag_list: Vec<String>) -> Result<Vec<String>, ApiErr> {
+    if tag_list.len() > get_max_tags_per_article() {
+        return Err(ApiErr::InvalidInput("Too many tags"));
+    }
+    tag_list
+        .iter()
+        .map(|tag| match tag.trim() {
+            "" => Err(ApiErr::InvalidInput("Tag must not be empty")),
+            tag => Ok(tag.to_owned()),
+        })
+        .collect()
+}
+
+/// Axum handler for delete article by provided article slug. Only author of article allowed
+/// to delete it, thus token is required. Returns empty json object on success, `Forbidden`
+/// for other users, otherwise returns an `api error`.
+pub async fn delete_article(
+    Path(slug): Path<String>,
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<Token>,
+) -> Result<Json<()>, ApiErr> {
+    let deleted_article = get_article_model_by_slug(&db, &slug, Some(token.id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+    if deleted_article.author_id != token.id {
+        return Err(ApiErr::Forbidden);
+    }
+
+    let article_model: article::ActiveModel = deleted_article.into();
+
+    repo_delete_article(&db, article_model).await?;
+
+    Ok(Json(()))
+}
+
+/// Axum handler for adding single tag to article. Only author of article allowed to add tags.
+/// Already attached tag is ignored. Returns json object with article on success, otherwise
+/// returns an `api error`.
+pub async fn add_article_tag(
+    Path(slug): Path<String>,
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<Token>,
+    Json(payload): Json<AddArticleTagDto>,
+) -> Result<Json<ArticleDto>, ApiErr> {
+    let current_user_id = token.id;
+    let tag_name = normalize_tag_list(vec![payload.tag])?.remove(0);
+
+    let finded = get_article_model_by_slug(&db, &slug, Some(current_user_id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+    if finded.author_id != current_user_id {
+        return Err(ApiErr::Forbidden);
+    }
+
+    let tag_id = get_or_create_tag(&db, &tag_name).await?;
+    repo_add_article_tag(&db, finded.id, tag_id).await?;
+
+    let article = get_article_by_id(&db, finded.id, Some(current_user_id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+
+    let article_dto = ArticleDto { article };
+    Ok(Json(article_dto))
+}
+
+/// Axum handler for removing single tag from article. Only author of article allowed to remove
+/// tags. Tag not attached to article is ignored, tag itself is deleted when no longer attached
+/// to any article.
+/// Returns json object with article on success, otherwise returns an `api error`.
+pub async fn delete_article_tag(
+    Path((slug, tag_name)): Path<(String, String)>,
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<Token>,
+) -> Result<Json<ArticleDto>, ApiErr> {
+    let current_user_id = token.id;
+
+    let finded = get_article_model_by_slug(&db, &slug, Some(current_user_id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+    if finded.author_id != current_user_id {
+        return Err(ApiErr::Forbidden);
+    }
+
+    let tag_id = get_tags_ids(&db, vec![tag_name.trim().to_owned()])
+        .await?
+        .into_iter()
+        .next();
+
+    if let Some(tag_id) = tag_id {
+        let del_res = remove_article_tag(&db, finded.id, tag_id).await?;
+        if del_res.rows_affected > 0 {
+            delete_tag_if_orphaned(&db, tag_id).await?;
+        }
+    }
+
+    let article = get_article_by_id(&db, finded.id, Some(current_user_id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+
+    let article_dto = ArticleDto { article };
+    Ok(Json(article_dto))
+}
+
+/// Axum handler for favorite article by logged user. New favorites count pushed to
+/// WebSocket clients of the article (see FavoritesHub). Author of the article is notified
+/// about new favorite of another user (see NotificationsHub).
+/// Returns json object with article on success, otherwise returns an `api error`.
+pub async fn favorite_article(
+    Path(slug): Path<String>,
+    Extension(token): Extension<Token>,
+    Extension(hub): Extension<Arc<FavoritesHub>>,
+    Extension(notifications): Extension<Arc<NotificationsHub>>,
+    Extension(user_cache): Extension<Arc<UserCache>>,
+    State(db): State<DatabaseConnection>,
+) -> Result<Json<ArticleDto>, ApiErr> {
+    let current_user_id = token.id;
+
+    let finded = get_article_model_by_slug(&db, &slug, Some(current_user_id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+
+    let favorite_article_model = favorited_article::ActiveModel {
+        article_id: Set(finded.id),
+        user_id: Set(current_user_id),
+        ..Default::default()
+    };
+
+    let outcome = repo_favorite_article(&db, favorite_article_model).await?;
+
+    let article = get_article_by_id(&db, finded.id, Some(current_user_id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+    hub.publish(finded.id, article.favorites_count);
+
+    if let FavoriteOutcome::NotifyAuthor { author_id } = outcome {
+        let user = get_user_by_id(&db, &user_cache, current_user_id)
+            .await?
+            .ok_or(ApiErr::UserNotExist)?;
+        let notification = Notification::ArticleFavorited {
+            slug: article.slug.clone(),
+            username: user.username,
+        };
+        notifications.publish(author_id, notification);
+    }
+
+    let article_dto = ArticleDto { article };
+    Ok(Json(article_dto))
+}
+
+/// Axum handler for unfavorite article by logged user. New favorites count pushed to
+/// WebSocket clients of the article (see FavoritesHub).
+/// Returns json object with article on success, otherwise returns an `api error`.
+pub async fn unfavorite_article(
+    Path(slug): Path<String>,
+    Extension(token): Extension<Token>,
+    Extension(hub): Extension<Arc<FavoritesHub>>,
+    State(db): State<DatabaseConnection>,
+) -> Result<Json<ArticleDto>, ApiErr> {
     let current_user_id = token.id;
 
-    let finded = get_article_model_by_slug(&db, &slug)
+    let finded = get_article_model_by_slug(&db, &slug, Some(current_user_id))
         .await?
         .ok_or(ApiErr::ArticleNotExist)?;
 
     let favorite_article_model = favorited_article::ActiveModel {
         article_id: Set(finded.id),
         user_id: Set(current_user_id),
+        ..Default::default()
     };
 
     repo_unfavorite_article(&db, favorite_article_model).await?;
 
-    let article = get_article_by_id(&db, finded.id, Some(current_user_id)).await?;
+    let article = get_article_by_id(&db, finded.id, Some(current_user_id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+    hub.publish(finded.id, article.favorites_count);
 
     let article_dto = ArticleDto { article };
     Ok(Json(article_dto))
 }
 
 /// Struct describing JSON object, returned by handler. Contains list of articles.
+/// Length of `articles` never exceeds requested limit, while `articles_count` is the total
+/// number of matching articles, e.g. `limit=2` against 5 matching articles gives 2 and 5.
+/// Empty result is serialized as empty list, never as `null`.
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ArticlesDto {
     articles: Vec<ArticleWithAuthor>,
     articles_count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<String>,
+}
+
+impl From<Page<ArticleWithAuthor>> for ArticlesDto {
+    fn from(page: Page<ArticleWithAuthor>) -> Self {
+        ArticlesDto {
+            articles: page.items,
+            articles_count: page.total,
+            next_cursor: None,
+        }
+    }
 }
 
-/// Struct describing JSON object, returned by handler. Contains optional article.
+/// Struct describing JSON object, returned by handler. Contains article.
 #[derive(Debug, Serialize)]
 pub struct ArticleDto {
-    article: Option<ArticleWithAuthor>,
+    article: ArticleWithAuthor,
+}
+
+/// Struct wrapping JSON object, returned by handler. Serialized object contains only requested
+/// fields of its article (or articles), unknown field names are ignored. All fields are
+/// serialized when none requested.
+#[derive(Debug)]
+pub struct Sparse<T> {
+    inner: T,
+    fields: Option<Vec<String>>,
+}
+
+impl<T> Sparse<T> {
+    pub fn new(inner: T, fields: Option<Vec<String>>) -> Self {
+        Self { inner, fields }
+    }
+}
+
+impl<T> Deref for Sparse<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T: Serialize> Serialize for Sparse<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Some(fields) = &self.fields else {
+            return self.inner.serialize(serializer);
+        };
+
+        let mut value = serde_json::to_value(&self.inner).map_err(S::Error::custom)?;
+        let retain_fields = |article: &mut Value| {
+            if let Value::Object(map) = article {
+                map.retain(|key, _| fields.contains(key));
+            }
+        };
+        if let Some(article) = value.get_mut("article") {
+            retain_fields(article);
+        }
+        if let Some(Value::Array(articles)) = value.get_mut("articles") {
+            articles.iter_mut().for_each(retain_fields);
+        }
+
+        value.serialize(serializer)
+    }
+}
+
+/// Struct describing JSON object, returned by v2 handler.
+#[derive(Debug, Serialize)]
+pub struct ArticleV2Dto {
+    article: ArticleWithAuthorV2,
+}
+
+/// Article shape of api v2, extends v1 shape with author identifier.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArticleWithAuthorV2 {
+    #[serde(flatten)]
+    article: ArticleWithAuthor,
+    author_id: Uuid,
+}
+
+impl From<ArticleWithAuthor> for ArticleWithAuthorV2 {
+    fn from(article: ArticleWithAuthor) -> Self {
+        let author_id = article.author_id;
+        Self { article, author_id }
+    }
 }
 
 /// Struct describing JSON object from article creation request. Contains article.
@@ -328,6 +1207,16 @@ struct CreateArticle {
     description: String,
     body: String,
     tag_list: Option<Vec<String>>,
+    published: Option<bool>,
+}
+
+/// Struct describing YAML front matter of imported Markdown article.
+#[derive(Debug, Default, Deserialize)]
+struct FrontMatter {
+    title: Option<String>,
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+    published: Option<bool>,
 }
 
 /// Struct describing JSON object from change article data request. Contains article data.
@@ -337,16 +1226,113 @@ pub struct UpdateArticleDto {
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct UpdateArticle {
     title: Option<String>,
     description: Option<String>,
     body: Option<String>,
+    published: Option<bool>,
+    tag_list: Option<Vec<String>>,
+}
+
+/// Struct describing JSON object from transfer article request. Contains new author.
+#[derive(Debug, Deserialize)]
+pub struct TransferArticleDto {
+    author: TransferArticleAuthor,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct TransferArticleAuthor {
+    username: String,
+}
+
+/// Struct describing JSON object from add tag to article request. Contains tag name.
+#[derive(Debug, Deserialize)]
+pub struct AddArticleTagDto {
+    tag: String,
+}
+
+/// Struct describing JSON object from fetch articles by slugs request. Contains list of slugs.
+#[derive(Debug, Deserialize)]
+pub struct ArticlesBySlugsDto {
+    slugs: Vec<String>,
+}
+
+#[cfg(test)]
+mod test_anon_list_allowed {
+    use super::{get_article_versioned, require_token_unless_anon_allowed, ANON_LIST_ALLOWED};
+    use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::middleware::auth::Token;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestDataBuilder, TestErr,
+    };
+    use axum::{
+        extract::{Query, State},
+        http::StatusCode,
+        Extension,
+    };
+    use serial_test::serial;
+    use std::{collections::HashMap, env};
+    use uuid::Uuid;
+
+    fn token() -> Option<Extension<Token>> {
+        Some(Extension(Token {
+            exp: 35,
+            id: Uuid::new_v4(),
+        }))
+    }
+
+    #[test]
+    fn anonymous_allowed() {
+        assert_eq!(require_token_unless_anon_allowed(&None, true), Ok(()));
+        assert_eq!(require_token_unless_anon_allowed(&token(), true), Ok(()));
+    }
+
+    #[test]
+    fn anonymous_denied() {
+        assert_eq!(
+            require_token_unless_anon_allowed(&None, false),
+            Err(ApiErr::Unauthorized)
+        );
+        assert_eq!(require_token_unless_anon_allowed(&token(), false), Ok(()));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn anonymous_allowed_by_default() -> Result<(), TestErr> {
+        env::remove_var(ANON_LIST_ALLOWED);
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let response = get_article_versioned(
+            None,
+            State(connection),
+            None,
+            Path("title1".to_owned()),
+            Query(HashMap::new()),
+        )
+        .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test_list_articles {
-    use super::list_articles;
+    use super::{list_articles, DEFAULT_ARTICLE_SORT};
+    use crate::api::limits::PageConfig;
     use crate::{
+        api::error::ApiErr,
         middleware::auth::Token,
         tests::{
             Operation::{Insert, Migration},
@@ -355,10 +1341,14 @@ mod test_list_articles {
     };
     use axum::extract::Query;
     use axum::{extract::State, Extension, Json};
+    use chrono::Duration;
     use dotenvy::dotenv;
-    use entity::entities::user;
+    use entity::entities::{article, user};
+    use sea_orm::{ActiveModelTrait, Set};
+    use serde_json::json;
+    use serial_test::serial;
     use std::collections::HashMap;
-    use std::vec;
+    use std::{env, vec};
 
     #[tokio::test]
     async fn get_existing_articles() -> Result<(), TestErr> {
@@ -408,8 +1398,13 @@ mod test_list_articles {
         .into_iter()
         .collect();
 
-        let result =
-            list_articles(Query(params), Some(Extension(token)), State(connection)).await?;
+        let result = list_articles(
+            Query(params.into_iter().collect()),
+            Some(Extension(token)),
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
         let Json(result) = result;
 
         assert_eq!(result.articles.len(), 3);
@@ -419,171 +1414,2774 @@ mod test_list_articles {
     }
 
     #[tokio::test]
-    async fn get_no_articles() -> Result<(), TestErr> {
+    async fn get_sparse_articles() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new()
-            .users(Migration)
-            .articles(Migration)
-            .favorited_articles(Migration)
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1]))
             .tags(Migration)
             .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
             .build()
             .await?;
 
-        let params: HashMap<String, String> = HashMap::new();
+        let params: HashMap<String, String> = [("fields".to_owned(), "slug".to_owned())]
+            .into_iter()
+            .collect();
 
-        let result = list_articles(Query(params), None, State(connection)).await?;
-        let Json(result) = result;
+        let Json(result) = list_articles(
+            Query(params.into_iter().collect()),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let value = serde_json::to_value(result).unwrap();
 
-        assert_eq!(result.articles.len(), 0);
-        assert_eq!(result.articles_count, 0);
+        assert_eq!(
+            value,
+            json!({"articles": [{"slug": "title2"}, {"slug": "title1"}], "articlesCount": 2})
+        );
 
         Ok(())
     }
-}
-
-#[cfg(test)]
-mod test_feed_articles {
-    use super::feed_articles;
-    use crate::{
-        middleware::auth::Token,
-        tests::{
-            Operation::{Insert, Migration},
-            TestData, TestDataBuilder, TestErr,
-        },
-    };
-    use axum::extract::Query;
-    use axum::{extract::State, Extension, Json};
-    use dotenvy::dotenv;
-    use entity::entities::user;
-    use std::collections::HashMap;
-    use std::vec;
 
     #[tokio::test]
-    async fn get_existing_articles() -> Result<(), TestErr> {
-        dotenv().expect(".env file not found");
-        let (connection, TestData { users, .. }) = TestDataBuilder::new()
-            .users(Insert(6))
-            .articles(Insert(vec![1, 2, 3, 4, 5, 1, 2, 3, 4, 5]))
-            .favorited_articles(Migration)
+    async fn count_ignores_limit() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
             .tags(Migration)
             .article_tags(Migration)
-            .followers(Insert(vec![(1, 6), (2, 6), (3, 6), (4, 6), (3, 5)]))
+            .favorited_articles(Migration)
+            .followers(Migration)
             .build()
             .await?;
 
-        let current_user: user::Model = users.unwrap().into_iter().last().unwrap();
+        let params: HashMap<String, String> =
+            [("limit".to_owned(), "2".to_owned())].into_iter().collect();
 
-        let token = Token {
-            exp: 35,
-            id: current_user.id,
-        };
+        let Json(result) = list_articles(
+            Query(params.into_iter().collect()),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+
+        assert_eq!(result.articles.len(), 2);
+        assert_eq!(result.articles_count, 5);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn repeated_tag_params() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (1, 2), (2, 1)]))
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let params = vec![
+            ("tag".to_owned(), "tag_name1".to_owned()),
+            ("tag".to_owned(), "tag_name2".to_owned()),
+        ];
+
+        let Json(result) = list_articles(
+            Query(params),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let value = serde_json::to_value(result).unwrap();
+
+        assert_eq!(value["articlesCount"], 1);
+        assert_eq!(value["articles"][0]["slug"], "title1");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tag_names_trimmed() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (1, 2), (2, 1)]))
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let params = vec![
+            ("tag".to_owned(), " tag_name1 , ,tag_name2 ".to_owned()),
+            ("tag".to_owned(), " ".to_owned()),
+        ];
+
+        let Json(result) = list_articles(
+            Query(params),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let value = serde_json::to_value(result).unwrap();
+
+        assert_eq!(value["articlesCount"], 1);
+        assert_eq!(value["articles"][0]["slug"], "title1");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn walk_pages_by_cursor() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let params = |cursor: &str| {
+            vec![
+                ("limit".to_owned(), "2".to_owned()),
+                ("offset".to_owned(), "1".to_owned()),
+                ("cursor".to_owned(), cursor.to_owned()),
+            ]
+        };
+
+        let Json(first) = list_articles(
+            Query(params("")),
+            None,
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let first = serde_json::to_value(first).unwrap();
+        let cursor = first["nextCursor"].as_str().unwrap();
+        let Json(second) = list_articles(
+            Query(params(cursor)),
+            None,
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let second = serde_json::to_value(second).unwrap();
+
+        let slugs = |value: &serde_json::Value| -> Vec<String> {
+            value["articles"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|artcl| artcl["slug"].as_str().unwrap().to_owned())
+                .collect()
+        };
+        let (first_slugs, second_slugs) = (slugs(&first), slugs(&second));
+        assert_eq!(first_slugs.len(), 2);
+        assert_eq!(second_slugs.len(), 1);
+        assert!(first_slugs.iter().all(|slug| !second_slugs.contains(slug)));
+        assert_eq!(second["articlesCount"], 3);
+        assert!(second.get("nextCursor").is_none());
+
+        let result = list_articles(
+            Query(params("not a cursor")),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(ApiErr::InvalidInput("Invalid cursor"))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_no_articles() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Migration)
+            .articles(Migration)
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let params: HashMap<String, String> = HashMap::new();
+
+        let result = list_articles(
+            Query(params.into_iter().collect()),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let Json(result) = result;
+
+        assert_eq!(result.articles.len(), 0);
+        assert_eq!(result.articles_count, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn any_of_tags() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (1, 2), (2, 2)]))
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
 
         let params: HashMap<String, String> = [
-            ("limit".to_owned(), "5".to_owned()),
-            ("offset".to_owned(), "5".to_owned()),
+            ("tag".to_owned(), "tag_name1,tag_name2".to_owned()),
+            ("tagMatch".to_owned(), "any".to_owned()),
         ]
         .into_iter()
         .collect();
 
-        let result = feed_articles(Query(params), Extension(token), State(connection)).await?;
+        let result = list_articles(
+            Query(params.into_iter().collect()),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
         let Json(result) = result;
 
-        assert_eq!(result.articles.len(), 3);
-        assert_eq!(result.articles_count, 8);
+        assert_eq!(result.articles.len(), 2);
+        assert_eq!(result.articles_count, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tag_and_author_count_parity() -> Result<(), TestErr> {
+        // 30 articles alternating between two authors, every third one tagged twice
+        let authors: Vec<usize> = (0..30).map(|idx| idx % 2 + 1).collect();
+        let article_tags: Vec<(usize, usize)> = (1..=30)
+            .flat_map(|article| {
+                let mut rels = vec![(article, 1)];
+                if article % 3 == 0 {
+                    rels.push((article, 2));
+                }
+                rels
+            })
+            .collect();
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(authors))
+            .tags(Insert(2))
+            .article_tags(Insert(article_tags))
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        for (tag, expected) in [("tag_name1", 15), ("tag_name2", 5)] {
+            let paged: HashMap<String, String> = [
+                ("tag".to_owned(), tag.to_owned()),
+                ("author".to_owned(), "username1".to_owned()),
+                ("limit".to_owned(), "4".to_owned()),
+            ]
+            .into_iter()
+            .collect();
+            let mut unpaged = paged.clone();
+            unpaged.insert("limit".to_owned(), "100".to_owned());
+
+            let Json(paged) = list_articles(
+                Query(paged.into_iter().collect()),
+                None,
+                State(connection.clone()),
+                Extension(PageConfig::default()),
+            )
+            .await?;
+            let Json(unpaged) = list_articles(
+                Query(unpaged.into_iter().collect()),
+                None,
+                State(connection.clone()),
+                Extension(PageConfig::default()),
+            )
+            .await?;
+
+            assert_eq!(paged.articles_count, expected);
+            assert_eq!(paged.articles_count, unpaged.articles.len() as u64);
+            assert_eq!(unpaged.articles_count, unpaged.articles.len() as u64);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn configured_default_sort() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        // Oldest article was updated most recently
+        let oldest = articles.unwrap().into_iter().next().unwrap();
+        let updated_at = oldest.updated_at.unwrap() + Duration::hours(1);
+        let mut model: article::ActiveModel = oldest.into();
+        model.updated_at = Set(Some(updated_at));
+        model.update(&connection).await?;
+
+        env::set_var(DEFAULT_ARTICLE_SORT, "created");
+        let Json(result) = list_articles(
+            Query(Vec::new()),
+            None,
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        assert_eq!(result.articles[0].slug, "title3");
+
+        let params: HashMap<String, String> = [("sort".to_owned(), "updated".to_owned())]
+            .into_iter()
+            .collect();
+        let Json(result) = list_articles(
+            Query(params.into_iter().collect()),
+            None,
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        assert_eq!(result.articles[0].slug, "title1");
+
+        env::remove_var(DEFAULT_ARTICLE_SORT);
+        let Json(result) = list_articles(
+            Query(Vec::new()),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        assert_eq!(result.articles[0].slug, "title1");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn favorited_by_non_existing_user() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1, 2]))
+            .favorited_articles(Insert(vec![(1, 2), (3, 1)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let params: HashMap<String, String> = [("favorited".to_owned(), "ghost".to_owned())]
+            .into_iter()
+            .collect();
+
+        let result = list_articles(
+            Query(params.into_iter().collect()),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let Json(result) = result;
+
+        assert_eq!(result.articles.len(), 0);
+        assert_eq!(result.articles_count, 0);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_drafts {
+    use super::{
+        create_article, feed_articles, get_article, list_articles, list_drafts, CreateArticle,
+        CreateArticleDto,
+    };
+    use crate::api::extract::Path;
+    use crate::api::limits::PageConfig;
+    use crate::{
+        api::error::ApiErr,
+        middleware::auth::Token,
+        tests::{Operation::Insert, TestData, TestDataBuilder, TestErr},
+    };
+    use axum::extract::Query;
+    use axum::{extract::State, Extension, Json};
+    use dotenvy::dotenv;
+    use entity::entities::article;
+    use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+    use std::collections::HashMap;
+    use std::vec;
+
+    /// Two users following each other, first one has published article `title1`
+    /// and draft `title2`.
+    async fn with_draft() -> Result<(DatabaseConnection, Token, Token), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1]))
+            .tags(Insert(1))
+            .article_tags(Insert(vec![(1, 1)]))
+            .favorited_articles(Insert(vec![(1, 1)]))
+            .followers(Insert(vec![(1, 2), (2, 1)]))
+            .build()
+            .await?;
+
+        let draft = articles.unwrap().into_iter().nth(1).unwrap();
+        let mut model: article::ActiveModel = draft.into();
+        model.published = Set(false);
+        model.update(&connection).await?;
+
+        let users = users.unwrap();
+        let author = Token {
+            exp: 35,
+            id: users[0].id,
+        };
+        let reader = Token {
+            exp: 35,
+            id: users[1].id,
+        };
+        Ok((connection, author, reader))
+    }
+
+    fn slugs(articles: &super::ArticlesDto) -> Vec<&str> {
+        articles
+            .articles
+            .iter()
+            .map(|art| art.slug.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn hidden_from_global_list() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, _, reader) = with_draft().await?;
+
+        let Json(result) = list_articles(
+            Query(Vec::new()),
+            None,
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        assert_eq!(slugs(&result), vec!["title1"]);
+        assert_eq!(result.articles_count, 1);
+
+        let Json(result) = list_articles(
+            Query(Vec::new()),
+            Some(Extension(reader.clone())),
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        assert_eq!(slugs(&result), vec!["title1"]);
+
+        let Json(result) = feed_articles(
+            Query(HashMap::new()),
+            Extension(reader),
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        assert_eq!(slugs(&result), vec!["title1"]);
+        assert_eq!(result.articles_count, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn visible_to_author() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, author, reader) = with_draft().await?;
+
+        let Json(result) = list_articles(
+            Query(Vec::new()),
+            Some(Extension(author.clone())),
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        assert_eq!(slugs(&result), vec!["title2", "title1"]);
+        assert_eq!(result.articles_count, 2);
+        assert!(!result.articles[0].published);
+
+        let Json(result) = list_drafts(
+            Query(HashMap::new()),
+            Extension(author),
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        assert_eq!(slugs(&result), vec!["title2"]);
+
+        let Json(result) = list_drafts(
+            Query(HashMap::new()),
+            Extension(reader),
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        assert!(result.articles.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn not_found_by_slug_for_others() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, author, reader) = with_draft().await?;
+
+        let Json(result) = get_article(
+            State(connection.clone()),
+            Some(Extension(author)),
+            Path("title2".to_owned()),
+            Query(HashMap::new()),
+        )
+        .await?;
+        assert_eq!(result.article.slug, "title2");
+
+        for maybe_token in [Some(Extension(reader)), None] {
+            let result = get_article(
+                State(connection.clone()),
+                maybe_token,
+                Path("title2".to_owned()),
+                Query(HashMap::new()),
+            )
+            .await;
+            assert_eq!(result.err(), Some(ApiErr::ArticleNotExist));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn create_draft() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, author, _) = with_draft().await?;
+
+        let article_data = CreateArticleDto {
+            article: CreateArticle {
+                title: "new draft".to_owned(),
+                description: "description".to_owned(),
+                body: "body".to_owned(),
+                tag_list: None,
+                published: Some(false),
+            },
+        };
+        let Json(result) = create_article(
+            State(connection.clone()),
+            Extension(author.clone()),
+            Json(article_data),
+        )
+        .await?;
+        assert!(!result.article.published);
+
+        let Json(result) = list_drafts(
+            Query(HashMap::new()),
+            Extension(author.clone()),
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        assert_eq!(result.articles.len(), 2);
+
+        let params: HashMap<String, String> =
+            [("limit".to_owned(), "1".to_owned())].into_iter().collect();
+        let Json(result) = list_drafts(
+            Query(params),
+            Extension(author),
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        // Total is not affected by limit
+        assert_eq!(result.articles.len(), 1);
+        assert_eq!(result.articles_count, 2);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_feed_articles {
+    use super::feed_articles;
+    use crate::api::limits::PageConfig;
+    use crate::{
+        middleware::auth::Token,
+        tests::{
+            Operation::{Insert, Migration},
+            TestData, TestDataBuilder, TestErr,
+        },
+    };
+    use axum::extract::Query;
+    use axum::{extract::State, Extension, Json};
+    use dotenvy::dotenv;
+    use entity::entities::user;
+    use std::collections::HashMap;
+    use std::vec;
+
+    #[tokio::test]
+    async fn get_existing_articles() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(6))
+            .articles(Insert(vec![1, 2, 3, 4, 5, 1, 2, 3, 4, 5]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Insert(vec![(1, 6), (2, 6), (3, 6), (4, 6), (3, 5)]))
+            .build()
+            .await?;
+
+        let current_user: user::Model = users.unwrap().into_iter().last().unwrap();
+
+        let token = Token {
+            exp: 35,
+            id: current_user.id,
+        };
+
+        let params: HashMap<String, String> = [
+            ("limit".to_owned(), "5".to_owned()),
+            ("offset".to_owned(), "5".to_owned()),
+        ]
+        .into_iter()
+        .collect();
+
+        let result = feed_articles(
+            Query(params),
+            Extension(token),
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let Json(result) = result;
+
+        assert_eq!(result.articles.len(), 3);
+        assert_eq!(result.articles_count, 8);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_no_articles() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Migration)
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let current_user: user::Model = users.unwrap().into_iter().last().unwrap();
+        let token = Token {
+            exp: 35,
+            id: current_user.id,
+        };
+        let params: HashMap<String, String> = HashMap::new();
+
+        let result = feed_articles(
+            Query(params),
+            Extension(token),
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let Json(result) = result;
+
+        assert_eq!(result.articles.len(), 0);
+        assert_eq!(result.articles_count, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tag_filtered_count_matches_pages() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(3))
+            .articles(Insert(vec![1, 1, 1, 2, 2, 3, 3]))
+            .favorited_articles(Migration)
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (2, 1), (3, 2), (4, 1), (6, 1)]))
+            .followers(Insert(vec![(1, 3), (2, 3)]))
+            .build()
+            .await?;
+
+        let current_user: user::Model = users.unwrap().into_iter().last().unwrap();
+        let token = Token {
+            exp: 35,
+            id: current_user.id,
+        };
+
+        let mut total = 0;
+        for offset in ["0", "2"] {
+            let params: HashMap<String, String> = [
+                ("tag".to_owned(), "tag_name1".to_owned()),
+                ("limit".to_owned(), "2".to_owned()),
+                ("offset".to_owned(), offset.to_owned()),
+            ]
+            .into_iter()
+            .collect();
+
+            let Json(result) = feed_articles(
+                Query(params),
+                Extension(token.clone()),
+                State(connection.clone()),
+                Extension(PageConfig::default()),
+            )
+            .await?;
+            assert_eq!(result.articles_count, 3);
+            total += result.articles.len() as u64;
+        }
+        assert_eq!(total, 3);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_trending_articles {
+    use super::trending_articles;
+    use crate::api::{error::ApiErr, limits::PageConfig};
+    use crate::middleware::auth::Token;
+    use crate::repo::block::block_user;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{
+        extract::{Query, State},
+        Extension, Json,
+    };
+    use chrono::{Duration, Utc};
+    use entity::entities::{favorited_article, prelude::FavoritedArticle};
+    use migration::Expr;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn favorites_within_window() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(3))
+            .articles(Insert(vec![1, 1]))
+            .favorited_articles(Insert(vec![(1, 1), (1, 2), (1, 3), (2, 1)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        // First article gained its favorites ten days ago
+        let ten_days_ago = Utc::now().naive_utc() - Duration::days(10);
+        FavoritedArticle::update_many()
+            .col_expr(
+                favorited_article::Column::CreatedAt,
+                Expr::value(ten_days_ago),
+            )
+            .filter(favorited_article::Column::ArticleId.eq(articles.unwrap()[0].id))
+            .exec(&connection)
+            .await?;
+
+        let Json(result) = trending_articles(
+            Query(HashMap::new()),
+            None,
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let slugs: Vec<&str> = result
+            .articles
+            .iter()
+            .map(|art| art.slug.as_str())
+            .collect();
+        assert_eq!(slugs, ["title2"]);
+        assert_eq!(result.articles_count, 1);
+
+        let params: HashMap<String, String> =
+            [("days".to_owned(), "30".to_owned())].into_iter().collect();
+        let Json(result) = trending_articles(
+            Query(params),
+            None,
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let slugs: Vec<&str> = result
+            .articles
+            .iter()
+            .map(|art| art.slug.as_str())
+            .collect();
+        assert_eq!(slugs, ["title1", "title2"]);
+        assert_eq!(result.articles_count, 2);
+
+        let params: HashMap<String, String> = [("days", "30"), ("limit", "1")]
+            .into_iter()
+            .map(|(key, val)| (key.to_owned(), val.to_owned()))
+            .collect();
+        let Json(result) = trending_articles(
+            Query(params),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        // Total is not affected by limit
+        assert_eq!(result.articles.len(), 1);
+        assert_eq!(result.articles_count, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn blocked_author_excluded() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2]))
+            .favorited_articles(Insert(vec![(1, 1), (2, 1)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let users = users.unwrap();
+        block_user(&connection, users[0].id, users[1].id).await?;
+        let token = Token {
+            exp: 35,
+            id: users[0].id,
+        };
+
+        let Json(result) = trending_articles(
+            Query(HashMap::new()),
+            Some(Extension(token)),
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let slugs: Vec<&str> = result
+            .articles
+            .iter()
+            .map(|art| art.slug.as_str())
+            .collect();
+        assert_eq!(slugs, ["title1"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn huge_days() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Migration)
+            .articles(Migration)
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let params: HashMap<String, String> = [("days".to_owned(), "100000000".to_owned())]
+            .into_iter()
+            .collect();
+        let result = trending_articles(
+            Query(params),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await;
+        assert_eq!(result.err(), Some(ApiErr::InvalidInput("Invalid days")));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_article {
+    use super::get_article;
+    use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestDataBuilder, TestErr,
+    };
+    use axum::{
+        extract::{Query, State},
+        Json,
+    };
+    use dotenvy::dotenv;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn get_existing_article() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        // Actual test start
+        let slug = "title1";
+        let result = get_article(
+            State(connection),
+            None,
+            Path(slug.to_owned()),
+            Query(HashMap::new()),
+        )
+        .await?;
+        let Json(result) = result;
+
+        assert_eq!(result.article.title, slug.to_owned());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_sparse_article() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let params: HashMap<String, String> = [(
+            "fields".to_owned(),
+            "slug,title,favoritesCount,unknown".to_owned(),
+        )]
+        .into_iter()
+        .collect();
+        let Json(result) = get_article(
+            State(connection.clone()),
+            None,
+            Path("title1".to_owned()),
+            Query(params),
+        )
+        .await?;
+
+        let value = serde_json::to_value(result).unwrap();
+        assert_eq!(
+            value,
+            json!({"article": {"slug": "title1", "title": "title1", "favoritesCount": 0}})
+        );
+
+        let Json(result) = get_article(
+            State(connection),
+            None,
+            Path("title1".to_owned()),
+            Query(HashMap::new()),
+        )
+        .await?;
+        let full = serde_json::to_value(&result.article).unwrap();
+
+        let value = serde_json::to_value(result).unwrap();
+        assert_eq!(value["article"], full);
+        assert!(value["article"]["body"].is_string());
+        assert!(value["article"]["author"].is_object());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_non_existing_article() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Migration)
+            .articles(Migration)
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let slug = "not existing slug";
+        let result = get_article(
+            State(connection),
+            None,
+            Path(slug.to_owned()),
+            Query(HashMap::new()),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApiErr::ArticleNotExist)));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_article_versioned {
+    use super::get_article_versioned;
+    use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::middleware::version::ApiVersion;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{
+        body::HttpBody,
+        extract::{Query, State},
+        response::Response,
+        Extension,
+    };
+    use serde_json::Value;
+    use std::collections::HashMap;
+
+    async fn article_json(response: Response) -> Value {
+        let bytes = response.into_body().data().await.unwrap().unwrap();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        body["article"].clone()
+    }
+
+    #[tokio::test]
+    async fn v1_and_v2_shapes() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let author_id = users.unwrap()[0].id;
+
+        let response = get_article_versioned(
+            None,
+            State(connection.clone()),
+            None,
+            Path("title1".to_owned()),
+            Query(HashMap::new()),
+        )
+        .await?;
+        let v1 = article_json(response).await;
+        assert_eq!(v1["title"], "title1");
+        assert!(v1.get("authorId").is_none());
+
+        let response = get_article_versioned(
+            Some(Extension(ApiVersion::V2)),
+            State(connection),
+            None,
+            Path("title1".to_owned()),
+            Query(HashMap::new()),
+        )
+        .await?;
+        let v2 = article_json(response).await;
+        assert_eq!(v2["title"], "title1");
+        assert_eq!(v2["authorId"], author_id.to_string());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn v2_unknown_slug() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let result = get_article_versioned(
+            Some(Extension(ApiVersion::V2)),
+            State(connection),
+            None,
+            Path("unknown".to_owned()),
+            Query(HashMap::new()),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::ArticleNotExist)));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_create_article {
+    use super::{create_article, CreateArticle, CreateArticleDto};
+    use crate::api::{error::ApiErr, limits::get_max_tags_per_article};
+    use crate::middleware::auth::Token;
+    use crate::tests::{
+        Operation::{Create, Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, Extension, Json};
+    use dotenvy::dotenv;
+    use entity::entities::{
+        article, article_tag,
+        prelude::{Article, ArticleTag, Tag},
+        tag, user,
+    };
+    use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+    use serial_test::serial;
+
+    #[tokio::test]
+    async fn create_new_article() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Create(vec![1]))
+            .comments(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let current_user: user::Model = users.unwrap().into_iter().next().unwrap();
+        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
+
+        let article_data = CreateArticleDto {
+            article: CreateArticle {
+                title: article.title.clone(),
+                description: article.description,
+                body: article.body,
+                tag_list: Some(vec!["tag_name1".to_owned(), "tag_name2".to_owned()]),
+                published: None,
+            },
+        };
+
+        let token = Token {
+            exp: 35,
+            id: current_user.id,
+        };
+
+        let result =
+            create_article(State(connection), Extension(token), Json(article_data)).await?;
+        let Json(result) = result;
+
+        assert_eq!(result.article.title, article.title);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn concurrent_overlapping_new_tag() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Migration)
+            .comments(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let tasks: Vec<_> = users
+            .unwrap()
+            .into_iter()
+            .enumerate()
+            .map(|(idx, user)| {
+                let connection = connection.clone();
+                tokio::spawn(async move {
+                    let article_data = CreateArticleDto {
+                        article: CreateArticle {
+                            title: format!("title{idx}"),
+                            description: "description".to_owned(),
+                            body: "body".to_owned(),
+                            tag_list: Some(vec!["shared".to_owned(), format!("own{idx}")]),
+                            published: None,
+                        },
+                    };
+                    let token = Token {
+                        exp: 35,
+                        id: user.id,
+                    };
+                    create_article(State(connection), Extension(token), Json(article_data)).await
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            let Json(result) = task.await.unwrap()?;
+            assert!(result.article.tag_list.contains(&"shared".to_owned()));
+        }
+
+        let shared = Tag::find()
+            .filter(tag::Column::TagName.eq("shared"))
+            .one(&connection)
+            .await?
+            .unwrap();
+        let links = ArticleTag::find()
+            .filter(article_tag::Column::TagId.eq(shared.id))
+            .all(&connection)
+            .await?;
+        assert_eq!(links.len(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn padded_title_produces_clean_slug() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Migration)
+            .comments(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let current_user: user::Model = users.unwrap().into_iter().next().unwrap();
+
+        let article_data = CreateArticleDto {
+            article: CreateArticle {
+                title: "  Padded \t  title  ".to_owned(),
+                description: " description ".to_owned(),
+                body: "body".to_owned(),
+                tag_list: None,
+                published: None,
+            },
+        };
+
+        let token = Token {
+            exp: 35,
+            id: current_user.id,
+        };
+
+        let result =
+            create_article(State(connection), Extension(token), Json(article_data)).await?;
+        let Json(result) = result;
+        let article = result.article;
+
+        assert_eq!(article.title, "Padded title");
+        assert_eq!(article.description, "description");
+        assert_eq!(article.slug, "padded-title");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn same_title_gets_suffix() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Migration)
+            .comments(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let mut slugs = vec![];
+
+        for user in users.unwrap() {
+            let article_data = CreateArticleDto {
+                article: CreateArticle {
+                    title: "Same title".to_owned(),
+                    description: "description".to_owned(),
+                    body: format!("body of {}", user.username),
+                    tag_list: None,
+                    published: None,
+                },
+            };
+            let token = Token {
+                exp: 35,
+                id: user.id,
+            };
+
+            let Json(result) = create_article(
+                State(connection.clone()),
+                Extension(token),
+                Json(article_data),
+            )
+            .await?;
+            slugs.push(result.article.slug);
+        }
+
+        assert_eq!(slugs, vec!["same-title", "same-title-2"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn whitespace_only_title() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Migration)
+            .build()
+            .await?;
+        let current_user: user::Model = users.unwrap().into_iter().next().unwrap();
+
+        let article_data = CreateArticleDto {
+            article: CreateArticle {
+                title: " \t ".to_owned(),
+                description: "description".to_owned(),
+                body: "body".to_owned(),
+                tag_list: None,
+                published: None,
+            },
+        };
+
+        let token = Token {
+            exp: 35,
+            id: current_user.id,
+        };
+
+        let result = create_article(State(connection), Extension(token), Json(article_data)).await;
+
+        assert!(matches!(result, Err(ApiErr::InvalidInput(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn invalid_tag_list_writes_nothing() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+        let current_user: user::Model = users.unwrap().into_iter().next().unwrap();
+
+        let too_many: Vec<String> = (0..=get_max_tags_per_article())
+            .map(|idx| format!("tag{idx}"))
+            .collect();
+        for tag_list in [vec!["valid".to_owned(), "  ".to_owned()], too_many] {
+            let article_data = CreateArticleDto {
+                article: CreateArticle {
+                    title: "title".to_owned(),
+                    description: "description".to_owned(),
+                    body: "body".to_owned(),
+                    tag_list: Some(tag_list),
+                    published: None,
+                },
+            };
+            let token = Token {
+                exp: 35,
+                id: current_user.id,
+            };
+
+            let result = create_article(
+                State(connection.clone()),
+                Extension(token),
+                Json(article_data),
+            )
+            .await;
+
+            assert!(matches!(result, Err(ApiErr::InvalidInput(_))));
+        }
+
+        assert_eq!(Article::find().count(&connection).await?, 0);
+        assert_eq!(Tag::find().count(&connection).await?, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rollback_on_tag_failure() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        // No article tag table, so linking tags to created article fails
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Migration)
+            .tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        let article_data = CreateArticleDto {
+            article: CreateArticle {
+                title: "title".to_owned(),
+                description: "description".to_owned(),
+                body: "body".to_owned(),
+                tag_list: Some(vec!["tag_name1".to_owned()]),
+                published: None,
+            },
+        };
+        let result = create_article(
+            State(connection.clone()),
+            Extension(token),
+            Json(article_data),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApiErr::DbErr(_))));
+        assert_eq!(Article::find().count(&connection).await?, 0);
+        assert_eq!(Tag::find().count(&connection).await?, 0);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_inserted_article {
+    use super::inserted_article;
+    use crate::api::error::ApiErr;
+    use axum::{http::StatusCode, response::IntoResponse};
+    use sea_orm::DbErr;
+
+    #[test]
+    fn missing_refetch_is_internal_error() {
+        let err = inserted_article(None).unwrap_err();
+        assert!(matches!(err, ApiErr::DbErr(DbErr::RecordNotFound(_))));
+        assert_eq!(
+            err.into_response().status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_import_article {
+    use super::import_article;
+    use crate::api::error::ApiErr;
+    use crate::middleware::auth::Token;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+    use dotenvy::dotenv;
+    use entity::entities::prelude::{Article, ArticleTag};
+    use sea_orm::{EntityTrait, PaginatorTrait};
+
+    #[tokio::test]
+    async fn import_valid_document() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+        let document = "---\n\
+            title: Imported article\n\
+            description: From markdown\n\
+            tags: [rust, axum]\n\
+            ---\n\
+            \n\
+            # Heading\n\
+            \n\
+            Markdown body\n";
+
+        let Json(result) = import_article(
+            State(connection.clone()),
+            Extension(token),
+            document.to_owned(),
+        )
+        .await?;
+
+        assert_eq!(result.article.title, "Imported article");
+        assert_eq!(result.article.description, "From markdown");
+        assert_eq!(result.article.body, "# Heading\n\nMarkdown body");
+        assert_eq!(result.article.tag_list, ["axum", "rust"]);
+        assert_eq!(Article::find().count(&connection).await?, 1);
+        assert_eq!(ArticleTag::find().count(&connection).await?, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn import_without_title() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+        let document = "---\ndescription: From markdown\n---\nMarkdown body\n";
+
+        let result = import_article(
+            State(connection.clone()),
+            Extension(token),
+            document.to_owned(),
+        )
+        .await;
+
+        let err = result.unwrap_err();
+        assert_eq!(err, ApiErr::InvalidInput("Front matter title is required"));
+        assert_eq!(
+            err.into_response().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(Article::find().count(&connection).await?, 0);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_article_dedup {
+    use super::{create_article, CreateArticle, CreateArticleDto, ARTICLE_DEDUP_WINDOW_SECS};
+    use crate::api::error::ApiErr;
+    use crate::middleware::auth::Token;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, Extension, Json};
+    use chrono::{Duration, Utc};
+    use dotenvy::dotenv;
+    use entity::entities::{article, prelude::Article};
+    use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, Set};
+    use serial_test::serial;
+    use std::env;
+
+    fn payload() -> Json<CreateArticleDto> {
+        Json(CreateArticleDto {
+            article: CreateArticle {
+                title: "title".to_owned(),
+                description: "description".to_owned(),
+                body: "body".to_owned(),
+                tag_list: None,
+                published: None,
+            },
+        })
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn duplicate_within_window() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        env::set_var(ARTICLE_DEDUP_WINDOW_SECS, "60");
+        let Json(first) = create_article(
+            State(connection.clone()),
+            Extension(token.clone()),
+            payload(),
+        )
+        .await?;
+        let Json(second) =
+            create_article(State(connection.clone()), Extension(token), payload()).await?;
+        env::remove_var(ARTICLE_DEDUP_WINDOW_SECS);
+
+        assert_eq!(second.article.slug, first.article.slug);
+        assert_eq!(Article::find().count(&connection).await?, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn duplicate_after_window() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        env::set_var(ARTICLE_DEDUP_WINDOW_SECS, "10");
+        let Json(first) = create_article(
+            State(connection.clone()),
+            Extension(token.clone()),
+            payload(),
+        )
+        .await?;
+
+        // Move first article out of dedup window
+        let first = Article::find()
+            .filter(article::Column::Slug.eq(first.article.slug))
+            .one(&connection)
+            .await?
+            .unwrap();
+        let mut model: article::ActiveModel = first.into();
+        model.created_at = Set(Some(Utc::now().naive_utc() - Duration::minutes(1)));
+        model.update(&connection).await?;
+
+        let result = create_article(State(connection.clone()), Extension(token), payload()).await;
+        env::remove_var(ARTICLE_DEDUP_WINDOW_SECS);
+
+        // Not deduplicated, so insert is attempted and rejected by unique author and title
+        assert!(matches!(result, Err(ApiErr::DbErr(_))));
+        assert_eq!(Article::find().count(&connection).await?, 1);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_article_body_sanitization {
+    use super::{
+        create_article, update_article, CreateArticle, CreateArticleDto, UpdateArticle,
+        UpdateArticleDto,
+    };
+    use crate::api::extract::Path;
+    use crate::api::sanitize::SANITIZE_BODY;
+    use crate::middleware::auth::Token;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, Extension, Json};
+    use dotenvy::dotenv;
+    use entity::entities::{article, prelude::Article};
+    use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+    use serial_test::serial;
+    use std::env;
+
+    const BODY: &str = "Markdown *body*<script>alert(1)</script>";
+
+    /// Create article with script in body, then update body of another article the same way.
+    /// Returns stored bodies of created and updated articles.
+    async fn store_bodies(connection: &DatabaseConnection) -> Result<Vec<String>, TestErr> {
+        let article = Article::find().one(connection).await?.unwrap();
+        let token = Token {
+            exp: 35,
+            id: article.author_id,
+        };
+
+        let create_data = CreateArticleDto {
+            article: CreateArticle {
+                title: "created".to_owned(),
+                description: "description".to_owned(),
+                body: BODY.to_owned(),
+                tag_list: None,
+                published: None,
+            },
+        };
+        let Json(created) = create_article(
+            State(connection.clone()),
+            Extension(token.clone()),
+            Json(create_data),
+        )
+        .await?;
+
+        let update_data = UpdateArticleDto {
+            article: UpdateArticle {
+                body: Some(BODY.to_owned()),
+                ..Default::default()
+            },
+        };
+        let Json(updated) = update_article(
+            Path(article.slug),
+            State(connection.clone()),
+            Extension(token),
+            Json(update_data),
+        )
+        .await?;
+
+        let mut bodies = vec![];
+        for slug in [created.article.slug, updated.article.slug] {
+            let stored = Article::find()
+                .filter(article::Column::Slug.eq(slug))
+                .one(connection)
+                .await?
+                .unwrap();
+            bodies.push(stored.body);
+        }
+        Ok(bodies)
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn stored_sanitized_when_enabled() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        env::set_var(SANITIZE_BODY, "1");
+        let bodies = store_bodies(&connection).await;
+        env::remove_var(SANITIZE_BODY);
+
+        assert_eq!(bodies?, vec!["Markdown *body*"; 2]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn stored_verbatim_when_disabled() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        env::remove_var(SANITIZE_BODY);
+        let bodies = store_bodies(&connection).await?;
+
+        assert_eq!(bodies, vec![BODY; 2]);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_normalize_tag_list {
+    use super::normalize_tag_list;
+    use crate::api::{error::ApiErr, limits::get_max_tags_per_article};
+    use serial_test::serial;
+
+    #[test]
+    fn trimmed_tags() {
+        let result = normalize_tag_list(vec![" rust ".to_owned(), "axum".to_owned()]);
+        assert_eq!(result, Ok(vec!["rust".to_owned(), "axum".to_owned()]));
+    }
+
+    #[test]
+    fn empty_tag() {
+        let result = normalize_tag_list(vec!["rust".to_owned(), "\t".to_owned()]);
+        assert_eq!(result, Err(ApiErr::InvalidInput("Tag must not be empty")));
+    }
+
+    #[test]
+    #[serial]
+    fn capped_count() {
+        let tags: Vec<String> = (0..get_max_tags_per_article())
+            .map(|idx| format!("tag{idx}"))
+            .collect();
+        assert!(normalize_tag_list(tags.clone()).is_ok());
+
+        let tags = [tags, vec!["extra".to_owned()]].concat();
+        assert_eq!(
+            normalize_tag_list(tags),
+            Err(ApiErr::InvalidInput("Too many tags"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_transfer_article_author {
+    use super::{transfer_article_author, TransferArticleAuthor, TransferArticleDto};
+    use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::middleware::auth::Token;
+    use crate::repo::user_cache::UserCache;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, Extension, Json};
+    use entity::entities::prelude::Article;
+    use sea_orm::EntityTrait;
+    use std::sync::Arc;
+    use uuid::Uuid;
+
+    fn transfer_data(username: &str) -> Json<TransferArticleDto> {
+        Json(TransferArticleDto {
+            author: TransferArticleAuthor {
+                username: username.to_owned(),
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn transfer_to_existing_user() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(3))
+            .articles(Insert(vec![1]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let users = users.unwrap();
+        let article = articles.unwrap().into_iter().next().unwrap();
+        // Admin is neither old nor new author
+        let token = Token {
+            exp: 35,
+            id: users[2].id,
+        };
+
+        let Json(result) = transfer_article_author(
+            Path(article.slug.clone()),
+            State(connection.clone()),
+            Extension(token),
+            Extension(Arc::new(UserCache::default())),
+            transfer_data(&users[1].username),
+        )
+        .await?;
+        let transferred = result.article;
+        assert_eq!(transferred.slug, article.slug);
+        assert_eq!(transferred.author.username, users[1].username);
+
+        let stored = Article::find_by_id(article.id).one(&connection).await?;
+        assert_eq!(stored.unwrap().author_id, users[1].id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn transfer_to_not_existing_user() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .followers(Migration)
+            .build()
+            .await?;
+        let article = articles.unwrap().into_iter().next().unwrap();
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        let result = transfer_article_author(
+            Path(article.slug),
+            State(connection),
+            Extension(token),
+            Extension(Arc::new(UserCache::default())),
+            transfer_data("not existing username"),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::UserNotExist)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn transfer_not_existing_article() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Migration)
+            .build()
+            .await?;
+        let user = users.unwrap().into_iter().next().unwrap();
+        let token = Token {
+            exp: 35,
+            id: Uuid::new_v4(),
+        };
+
+        let result = transfer_article_author(
+            Path("not_existing_slug".to_owned()),
+            State(connection),
+            Extension(token),
+            Extension(Arc::new(UserCache::default())),
+            transfer_data(&user.username),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::ArticleNotExist)));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_update_article {
+    use super::{update_article, UpdateArticle, UpdateArticleDto};
+    use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::middleware::auth::Token;
+    use crate::tests::{
+        Operation::{Create, Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, Extension, Json};
+    use dotenvy::dotenv;
+    use entity::entities::{article, user};
+    use entity::entities::{
+        prelude::{Article, ArticleTag, Tag},
+        tag,
+    };
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    #[tokio::test]
+    async fn update_existing_article() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .comments(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let new_article_title = "updated_title";
+        let user: user::Model = users.unwrap().into_iter().next().unwrap();
+        let mut article: article::Model = articles.unwrap().into_iter().next().unwrap();
+        article.title = new_article_title.to_owned();
+
+        let payload = UpdateArticleDto {
+            article: UpdateArticle {
+                title: Some(new_article_title.to_owned()),
+                ..Default::default()
+            },
+        };
+
+        let token = Token {
+            exp: 35,
+            id: user.id,
+        };
+
+        // Actual test start
+        let result = update_article(
+            Path(article.slug),
+            State(connection),
+            Extension(token),
+            Json(payload),
+        )
+        .await?;
+        let Json(result) = result;
+
+        assert_eq!(result.article.title, new_article_title);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_non_existing_article() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Create(vec![1]))
+            .comments(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let user: user::Model = users.unwrap().into_iter().next().unwrap();
+        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
+
+        let payload = UpdateArticleDto {
+            article: UpdateArticle {
+                title: Some("updated_title".to_owned()),
+                ..Default::default()
+            },
+        };
+
+        let token = Token {
+            exp: 35,
+            id: user.id,
+        };
+
+        // Actual test start
+        let result = update_article(
+            Path(article.slug),
+            State(connection),
+            Extension(token),
+            Json(payload),
+        )
+        .await;
+
+        matches!(result, Err(ApiErr::ArticleNotExist));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_article_of_other_user() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .comments(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let other_user: user::Model = users.unwrap().into_iter().nth(1).unwrap();
+        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
+
+        let payload = UpdateArticleDto {
+            article: UpdateArticle {
+                title: Some("updated_title".to_owned()),
+                ..Default::default()
+            },
+        };
+        let token = Token {
+            exp: 35,
+            id: other_user.id,
+        };
+
+        let result = update_article(
+            Path(article.slug),
+            State(connection),
+            Extension(token),
+            Json(payload),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::Forbidden)));
+
+        Ok(())
+    }
+
+    async fn update_tags(
+        connection: &sea_orm::DatabaseConnection,
+        token: &Token,
+        tags: &[&str],
+    ) -> Result<Vec<String>, TestErr> {
+        let payload = UpdateArticleDto {
+            article: UpdateArticle {
+                tag_list: Some(tags.iter().map(|&tg| tg.to_owned()).collect()),
+                ..Default::default()
+            },
+        };
+        let Json(result) = update_article(
+            Path("title1".to_owned()),
+            State(connection.clone()),
+            Extension(token.clone()),
+            Json(payload),
+        )
+        .await?;
+        let mut tag_list = result.article.tag_list;
+        tag_list.sort();
+        Ok(tag_list)
+    }
+
+    #[tokio::test]
+    async fn replace_tag_list() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1]))
+            .comments(Migration)
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (2, 2)]))
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        // Add tag
+        let result = update_tags(&connection, &token, &["tag_name1", "new_tag"]).await?;
+        assert_eq!(result, ["new_tag", "tag_name1"]);
+
+        // Remove tag, orphaned one is deleted
+        let result = update_tags(&connection, &token, &["new_tag"]).await?;
+        assert_eq!(result, ["new_tag"]);
+        assert!(Tag::find()
+            .filter(tag::Column::TagName.eq("tag_name1"))
+            .one(&connection)
+            .await?
+            .is_none());
+
+        // Omitted tag list leaves tags untouched
+        let payload = UpdateArticleDto {
+            article: UpdateArticle {
+                body: Some("new body".to_owned()),
+                ..Default::default()
+            },
+        };
+        let Json(result) = update_article(
+            Path("title1".to_owned()),
+            State(connection.clone()),
+            Extension(token.clone()),
+            Json(payload),
+        )
+        .await?;
+        assert_eq!(result.article.tag_list, ["new_tag"]);
+
+        // Clear all tags
+        let result = update_tags(&connection, &token, &[]).await?;
+        assert!(result.is_empty());
+
+        // Tags of other article are not affected
+        assert_eq!(ArticleTag::find().all(&connection).await?.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rollback_on_tag_failure() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        // No article tag table, so reconciliation of tags fails after article is patched
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let article = articles.unwrap().remove(0);
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        let payload = UpdateArticleDto {
+            article: UpdateArticle {
+                body: Some("new body".to_owned()),
+                tag_list: Some(vec!["tag_name1".to_owned()]),
+                ..Default::default()
+            },
+        };
+        let result = update_article(
+            Path(article.slug.clone()),
+            State(connection.clone()),
+            Extension(token),
+            Json(payload),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApiErr::DbErr(_))));
+        let stored = Article::find_by_id(article.id).one(&connection).await?;
+        assert_eq!(stored, Some(article));
+        assert!(Tag::find().all(&connection).await?.is_empty());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_delete_article {
+    use super::delete_article;
+    use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::middleware::auth::Token;
+    use crate::tests::{
+        execute_migration,
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, Extension};
+    use entity::entities::{article, prelude::Article};
+    use sea_orm::EntityTrait;
+    use std::vec;
+
+    #[tokio::test]
+    async fn delete_existing_article() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(5))
+            .articles(Insert(vec![1, 1]))
+            .comments(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        execute_migration(&connection, "m20231201_000009_create_reaction_table").await?;
+
+        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
+        let token = Token {
+            exp: 35,
+            id: article.author_id,
+        };
+
+        let _result = delete_article(
+            Path(article.slug.clone()),
+            State(connection.clone()),
+            Extension(token.clone()),
+        )
+        .await?;
+
+        let result = delete_article(Path(article.slug), State(connection), Extension(token)).await;
+        assert!(matches!(result, Err(ApiErr::ArticleNotExist)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_article_of_other_user() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[1].id,
+        };
+
+        let result = delete_article(
+            Path(article.slug.clone()),
+            State(connection.clone()),
+            Extension(token),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::Forbidden)));
+        let stored = Article::find_by_id(article.id).one(&connection).await?;
+        assert!(stored.unwrap().deleted_at.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_non_existing_article() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(5))
+            .articles(Insert(vec![1, 1]))
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        let result =
+            delete_article(Path("slug".to_owned()), State(connection), Extension(token)).await;
+
+        matches!(result, Err(ApiErr::ArticleNotExist));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_add_article_tag {
+    use super::{add_article_tag, AddArticleTagDto};
+    use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::middleware::auth::Token;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, Extension, Json};
+    use dotenvy::dotenv;
+    use entity::entities::prelude::{ArticleTag, Tag};
+    use sea_orm::{EntityTrait, PaginatorTrait};
+
+    fn payload(tag: &str) -> Json<AddArticleTagDto> {
+        Json(AddArticleTagDto {
+            tag: tag.to_owned(),
+        })
+    }
+
+    #[tokio::test]
+    async fn add_new_tag() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .tags(Insert(1))
+            .article_tags(Insert(vec![(1, 1)]))
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        let Json(result) = add_article_tag(
+            Path("title1".to_owned()),
+            State(connection.clone()),
+            Extension(token),
+            payload(" new_tag "),
+        )
+        .await?;
+
+        let mut tag_list = result.article.tag_list;
+        tag_list.sort();
+        assert_eq!(tag_list, vec!["new_tag", "tag_name1"]);
+        assert_eq!(Tag::find().count(&connection).await?, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn add_existing_tag() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1)]))
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        // Existing tag not attached to article, then already attached one
+        for tag in ["tag_name2", "tag_name1"] {
+            let Json(result) = add_article_tag(
+                Path("title1".to_owned()),
+                State(connection.clone()),
+                Extension(token.clone()),
+                payload(tag),
+            )
+            .await?;
+            assert_eq!(result.article.tag_list.len(), 2);
+        }
+
+        assert_eq!(Tag::find().count(&connection).await?, 2);
+        assert_eq!(ArticleTag::find().count(&connection).await?, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn not_author_forbidden() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[1].id,
+        };
+
+        let result = add_article_tag(
+            Path("title1".to_owned()),
+            State(connection.clone()),
+            Extension(token),
+            payload("new_tag"),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApiErr::Forbidden)));
+        assert_eq!(Tag::find().count(&connection).await?, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn not_existing_article() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        let result = add_article_tag(
+            Path("not_exist".to_owned()),
+            State(connection),
+            Extension(token),
+            payload("new_tag"),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApiErr::ArticleNotExist)));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_delete_article_tag {
+    use super::delete_article_tag;
+    use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::middleware::auth::Token;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, Extension, Json};
+    use dotenvy::dotenv;
+    use entity::entities::prelude::{ArticleTag, Tag};
+    use sea_orm::{EntityTrait, PaginatorTrait};
+
+    #[tokio::test]
+    async fn delete_linked_tag() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (1, 2), (2, 2)]))
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        // Orphaned tag removed
+        let Json(result) = delete_article_tag(
+            Path(("title1".to_owned(), "tag_name1".to_owned())),
+            State(connection.clone()),
+            Extension(token.clone()),
+        )
+        .await?;
+        assert_eq!(result.article.tag_list, vec!["tag_name2"]);
+        assert_eq!(Tag::find().count(&connection).await?, 1);
+
+        // Tag still attached to other article kept
+        let Json(result) = delete_article_tag(
+            Path(("title1".to_owned(), "tag_name2".to_owned())),
+            State(connection.clone()),
+            Extension(token),
+        )
+        .await?;
+        assert!(result.article.tag_list.is_empty());
+        assert_eq!(Tag::find().count(&connection).await?, 1);
+        assert_eq!(ArticleTag::find().count(&connection).await?, 1);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn get_no_articles() -> Result<(), TestErr> {
+    async fn delete_not_linked_tag() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
         let (connection, TestData { users, .. }) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Migration)
+            .articles(Insert(vec![1, 1]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (2, 2)]))
             .favorited_articles(Migration)
-            .tags(Migration)
-            .article_tags(Migration)
             .followers(Migration)
             .build()
             .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        // Tag attached to other article only, then not existing tag
+        for tag_name in ["tag_name2", "not_exist"] {
+            let Json(result) = delete_article_tag(
+                Path(("title1".to_owned(), tag_name.to_owned())),
+                State(connection.clone()),
+                Extension(token.clone()),
+            )
+            .await?;
+            assert_eq!(result.article.tag_list, vec!["tag_name1"]);
+        }
+        assert_eq!(Tag::find().count(&connection).await?, 2);
+        assert_eq!(ArticleTag::find().count(&connection).await?, 2);
 
-        let current_user: user::Model = users.unwrap().into_iter().last().unwrap();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn not_author_forbidden() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .tags(Insert(1))
+            .article_tags(Insert(vec![(1, 1)]))
+            .build()
+            .await?;
         let token = Token {
             exp: 35,
-            id: current_user.id,
+            id: users.unwrap()[1].id,
         };
-        let params: HashMap<String, String> = HashMap::new();
 
-        let result = feed_articles(Query(params), Extension(token), State(connection)).await?;
-        let Json(result) = result;
+        let result = delete_article_tag(
+            Path(("title1".to_owned(), "tag_name1".to_owned())),
+            State(connection.clone()),
+            Extension(token),
+        )
+        .await;
 
-        assert_eq!(result.articles.len(), 0);
-        assert_eq!(result.articles_count, 0);
+        assert!(matches!(result, Err(ApiErr::Forbidden)));
+        assert_eq!(ArticleTag::find().count(&connection).await?, 1);
 
         Ok(())
     }
 }
 
 #[cfg(test)]
-mod test_get_article {
-    use super::get_article;
-    use crate::tests::{
-        Operation::{Insert, Migration},
-        TestDataBuilder, TestErr,
+mod test_favorite_article {
+    use super::favorite_article;
+    use crate::api::extract::Path;
+    use crate::api::{
+        error::ApiErr,
+        live::{FavoritesHub, Notification, NotificationsHub},
     };
-    use axum::{
-        extract::{Path, State},
-        Json,
+    use crate::middleware::auth::Token;
+    use crate::repo::user_cache::UserCache;
+    use crate::tests::{
+        Operation::{Create, Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
     };
+    use axum::{extract::State, Extension, Json};
     use dotenvy::dotenv;
+    use entity::entities::{article, user};
+    use std::sync::Arc;
 
     #[tokio::test]
-    async fn get_existing_article() -> Result<(), TestErr> {
+    async fn favorite_existing_article() -> Result<(), TestErr> {
         dotenv().expect(".env file not found");
-        let (connection, _) = TestDataBuilder::new()
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
             .users(Insert(1))
             .articles(Insert(vec![1]))
-            .favorited_articles(Migration)
+            .comments(Migration)
             .tags(Migration)
             .article_tags(Migration)
+            .favorited_articles(Migration)
             .followers(Migration)
             .build()
             .await?;
 
-        // Actual test start
-        let slug = "title1";
-        let result = get_article(State(connection), None, Path(slug.to_owned())).await?;
+        let current_user: user::Model = users.unwrap().into_iter().next().unwrap();
+        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
+
+        let token = Token {
+            exp: 35,
+            id: current_user.id,
+        };
+
+        let result = favorite_article(
+            Path(article.slug.clone()),
+            Extension(token),
+            Extension(Arc::new(FavoritesHub::default())),
+            Extension(Arc::new(NotificationsHub::default())),
+            Extension(Arc::new(UserCache::default())),
+            State(connection),
+        )
+        .await?;
         let Json(result) = result;
 
-        assert_eq!(result.article.unwrap().title, slug.to_owned());
+        assert_eq!(result.article.slug, article.slug);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn get_non_existing_article() -> Result<(), TestErr> {
-        let (connection, _) = TestDataBuilder::new()
-            .users(Migration)
-            .articles(Migration)
+    async fn favorite_non_existing_user() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Create(vec![1]))
+            .comments(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
             .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let current_user: user::Model = users.unwrap().into_iter().next().unwrap();
+        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
+
+        let token = Token {
+            exp: 35,
+            id: current_user.id,
+        };
+
+        let result = favorite_article(
+            Path(article.slug),
+            Extension(token),
+            Extension(Arc::new(FavoritesHub::default())),
+            Extension(Arc::new(NotificationsHub::default())),
+            Extension(Arc::new(UserCache::default())),
+            State(connection),
+        )
+        .await;
+
+        matches!(result, Err(ApiErr::ArticleNotExist));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn favorite_notifies_author_only() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .comments(Migration)
             .tags(Migration)
             .article_tags(Migration)
+            .favorited_articles(Migration)
             .followers(Migration)
             .build()
             .await?;
 
-        let slug = "not existing slug";
-        let result = get_article(State(connection), None, Path(slug.to_owned())).await?;
-        let Json(result) = result;
+        let users = users.unwrap();
+        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
+        let notifications = Arc::new(NotificationsHub::default());
+        let mut author_receiver = notifications.subscribe(users[0].id);
+        let mut reader_receiver = notifications.subscribe(users[1].id);
+
+        for user in users.iter().rev() {
+            let token = Token {
+                exp: 35,
+                id: user.id,
+            };
+            let _ = favorite_article(
+                Path(article.slug.clone()),
+                Extension(token),
+                Extension(Arc::new(FavoritesHub::default())),
+                Extension(notifications.clone()),
+                Extension(Arc::new(UserCache::default())),
+                State(connection.clone()),
+            )
+            .await?;
+        }
 
-        assert_eq!(result.article, None);
+        let notification = Notification::ArticleFavorited {
+            slug: article.slug,
+            username: users[1].username.clone(),
+        };
+        assert_eq!(author_receiver.try_recv(), Ok(notification));
+        // Self favorite of author emits nothing
+        assert!(author_receiver.try_recv().is_err());
+        assert!(reader_receiver.try_recv().is_err());
 
         Ok(())
     }
 }
 
 #[cfg(test)]
-mod test_create_article {
-    use super::{create_article, CreateArticle, CreateArticleDto};
+mod test_unfavorite_article {
+    use super::unfavorite_article;
+    use crate::api::extract::Path;
+    use crate::api::{error::ApiErr, live::FavoritesHub};
     use crate::middleware::auth::Token;
     use crate::tests::{
         Operation::{Create, Insert, Migration},
@@ -592,9 +4190,10 @@ mod test_create_article {
     use axum::{extract::State, Extension, Json};
     use dotenvy::dotenv;
     use entity::entities::{article, user};
+    use std::sync::Arc;
 
     #[tokio::test]
-    async fn create_new_article() -> Result<(), TestErr> {
+    async fn unfavorite_existing_article() -> Result<(), TestErr> {
         dotenv().expect(".env file not found");
         let (
             connection,
@@ -603,7 +4202,7 @@ mod test_create_article {
             },
         ) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Create(vec![1]))
+            .articles(Insert(vec![1]))
             .comments(Migration)
             .tags(Migration)
             .article_tags(Migration)
@@ -611,51 +4210,31 @@ mod test_create_article {
             .followers(Migration)
             .build()
             .await?;
+
         let current_user: user::Model = users.unwrap().into_iter().next().unwrap();
         let article: article::Model = articles.unwrap().into_iter().next().unwrap();
 
-        let article_data = CreateArticleDto {
-            article: CreateArticle {
-                title: article.title.clone(),
-                description: article.description,
-                body: article.body,
-                tag_list: Some(vec!["tag_name1".to_owned(), "tag_name2".to_owned()]),
-            },
-        };
-
         let token = Token {
             exp: 35,
             id: current_user.id,
         };
 
-        let result =
-            create_article(State(connection), Extension(token), Json(article_data)).await?;
+        let result = unfavorite_article(
+            Path(article.slug.clone()),
+            Extension(token),
+            Extension(Arc::new(FavoritesHub::default())),
+            State(connection),
+        )
+        .await?;
         let Json(result) = result;
 
-        assert_eq!(result.article.unwrap().title, article.title);
+        assert_eq!(result.article.slug, article.slug);
 
         Ok(())
     }
-}
-
-#[cfg(test)]
-mod test_update_article {
-    use super::{update_article, UpdateArticle, UpdateArticleDto};
-    use crate::api::error::ApiErr;
-    use crate::middleware::auth::Token;
-    use crate::tests::{
-        Operation::{Create, Insert, Migration},
-        TestData, TestDataBuilder, TestErr,
-    };
-    use axum::{
-        extract::{Path, State},
-        Extension, Json,
-    };
-    use dotenvy::dotenv;
-    use entity::entities::{article, user};
 
     #[tokio::test]
-    async fn update_existing_article() -> Result<(), TestErr> {
+    async fn unfavorite_non_existing_user() -> Result<(), TestErr> {
         dotenv().expect(".env file not found");
         let (
             connection,
@@ -664,7 +4243,7 @@ mod test_update_article {
             },
         ) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Insert(vec![1]))
+            .articles(Create(vec![1]))
             .comments(Migration)
             .tags(Migration)
             .article_tags(Migration)
@@ -673,160 +4252,355 @@ mod test_update_article {
             .build()
             .await?;
 
-        let new_article_title = "updated_title";
-        let user: user::Model = users.unwrap().into_iter().next().unwrap();
-        let mut article: article::Model = articles.unwrap().into_iter().next().unwrap();
-        article.title = new_article_title.to_owned();
-
-        let payload = UpdateArticleDto {
-            article: UpdateArticle {
-                title: Some(new_article_title.to_owned()),
-                ..Default::default()
-            },
-        };
+        let current_user: user::Model = users.unwrap().into_iter().next().unwrap();
+        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
 
         let token = Token {
             exp: 35,
-            id: user.id,
+            id: current_user.id,
         };
 
-        // Actual test start
-        let result = update_article(
+        let result = unfavorite_article(
             Path(article.slug),
-            State(connection),
             Extension(token),
-            Json(payload),
+            Extension(Arc::new(FavoritesHub::default())),
+            State(connection),
+        )
+        .await;
+
+        matches!(result, Err(ApiErr::ArticleNotExist));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_recently_tagged_articles {
+    use super::recently_tagged_articles;
+    use crate::api::extract::Path;
+    use crate::api::limits::PageConfig;
+    use crate::middleware::auth::Token;
+    use crate::repo::block::block_user;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{
+        extract::{Query, State},
+        Extension, Json,
+    };
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn limited_by_tag() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (2, 1), (3, 2)]))
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let Json(result) = recently_tagged_articles(
+            Query(HashMap::new()),
+            None,
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+            Path("tag_name1".to_owned()),
         )
         .await?;
-        let Json(result) = result;
+        assert_eq!(result.articles_count, 2);
+        assert!(result
+            .articles
+            .iter()
+            .all(|art| art.tag_list.contains(&"tag_name1".to_owned())));
+
+        let params: HashMap<String, String> =
+            [("limit".to_owned(), "1".to_owned())].into_iter().collect();
+        let Json(result) = recently_tagged_articles(
+            Query(params),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+            Path("tag_name1".to_owned()),
+        )
+        .await?;
+        // Total is not affected by limit
+        assert_eq!(result.articles.len(), 1);
+        assert_eq!(result.articles_count, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn blocked_author_excluded() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2]))
+            .tags(Insert(1))
+            .article_tags(Insert(vec![(1, 1), (2, 1)]))
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let users = users.unwrap();
+        block_user(&connection, users[0].id, users[1].id).await?;
+        let token = Token {
+            exp: 35,
+            id: users[0].id,
+        };
 
-        assert_eq!(result.article.unwrap().title, new_article_title);
+        let Json(result) = recently_tagged_articles(
+            Query(HashMap::new()),
+            Some(Extension(token)),
+            State(connection),
+            Extension(PageConfig::default()),
+            Path("tag_name1".to_owned()),
+        )
+        .await?;
+        let slugs: Vec<&str> = result
+            .articles
+            .iter()
+            .map(|art| art.slug.as_str())
+            .collect();
+        assert_eq!(slugs, ["title1"]);
 
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod test_archive_articles {
+    use super::archive_articles;
+    use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::api::limits::PageConfig;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestDataBuilder, TestErr,
+    };
+    use axum::{
+        extract::{Query, State},
+        http::StatusCode,
+        response::IntoResponse,
+        Extension, Json,
+    };
+    use chrono::NaiveDate;
+    use entity::entities::{article, prelude::Article};
+    use sea_orm::{prelude::Expr, EntityTrait};
+    use std::collections::HashMap;
 
     #[tokio::test]
-    async fn update_non_existing_article() -> Result<(), TestErr> {
-        dotenv().expect(".env file not found");
-        let (
-            connection,
-            TestData {
-                users, articles, ..
-            },
-        ) = TestDataBuilder::new()
+    async fn requested_month() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Create(vec![1]))
-            .comments(Migration)
+            .articles(Insert(vec![1, 1]))
             .tags(Migration)
             .article_tags(Migration)
             .favorited_articles(Migration)
             .followers(Migration)
             .build()
             .await?;
+        let created_at = NaiveDate::from_ymd_opt(2023, 5, 10)
+            .and_then(|date| date.and_hms_opt(12, 0, 0))
+            .unwrap();
+        Article::update_many()
+            .col_expr(article::Column::CreatedAt, Expr::value(created_at))
+            .exec(&connection)
+            .await?;
 
-        let user: user::Model = users.unwrap().into_iter().next().unwrap();
-        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
-
-        let payload = UpdateArticleDto {
-            article: UpdateArticle {
-                title: Some("updated_title".to_owned()),
-                ..Default::default()
-            },
-        };
-
-        let token = Token {
-            exp: 35,
-            id: user.id,
-        };
-
-        // Actual test start
-        let result = update_article(
-            Path(article.slug),
+        let Json(result) = archive_articles(
+            Path((2023, 5)),
+            Query(HashMap::new()),
+            None,
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        assert_eq!(result.articles_count, 2);
+
+        let params: HashMap<String, String> =
+            [("limit".to_owned(), "1".to_owned())].into_iter().collect();
+        let Json(result) = archive_articles(
+            Path((2023, 5)),
+            Query(params),
+            None,
             State(connection),
-            Extension(token),
-            Json(payload),
+            Extension(PageConfig::default()),
         )
-        .await;
+        .await?;
+        // Total is not affected by limit
+        assert_eq!(result.articles.len(), 1);
+        assert_eq!(result.articles_count, 2);
 
-        matches!(result, Err(ApiErr::ArticleNotExist));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn month_out_of_range() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Migration)
+            .build()
+            .await?;
+
+        for month in [0, 13] {
+            let result = archive_articles(
+                Path((2023, month)),
+                Query(HashMap::new()),
+                None,
+                State(connection.clone()),
+                Extension(PageConfig::default()),
+            )
+            .await;
+
+            let err = result.unwrap_err();
+            assert!(matches!(err, ApiErr::InvalidInput(_)));
+            assert_eq!(
+                err.into_response().status(),
+                StatusCode::UNPROCESSABLE_ENTITY
+            );
+        }
 
         Ok(())
     }
 }
 
 #[cfg(test)]
-mod test_delete_article {
-    use super::delete_article;
+mod test_related_articles {
+    use super::related_articles;
     use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::api::limits::PageConfig;
+    use crate::middleware::auth::Token;
+    use crate::repo::block::block_user;
     use crate::tests::{
         Operation::{Insert, Migration},
         TestData, TestDataBuilder, TestErr,
     };
-    use axum::extract::{Path, State};
-    use entity::entities::article;
-    use std::vec;
+    use axum::{
+        extract::{Query, State},
+        Extension, Json,
+    };
+    use std::collections::HashMap;
 
     #[tokio::test]
-    async fn delete_existing_article() -> Result<(), TestErr> {
-        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
-            .users(Insert(5))
-            .articles(Insert(vec![1, 1]))
+    async fn overlapping_tags_rank_first() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (1, 2), (2, 1), (2, 2), (3, 1)]))
+            .favorited_articles(Migration)
             .followers(Migration)
             .build()
             .await?;
 
-        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
-
-        let _result = delete_article(Path(article.slug), State(connection)).await?;
+        let Json(result) = related_articles(
+            Path("title1".to_owned()),
+            Query(HashMap::new()),
+            None,
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let slugs: Vec<&str> = result
+            .articles
+            .iter()
+            .map(|art| art.slug.as_str())
+            .collect();
+        assert_eq!(slugs, ["title2", "title3"]);
+        assert_eq!(result.articles_count, 2);
+
+        let params: HashMap<String, String> =
+            [("limit".to_owned(), "1".to_owned())].into_iter().collect();
+        let Json(result) = related_articles(
+            Path("title1".to_owned()),
+            Query(params),
+            None,
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        // Total is not affected by limit
+        assert_eq!(result.articles.len(), 1);
+        assert_eq!(result.articles_count, 2);
+
+        let result = related_articles(
+            Path("not existing slug".to_owned()),
+            Query(HashMap::new()),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::ArticleNotExist)));
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn delete_non_existing_article() -> Result<(), TestErr> {
-        let (connection, _) = TestDataBuilder::new()
-            .users(Insert(5))
-            .articles(Insert(vec![1, 1]))
+    async fn blocked_author_excluded() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1, 2]))
+            .tags(Insert(1))
+            .article_tags(Insert(vec![(1, 1), (2, 1), (3, 1)]))
+            .favorited_articles(Migration)
             .followers(Migration)
             .build()
             .await?;
+        let users = users.unwrap();
+        block_user(&connection, users[0].id, users[1].id).await?;
+        let token = Token {
+            exp: 35,
+            id: users[0].id,
+        };
 
-        let result = delete_article(Path("slug".to_owned()), State(connection)).await;
-
-        matches!(result, Err(ApiErr::ArticleNotExist));
+        let Json(result) = related_articles(
+            Path("title1".to_owned()),
+            Query(HashMap::new()),
+            Some(Extension(token)),
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let slugs: Vec<&str> = result
+            .articles
+            .iter()
+            .map(|art| art.slug.as_str())
+            .collect();
+        assert_eq!(slugs, ["title2"]);
 
         Ok(())
     }
 }
 
 #[cfg(test)]
-mod test_favorite_article {
-    use super::favorite_article;
+mod test_articles_by_slugs {
+    use super::{articles_by_slugs, ArticlesBySlugsDto, MAX_SLUGS_LEN};
     use crate::api::error::ApiErr;
     use crate::middleware::auth::Token;
+    use crate::repo::block::block_user;
     use crate::tests::{
-        Operation::{Create, Insert, Migration},
+        Operation::{Insert, Migration},
         TestData, TestDataBuilder, TestErr,
     };
-    use axum::{
-        extract::{Path, State},
-        Extension, Json,
-    };
-    use dotenvy::dotenv;
-    use entity::entities::{article, user};
+    use axum::{extract::State, Extension, Json};
+
+    fn payload(slugs: &[&str]) -> Json<ArticlesBySlugsDto> {
+        Json(ArticlesBySlugsDto {
+            slugs: slugs.iter().map(|&slug| slug.to_owned()).collect(),
+        })
+    }
 
     #[tokio::test]
-    async fn favorite_existing_article() -> Result<(), TestErr> {
-        dotenv().expect(".env file not found");
-        let (
-            connection,
-            TestData {
-                users, articles, ..
-            },
-        ) = TestDataBuilder::new()
+    async fn existing_and_missing_slugs() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Insert(vec![1]))
-            .comments(Migration)
+            .articles(Insert(vec![1, 1, 1]))
             .tags(Migration)
             .article_tags(Migration)
             .favorited_articles(Migration)
@@ -834,150 +4608,260 @@ mod test_favorite_article {
             .build()
             .await?;
 
-        let current_user: user::Model = users.unwrap().into_iter().next().unwrap();
-        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
+        let Json(result) = articles_by_slugs(
+            None,
+            State(connection.clone()),
+            payload(&["title2", "missing", "title1"]),
+        )
+        .await?;
+        let slugs: Vec<&str> = result
+            .articles
+            .iter()
+            .map(|art| art.slug.as_str())
+            .collect();
+        assert_eq!(slugs, ["title2", "title1"]);
+        assert_eq!(result.articles_count, 2);
+
+        let too_many = vec!["title1"; MAX_SLUGS_LEN + 1];
+        let result = articles_by_slugs(None, State(connection), payload(&too_many)).await;
+        assert!(matches!(result, Err(ApiErr::InvalidInput(_))));
+
+        Ok(())
+    }
 
+    #[tokio::test]
+    async fn blocked_author_excluded() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let users = users.unwrap();
+        block_user(&connection, users[0].id, users[1].id).await?;
         let token = Token {
             exp: 35,
-            id: current_user.id,
+            id: users[0].id,
         };
 
-        let result = favorite_article(
-            Path(article.slug.clone()),
-            Extension(token),
+        let Json(result) = articles_by_slugs(
+            Some(Extension(token)),
             State(connection),
+            payload(&["title1", "title2"]),
         )
         .await?;
-        let Json(result) = result;
-
-        assert_eq!(result.article.unwrap().slug, article.slug);
+        let slugs: Vec<&str> = result
+            .articles
+            .iter()
+            .map(|art| art.slug.as_str())
+            .collect();
+        assert_eq!(slugs, ["title1"]);
 
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod test_list_favorite_articles {
+    use super::list_favorite_articles;
+    use crate::api::limits::PageConfig;
+    use crate::middleware::auth::Token;
+    use crate::repo::user_cache::UserCache;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{
+        extract::{Query, State},
+        Extension, Json,
+    };
+    use std::collections::HashMap;
+    use std::sync::Arc;
 
     #[tokio::test]
-    async fn favorite_non_existing_user() -> Result<(), TestErr> {
-        dotenv().expect(".env file not found");
-        let (
-            connection,
-            TestData {
-                users, articles, ..
-            },
-        ) = TestDataBuilder::new()
-            .users(Insert(1))
-            .articles(Create(vec![1]))
-            .comments(Migration)
+    async fn favorites_of_logged_user() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2, 2]))
             .tags(Migration)
             .article_tags(Migration)
-            .favorited_articles(Migration)
+            .favorited_articles(Insert(vec![(2, 1), (3, 1), (1, 2)]))
             .followers(Migration)
             .build()
             .await?;
-
-        let current_user: user::Model = users.unwrap().into_iter().next().unwrap();
-        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
-
         let token = Token {
             exp: 35,
-            id: current_user.id,
+            id: users.unwrap()[0].id,
         };
+        let params: HashMap<String, String> =
+            [("limit".to_owned(), "1".to_owned())].into_iter().collect();
 
-        let result =
-            favorite_article(Path(article.slug), Extension(token), State(connection)).await;
+        let Json(result) = list_favorite_articles(
+            Query(params),
+            Extension(token),
+            State(connection),
+            Extension(PageConfig::default()),
+            Extension(Arc::new(UserCache::default())),
+        )
+        .await?;
 
-        matches!(result, Err(ApiErr::ArticleNotExist));
+        assert_eq!(result.articles.len(), 1);
+        assert_eq!(result.articles[0].slug, "title3");
+        assert!(result.articles[0].favorited);
+        assert_eq!(result.articles_count, 2);
 
         Ok(())
     }
 }
 
 #[cfg(test)]
-mod test_unfavorite_article {
-    use super::unfavorite_article;
-    use crate::api::error::ApiErr;
+mod test_list_own_articles {
+    use super::list_own_articles;
+    use crate::api::limits::PageConfig;
     use crate::middleware::auth::Token;
     use crate::tests::{
-        Operation::{Create, Insert, Migration},
+        Operation::{Insert, Migration},
         TestData, TestDataBuilder, TestErr,
     };
     use axum::{
-        extract::{Path, State},
+        extract::{Query, State},
         Extension, Json,
     };
-    use dotenvy::dotenv;
-    use entity::entities::{article, user};
+    use entity::entities::article;
+    use sea_orm::{ActiveModelTrait, Set};
+    use std::collections::HashMap;
 
     #[tokio::test]
-    async fn unfavorite_existing_article() -> Result<(), TestErr> {
-        dotenv().expect(".env file not found");
+    async fn published_and_drafts_of_owner() -> Result<(), TestErr> {
         let (
             connection,
             TestData {
                 users, articles, ..
             },
         ) = TestDataBuilder::new()
-            .users(Insert(1))
-            .articles(Insert(vec![1]))
-            .comments(Migration)
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1, 2]))
             .tags(Migration)
             .article_tags(Migration)
             .favorited_articles(Migration)
             .followers(Migration)
             .build()
             .await?;
-
-        let current_user: user::Model = users.unwrap().into_iter().next().unwrap();
-        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
-
+        let mut model: article::ActiveModel = articles.unwrap().remove(0).into();
+        model.published = Set(false);
+        model.update(&connection).await?;
         let token = Token {
             exp: 35,
-            id: current_user.id,
+            id: users.unwrap()[0].id,
         };
 
-        let result = unfavorite_article(
-            Path(article.slug.clone()),
+        let Json(result) = list_own_articles(
+            Query(HashMap::new()),
+            Extension(token.clone()),
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+
+        let mut flags: Vec<_> = result
+            .articles
+            .iter()
+            .map(|art| (art.slug.as_str(), art.published))
+            .collect();
+        flags.sort();
+        assert_eq!(flags, vec![("title1", false), ("title2", true)]);
+        assert_eq!(result.articles_count, 2);
+
+        let params: HashMap<String, String> =
+            [("limit".to_owned(), "1".to_owned())].into_iter().collect();
+        let Json(result) = list_own_articles(
+            Query(params),
             Extension(token),
             State(connection),
+            Extension(PageConfig::default()),
         )
         .await?;
-        let Json(result) = result;
-
-        assert_eq!(result.article.unwrap().slug, article.slug);
+        // Total is not affected by limit
+        assert_eq!(result.articles.len(), 1);
+        assert_eq!(result.articles_count, 2);
 
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod test_empty_lists {
+    use super::{feed_articles, list_articles, recently_tagged_articles, trending_articles};
+    use crate::api::extract::Path;
+    use crate::api::limits::PageConfig;
+    use crate::middleware::auth::Token;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{
+        extract::{Query, State},
+        Extension, Json,
+    };
+    use serde_json::json;
+    use std::collections::HashMap;
 
     #[tokio::test]
-    async fn unfavorite_non_existing_user() -> Result<(), TestErr> {
-        dotenv().expect(".env file not found");
-        let (
-            connection,
-            TestData {
-                users, articles, ..
-            },
-        ) = TestDataBuilder::new()
+    async fn empty_articles_serialized_as_list() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Create(vec![1]))
-            .comments(Migration)
+            .articles(Migration)
             .tags(Migration)
             .article_tags(Migration)
             .favorited_articles(Migration)
             .followers(Migration)
             .build()
             .await?;
-
-        let current_user: user::Model = users.unwrap().into_iter().next().unwrap();
-        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
-
         let token = Token {
             exp: 35,
-            id: current_user.id,
+            id: users.unwrap()[0].id,
         };
+        let expected = json!({"articles": [], "articlesCount": 0});
 
-        let result =
-            unfavorite_article(Path(article.slug), Extension(token), State(connection)).await;
+        let Json(result) = list_articles(
+            Query(Vec::new()),
+            None,
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        assert_eq!(serde_json::to_value(result).unwrap(), expected);
 
-        matches!(result, Err(ApiErr::ArticleNotExist));
+        let Json(result) = feed_articles(
+            Query(HashMap::new()),
+            Extension(token),
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        assert_eq!(serde_json::to_value(result).unwrap(), expected);
+
+        let Json(result) = trending_articles(
+            Query(HashMap::new()),
+            None,
+            State(connection.clone()),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        assert_eq!(serde_json::to_value(result).unwrap(), expected);
+
+        let Json(result) = recently_tagged_articles(
+            Query(HashMap::new()),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+            Path("tag_name1".to_owned()),
+        )
+        .await?;
+        assert_eq!(serde_json::to_value(result).unwrap(), expected);
 
         Ok(())
     }
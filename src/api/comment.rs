@@ -1,22 +1,25 @@
-use super::error::ApiErr;
+use super::{error::ApiErr, extract::Path, id::new_id, limits::PageConfig};
 use crate::middleware::auth::Token;
 use crate::repo::{
     article::get_article_model_by_slug,
     comment::{
-        delete_comment as repo_delete_comment, get_comment_by_id, get_comments_by_article_id,
-        insert_comment, CommentWithAuthor,
+        count_comments_by_article_id, delete_comment as repo_delete_comment, get_comment_by_id,
+        get_comment_model_by_id, get_comments_by_article_id, insert_comment_returning,
+        update_comment as repo_update_comment, CommentWithAuthor,
     },
 };
 use axum::{
-    extract::{Path, State},
+    extract::{Query, State},
     Extension, Json,
 };
 use entity::entities::comment;
 use sea_orm::{ActiveValue::Set, DatabaseConnection};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use uuid::Uuid;
 
-/// Axum handler for creating article comment.
+/// Axum handler for creating article comment. Response contains updated number of article comments.
+/// Body stored as provided, but must contain not only whitespace.
 /// Returns json object with comment on success, otherwise returns an `api error`.
 pub async fn create_comment(
     Path(slug): Path<String>,
@@ -26,54 +29,84 @@ pub async fn create_comment(
 ) -> Result<Json<CommentDto>, ApiErr> {
     let current_user_id = token.id;
     let input = payload.comment;
+    if input.body.trim().is_empty() {
+        return Err(ApiErr::EmptyComment);
+    }
 
-    let commented_article = get_article_model_by_slug(&db, &slug)
+    let commented_article = get_article_model_by_slug(&db, &slug, Some(current_user_id))
         .await?
         .ok_or(ApiErr::ArticleNotExist)?;
 
     let comment_model = comment::ActiveModel {
-        id: Set(Uuid::new_v4()),
+        id: Set(new_id()),
         body: Set(input.body),
         author_id: Set(current_user_id),
         article_id: Set(commented_article.id),
         ..Default::default()
     };
 
-    let cmnt_res = insert_comment(&db, comment_model).await?;
+    let comment = insert_comment_returning(&db, comment_model, current_user_id).await?;
 
-    let comment = get_comment_by_id(&db, cmnt_res.last_insert_id, Some(current_user_id))
-        .await?
-        .ok_or(ApiErr::CommentNotExist)?;
+    let comments_count = count_comments_by_article_id(&db, commented_article.id).await?;
 
-    let comment_dto = CommentDto { comment };
+    let comment_dto = CommentDto {
+        comment,
+        comments_count,
+    };
     Ok(Json(comment_dto))
 }
 
-/// Axum handler for fetch all article `comments`.
+/// Axum handler for fetch article `comments`, newest first. Limit response by limit (capped by
+/// APP_MAX_PAGE_SIZE) and offset parameters. Response contains total number of article
+/// comments as well.
 /// Returns json object with list of comments on success, otherwise returns an `api error`.
 pub async fn list_comments(
     Path(slug): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
     maybe_token: Option<Extension<Token>>,
     State(db): State<DatabaseConnection>,
+    Extension(page_config): Extension<PageConfig>,
 ) -> Result<Json<CommentsDto>, ApiErr> {
-    let commented_article = get_article_model_by_slug(&db, &slug)
+    // Limit number of comments (default is 50, capped by APP_MAX_PAGE_SIZE):
+    let limit = params
+        .get("limit")
+        .and_then(|lm| lm.parse::<u64>().ok())
+        .map(|lm| lm.min(page_config.max_limit));
+
+    // Offset/skip number of comments (default is 0):
+    let offset = params.get("offset").and_then(|off| off.parse::<u64>().ok());
+
+    let current_user_id = maybe_token.map(|tkn| tkn.id);
+    let commented_article = get_article_model_by_slug(&db, &slug, current_user_id)
         .await?
         .ok_or(ApiErr::ArticleNotExist)?;
 
     let comments =
-        get_comments_by_article_id(&db, commented_article.id, maybe_token.map(|tkn| tkn.id))
+        get_comments_by_article_id(&db, commented_article.id, limit, offset, current_user_id)
             .await?;
+    let comments_count = count_comments_by_article_id(&db, commented_article.id).await?;
 
-    let comments_dto = CommentsDto { comments };
+    let comments_dto = CommentsDto {
+        comments,
+        comments_count,
+    };
     Ok(Json(comments_dto))
 }
 
-/// Axum handler for delete comment by provided comment id.
-/// Returns empty json object on success, otherwise returns an `api error`.
+/// Axum handler for delete comment by provided comment id. Only author of comment allowed
+/// to delete it, thus token is required. Returns empty json object on success, `Forbidden`
+/// for other users, `CommentNotExist` for comment of other article, otherwise returns
+/// an `api error`.
 pub async fn delete_comment(
-    Path((_slug, comment_id)): Path<(String, Uuid)>,
+    Path((slug, comment_id)): Path<(String, Uuid)>,
     State(db): State<DatabaseConnection>,
+    Extension(token): Extension<Token>,
 ) -> Result<Json<()>, ApiErr> {
+    let deleted_comment = find_article_comment(&db, &slug, comment_id, token.id).await?;
+    if deleted_comment.author_id != token.id {
+        return Err(ApiErr::Forbidden);
+    }
+
     let del_res = repo_delete_comment(&db, comment_id).await?;
 
     if del_res.rows_affected > 0 {
@@ -83,16 +116,76 @@ pub async fn delete_comment(
     }
 }
 
-/// Struct describing JSON object, returned by handler. Contains list of comments.
+/// Axum handler for edit body of comment by provided comment id. Only author of comment allowed
+/// to edit it, thus token is required. Body must contain not only whitespace, time of update
+/// set to current time. Response contains number of article comments.
+/// Returns json object with comment on success, `Forbidden` for other users, `CommentNotExist`
+/// for comment of other article, otherwise returns an `api error`.
+pub async fn update_comment(
+    Path((slug, comment_id)): Path<(String, Uuid)>,
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<Token>,
+    Json(payload): Json<UpdateCommentDto>,
+) -> Result<Json<CommentDto>, ApiErr> {
+    let input = payload.comment;
+    if input.body.trim().is_empty() {
+        return Err(ApiErr::EmptyComment);
+    }
+
+    let updated_comment = find_article_comment(&db, &slug, comment_id, token.id).await?;
+    if updated_comment.author_id != token.id {
+        return Err(ApiErr::Forbidden);
+    }
+
+    repo_update_comment(&db, comment_id, input.body).await?;
+
+    let comment = get_comment_by_id(&db, comment_id, Some(token.id))
+        .await?
+        .ok_or(ApiErr::CommentNotExist)?;
+    let comments_count = count_comments_by_article_id(&db, updated_comment.article_id).await?;
+
+    let comment_dto = CommentDto {
+        comment,
+        comments_count,
+    };
+    Ok(Json(comment_dto))
+}
+
+/// Fetch comment with provided id of article with provided slug, as seen by user with provided
+/// id. Returns `comment` on success, `ArticleNotExist` for missing article, `CommentNotExist`
+/// for missing comment or comment of other article, otherwise returns an `api error`.
+async fn find_article_comment(
+    db: &DatabaseConnection,
+    slug: &str,
+    comment_id: Uuid,
+    current_user_id: Uuid,
+) -> Result<comment::Model, ApiErr> {
+    let commented_article = get_article_model_by_slug(db, slug, Some(current_user_id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+
+    get_comment_model_by_id(db, comment_id)
+        .await?
+        .filter(|cmt| cmt.article_id == commented_article.id)
+        .ok_or(ApiErr::CommentNotExist)
+}
+
+/// Struct describing JSON object, returned by handler. Contains list of comments and total
+/// number of comments of the article. Empty result is serialized as empty list, never as `null`.
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CommentsDto {
     comments: Vec<CommentWithAuthor>,
+    comments_count: u64,
 }
 
-/// Struct describing JSON object, returned by handler. Contains comment.
+/// Struct describing JSON object, returned by handler. Contains comment and number
+/// of comments of commented article.
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CommentDto {
     comment: CommentWithAuthor,
+    comments_count: u64,
 }
 
 /// Struct describing JSON object from comment creation request. Contains comment.
@@ -106,19 +199,28 @@ struct CreateComment {
     body: String,
 }
 
+/// Struct describing JSON object from comment edit request. Contains comment.
+#[derive(Debug, Deserialize)]
+pub struct UpdateCommentDto {
+    comment: UpdateComment,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct UpdateComment {
+    body: String,
+}
+
 #[cfg(test)]
 mod test_create_comment {
     use super::{create_comment, CreateComment, CreateCommentDto};
     use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
     use crate::middleware::auth::Token;
     use crate::tests::{
         Operation::{Insert, Migration},
         TestData, TestDataBuilder, TestErr,
     };
-    use axum::{
-        extract::{Path, State},
-        Extension, Json,
-    };
+    use axum::{extract::State, Extension, Json};
     use dotenvy::dotenv;
     use entity::entities::{article, user};
 
@@ -165,6 +267,100 @@ mod test_create_comment {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn reject_blank_body() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .comments(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+        let slug = articles.unwrap()[0].slug.clone();
+        let comment_data = |body: &str| {
+            Json(CreateCommentDto {
+                comment: CreateComment {
+                    body: body.to_owned(),
+                },
+            })
+        };
+
+        for body in ["", "   "] {
+            let result = create_comment(
+                Path(slug.clone()),
+                State(connection.clone()),
+                Extension(token.clone()),
+                comment_data(body),
+            )
+            .await;
+            assert!(matches!(result, Err(ApiErr::EmptyComment)));
+        }
+
+        // Valid body stored untrimmed
+        let Json(result) = create_comment(
+            Path(slug),
+            State(connection),
+            Extension(token),
+            comment_data("  comment "),
+        )
+        .await?;
+        assert_eq!(result.comment.body, "  comment ");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn comments_count_increments() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2]))
+            .comments(Insert(vec![(2, 1), (1, 2)]))
+            .followers(Migration)
+            .build()
+            .await?;
+        let current_user: user::Model = users.unwrap().into_iter().next().unwrap();
+        let article: article::Model = articles.unwrap().into_iter().next().unwrap();
+
+        for expected_count in [2, 3] {
+            let comment_data = CreateCommentDto {
+                comment: CreateComment {
+                    body: "comment".to_owned(),
+                },
+            };
+            let token = Token {
+                exp: 35,
+                id: current_user.id,
+            };
+
+            let Json(result) = create_comment(
+                Path(article.slug.clone()),
+                State(connection.clone()),
+                Extension(token),
+                Json(comment_data),
+            )
+            .await?;
+            assert_eq!(result.comments_count, expected_count);
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn comment_for_not_existing_article() -> Result<(), TestErr> {
         dotenv().expect(".env file not found");
@@ -206,6 +402,8 @@ mod test_create_comment {
 mod test_list_comments {
     use super::list_comments;
     use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::api::limits::PageConfig;
     use crate::{
         middleware::auth::Token,
         tests::{
@@ -214,12 +412,12 @@ mod test_list_comments {
         },
     };
     use axum::{
-        extract::{Path, State},
+        extract::{Query, State},
         Extension, Json,
     };
     use dotenvy::dotenv;
     use entity::entities::{article, user};
-    use std::vec;
+    use std::{collections::HashMap, vec};
 
     #[tokio::test]
     async fn get_existing_comments() -> Result<(), TestErr> {
@@ -247,13 +445,16 @@ mod test_list_comments {
 
         let result = list_comments(
             Path(article.slug),
+            Query(HashMap::new()),
             Some(Extension(token)),
             State(connection),
+            Extension(PageConfig::default()),
         )
         .await?;
         let Json(result) = result;
 
         assert_eq!(result.comments.len(), 3);
+        assert_eq!(result.comments_count, 3);
 
         Ok(())
     }
@@ -284,13 +485,16 @@ mod test_list_comments {
 
         let result = list_comments(
             Path(article.slug),
+            Query(HashMap::new()),
             Some(Extension(token)),
             State(connection),
+            Extension(PageConfig::default()),
         )
         .await?;
         let Json(result) = result;
 
         assert_eq!(result.comments.len(), 0);
+        assert_eq!(result.comments_count, 0);
 
         Ok(())
     }
@@ -315,8 +519,10 @@ mod test_list_comments {
 
         let result = list_comments(
             Path("not existing article".to_owned()),
+            Query(HashMap::new()),
             Some(Extension(token)),
             State(connection),
+            Extension(PageConfig::default()),
         )
         .await;
 
@@ -324,19 +530,266 @@ mod test_list_comments {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn no_comments_serialized_as_list() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .comments(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let Json(result) = list_comments(
+            Path("title1".to_owned()),
+            Query(HashMap::new()),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+
+        assert_eq!(
+            serde_json::to_value(result).unwrap(),
+            serde_json::json!({"comments": [], "commentsCount": 0})
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn page_with_total_count() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1]))
+            .comments(Insert(vec![(1, 1), (2, 1), (1, 1), (2, 1), (1, 2)]))
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let params = HashMap::from([
+            ("limit".to_owned(), "3".to_owned()),
+            ("offset".to_owned(), "2".to_owned()),
+        ]);
+        let Json(result) = list_comments(
+            Path("title1".to_owned()),
+            Query(params),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+
+        // Two comments left after offset, total count is not paged
+        assert_eq!(result.comments.len(), 2);
+        assert_eq!(result.comments_count, 4);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_update_comment {
+    use super::{update_comment, UpdateComment, UpdateCommentDto};
+    use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::middleware::auth::Token;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, Extension, Json};
+    use entity::entities::{comment, prelude::Comment};
+    use sea_orm::EntityTrait;
+    use uuid::Uuid;
+
+    fn comment_data(body: &str) -> Json<UpdateCommentDto> {
+        Json(UpdateCommentDto {
+            comment: UpdateComment {
+                body: body.to_owned(),
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn update_own_comment() -> Result<(), TestErr> {
+        let (connection, TestData { comments, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .comments(Insert(vec![(2, 1), (1, 1)]))
+            .followers(Migration)
+            .build()
+            .await?;
+        let comment: comment::Model = comments.unwrap().into_iter().next().unwrap();
+        let token = Token {
+            exp: 35,
+            id: comment.author_id,
+        };
+
+        let Json(result) = update_comment(
+            Path(("title1".to_owned(), comment.id)),
+            State(connection.clone()),
+            Extension(token),
+            comment_data("edited"),
+        )
+        .await?;
+        assert_eq!(result.comment.id, comment.id);
+        assert_eq!(result.comment.body, "edited");
+        assert!(result.comment.can_edit);
+        assert_ne!(result.comment.updated_at, comment.updated_at);
+        assert_eq!(result.comments_count, 2);
+
+        let stored = Comment::find_by_id(comment.id).one(&connection).await?;
+        assert_eq!(stored.unwrap().body, "edited");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_comment_of_other_user() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                comments, users, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .comments(Insert(vec![(2, 1)]))
+            .followers(Migration)
+            .build()
+            .await?;
+        let comment: comment::Model = comments.unwrap().into_iter().next().unwrap();
+        // Author of commented article is not author of the comment
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        let result = update_comment(
+            Path(("title1".to_owned(), comment.id)),
+            State(connection.clone()),
+            Extension(token),
+            comment_data("edited"),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::Forbidden)));
+
+        let stored = Comment::find_by_id(comment.id).one(&connection).await?;
+        assert_eq!(stored.unwrap().body, comment.body);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_with_blank_body() -> Result<(), TestErr> {
+        let (connection, TestData { comments, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .comments(Insert(vec![(1, 1)]))
+            .followers(Migration)
+            .build()
+            .await?;
+        let comment: comment::Model = comments.unwrap().into_iter().next().unwrap();
+        let token = Token {
+            exp: 35,
+            id: comment.author_id,
+        };
+
+        for body in ["", "   "] {
+            let result = update_comment(
+                Path(("title1".to_owned(), comment.id)),
+                State(connection.clone()),
+                Extension(token.clone()),
+                comment_data(body),
+            )
+            .await;
+            assert!(matches!(result, Err(ApiErr::EmptyComment)));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_non_existing_comment() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .comments(Insert(vec![(1, 1)]))
+            .followers(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        let result = update_comment(
+            Path(("title1".to_owned(), Uuid::new_v4())),
+            State(connection),
+            Extension(token),
+            comment_data("edited"),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::CommentNotExist)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_comment_of_other_article() -> Result<(), TestErr> {
+        let (connection, TestData { comments, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1]))
+            .comments(Insert(vec![(1, 1)]))
+            .followers(Migration)
+            .build()
+            .await?;
+        let comment: comment::Model = comments.unwrap().into_iter().next().unwrap();
+        let token = Token {
+            exp: 35,
+            id: comment.author_id,
+        };
+
+        let result = update_comment(
+            Path(("title2".to_owned(), comment.id)),
+            State(connection.clone()),
+            Extension(token),
+            comment_data("edited"),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::CommentNotExist)));
+
+        let stored = Comment::find_by_id(comment.id).one(&connection).await?;
+        assert_eq!(stored.unwrap().body, comment.body);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test_delete_comment {
     use super::delete_comment;
     use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::middleware::auth::Token;
     use crate::tests::{
         Operation::{Insert, Migration},
         TestData, TestDataBuilder, TestErr,
     };
-    use axum::extract::{Path, State};
-    use entity::entities::comment;
+    use axum::{
+        body::{Body, HttpBody},
+        extract::State,
+        http::{Request, StatusCode},
+        response::IntoResponse,
+        routing::delete,
+        Extension, Json, Router,
+    };
+    use entity::entities::{comment, prelude::Comment};
+    use sea_orm::EntityTrait;
     use std::vec;
+    use tower::ServiceExt;
     use uuid::Uuid;
 
     #[tokio::test]
@@ -350,27 +803,158 @@ mod test_delete_comment {
             .await?;
 
         let comment: comment::Model = comments.unwrap().into_iter().next().unwrap();
+        let token = Token {
+            exp: 35,
+            id: comment.author_id,
+        };
 
-        let _result =
-            delete_comment(Path(("slug".to_owned(), comment.id)), State(connection)).await?;
+        let Json(_) = delete_comment(
+            Path(("title1".to_owned(), comment.id)),
+            State(connection.clone()),
+            Extension(token),
+        )
+        .await?;
+        assert!(Comment::find_by_id(comment.id)
+            .one(&connection)
+            .await?
+            .is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_comment_of_other_user() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                comments, users, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(5))
+            .articles(Insert(vec![1, 1]))
+            .comments(Insert(vec![(2, 1), (2, 2), (3, 1), (5, 1)]))
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let comment: comment::Model = comments.unwrap().into_iter().next().unwrap();
+        // Author of commented article is not author of the comment
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        let result = delete_comment(
+            Path(("title1".to_owned(), comment.id)),
+            State(connection.clone()),
+            Extension(token),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::Forbidden)));
+        assert!(Comment::find_by_id(comment.id)
+            .one(&connection)
+            .await?
+            .is_some());
 
         Ok(())
     }
 
     #[tokio::test]
     async fn delete_non_existing_comment() -> Result<(), TestErr> {
-        let (connection, _) = TestDataBuilder::new()
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
             .users(Insert(5))
             .articles(Insert(vec![1, 1]))
             .comments(Insert(vec![(2, 1), (2, 2), (3, 1), (5, 1)]))
             .followers(Migration)
             .build()
             .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[1].id,
+        };
 
-        let result =
-            delete_comment(Path(("slug".to_owned(), Uuid::new_v4())), State(connection)).await;
+        let result = delete_comment(
+            Path(("title1".to_owned(), Uuid::new_v4())),
+            State(connection),
+            Extension(token),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApiErr::CommentNotExist)));
+        assert_eq!(
+            result.unwrap_err().into_response().status(),
+            StatusCode::NOT_FOUND
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_comment_of_other_article() -> Result<(), TestErr> {
+        let (connection, TestData { comments, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1]))
+            .comments(Insert(vec![(2, 1)]))
+            .followers(Migration)
+            .build()
+            .await?;
+        let comment: comment::Model = comments.unwrap().into_iter().next().unwrap();
+        let token = Token {
+            exp: 35,
+            id: comment.author_id,
+        };
 
-        matches!(result, Err(ApiErr::CommentNotExist));
+        let result = delete_comment(
+            Path(("title2".to_owned(), comment.id)),
+            State(connection.clone()),
+            Extension(token.clone()),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::CommentNotExist)));
+
+        let result = delete_comment(
+            Path(("not_exist".to_owned(), comment.id)),
+            State(connection.clone()),
+            Extension(token),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::ArticleNotExist)));
+        assert!(Comment::find_by_id(comment.id)
+            .one(&connection)
+            .await?
+            .is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_with_invalid_id() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .comments(Insert(vec![(1, 1)]))
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+        let app = Router::new()
+            .route("/api/articles/:slug/comments/:id", delete(delete_comment))
+            .layer(Extension(token))
+            .with_state(connection);
+
+        let request = Request::delete("/api/articles/title1/comments/not-uuid")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let bytes = response.into_body().data().await.unwrap().unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert!(body["error"]
+            .as_str()
+            .is_some_and(|msg| msg.starts_with("Invalid path parameter")));
 
         Ok(())
     }
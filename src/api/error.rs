@@ -2,7 +2,7 @@ use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::response::Response;
 use axum::Json;
-use sea_orm::DbErr;
+use sea_orm::{DbErr, SqlErr, TransactionError};
 use serde_json::json;
 
 /// Error returned by Api
@@ -13,6 +13,16 @@ pub enum ApiErr {
     ArticleNotExist,
     CommentNotExist,
     WrongPass,
+    Forbidden,
+    InvalidInput(&'static str),
+    InvalidPath(String),
+    EmailTaken,
+    UsernameTaken,
+    InvalidEmail,
+    EmptyComment,
+    Conflict,
+    Unauthorized,
+    TokenNotCreated,
 }
 
 impl From<DbErr> for ApiErr {
@@ -21,9 +31,41 @@ impl From<DbErr> for ApiErr {
     }
 }
 
+impl From<jsonwebtoken::errors::Error> for ApiErr {
+    fn from(_err: jsonwebtoken::errors::Error) -> ApiErr {
+        ApiErr::TokenNotCreated
+    }
+}
+
+impl From<TransactionError<DbErr>> for ApiErr {
+    fn from(err: TransactionError<DbErr>) -> ApiErr {
+        match err {
+            TransactionError::Connection(err) | TransactionError::Transaction(err) => {
+                ApiErr::DbErr(err)
+            }
+        }
+    }
+}
+
+/// Map violation of email or username uniqueness to dedicated `api error`, other unique
+/// violations to `Conflict`. Violated constraint identified by error message of database,
+/// other errors are kept as is.
+pub fn user_unique_err(err: DbErr) -> ApiErr {
+    match err.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(message)) if message.contains("email") => {
+            ApiErr::EmailTaken
+        }
+        Some(SqlErr::UniqueConstraintViolation(message)) if message.contains("username") => {
+            ApiErr::UsernameTaken
+        }
+        Some(SqlErr::UniqueConstraintViolation(_)) => ApiErr::Conflict,
+        _ => err.into(),
+    }
+}
+
 impl IntoResponse for ApiErr {
     fn into_response(self) -> Response {
-        let (status, error_message) = match self {
+        let (status, error_message): (StatusCode, &str) = match &self {
             ApiErr::DbErr(DbErr::Exec(_)) => (
                 StatusCode::UNPROCESSABLE_ENTITY,
                 "Record with same parameters already exist",
@@ -31,7 +73,23 @@ impl IntoResponse for ApiErr {
             ApiErr::DbErr(DbErr::RecordNotUpdated) => (StatusCode::NOT_FOUND, "Record not exist"),
             ApiErr::UserNotExist => (StatusCode::NOT_FOUND, "User not exist"),
             ApiErr::ArticleNotExist => (StatusCode::NOT_FOUND, "Article not exist"),
+            ApiErr::CommentNotExist => (StatusCode::NOT_FOUND, "Comment not exist"),
             ApiErr::WrongPass => (StatusCode::UNAUTHORIZED, "Wrong password"),
+            ApiErr::Unauthorized => (StatusCode::UNAUTHORIZED, "Authentication required"),
+            ApiErr::Forbidden => (StatusCode::FORBIDDEN, "Not allowed"),
+            ApiErr::EmailTaken => (StatusCode::CONFLICT, "Email already taken"),
+            ApiErr::UsernameTaken => (StatusCode::CONFLICT, "Username already taken"),
+            ApiErr::Conflict => (
+                StatusCode::CONFLICT,
+                "Record with same parameters already exist",
+            ),
+            ApiErr::InvalidEmail => (StatusCode::UNPROCESSABLE_ENTITY, "Invalid email"),
+            ApiErr::EmptyComment => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Comment must not be empty",
+            ),
+            ApiErr::InvalidInput(message) => (StatusCode::UNPROCESSABLE_ENTITY, message),
+            ApiErr::InvalidPath(message) => (StatusCode::BAD_REQUEST, message),
             _ => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "The server cannot process the request",
@@ -0,0 +1,115 @@
+use super::error::ApiErr;
+use axum::{
+    async_trait,
+    extract::{path::ErrorKind, rejection::PathRejection, FromRequestParts, Path as AxumPath},
+    http::request::Parts,
+};
+use serde::de::DeserializeOwned;
+
+/// Path parameters extractor, same as axum `Path` except rejection. Not parsed parameter
+/// responded with `InvalidPath` error, so it has the same envelope as other api errors.
+#[derive(Debug)]
+pub struct Path<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = ApiErr;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match AxumPath::<T>::from_request_parts(parts, state).await {
+            Ok(AxumPath(value)) => Ok(Path(value)),
+            Err(rejection) => Err(path_rejection_err(rejection)),
+        }
+    }
+}
+
+/// Convert path rejection into api error. Message names not parsed parameter when known,
+/// otherwise contains parsing error (e.g. for not valid uuid).
+fn path_rejection_err(rejection: PathRejection) -> ApiErr {
+    let message = match &rejection {
+        PathRejection::FailedToDeserializePathParams(err) => match err.kind() {
+            ErrorKind::ParseErrorAtKey { key, value, .. } => {
+                format!("Invalid path parameter `{key}`: `{value}`")
+            }
+            kind => format!("Invalid path parameter: {kind}"),
+        },
+        _ => rejection.body_text(),
+    };
+    ApiErr::InvalidPath(message)
+}
+
+#[cfg(test)]
+mod test_path {
+    use super::Path;
+    use axum::{
+        body::{Body, HttpBody},
+        http::{Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use serde::Deserialize;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    async fn parse_id(Path((_slug, id)): Path<(String, Uuid)>) -> String {
+        id.to_string()
+    }
+
+    #[derive(Deserialize)]
+    struct PageParams {
+        page: u32,
+    }
+
+    async fn parse_page(Path(params): Path<PageParams>) -> String {
+        params.page.to_string()
+    }
+
+    #[tokio::test]
+    async fn parsed_path() {
+        let app = Router::new().route("/:slug/:id", get(parse_id));
+        let id = Uuid::new_v4();
+
+        let request = Request::get(format!("/slug/{id}"))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let bytes = response.into_body().data().await.unwrap().unwrap();
+        assert_eq!(bytes, id.to_string());
+    }
+
+    #[tokio::test]
+    async fn not_parsed_uuid() {
+        let app = Router::new().route("/:slug/:id", get(parse_id));
+
+        let request = Request::get("/slug/not-uuid").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let bytes = response.into_body().data().await.unwrap().unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let message = value["error"].as_str().unwrap();
+        assert!(message.starts_with("Invalid path parameter: UUID parsing failed"));
+    }
+
+    #[tokio::test]
+    async fn not_parsed_named_parameter() {
+        let app = Router::new().route("/pages/:page", get(parse_page));
+
+        let request = Request::get("/pages/first").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let bytes = response.into_body().data().await.unwrap().unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"error": "Invalid path parameter `page`: `first`"})
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use crate::middleware::lifecycle::Lifecycle;
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+    Json,
+};
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbErr};
+use serde::Serialize;
+use std::{env, future::Future, sync::Arc, time::Instant};
+
+const DEFAULT_DB_LATENCY_THRESHOLD_MS: u64 = 500;
+const APP_DB_LATENCY_THRESHOLD_MS: &str = "APP_DB_LATENCY_THRESHOLD_MS";
+
+/// Axum handler for health check. Reports `draining` with 503 status during shutdown,
+/// so load balancer stops sending traffic while existing requests complete.
+/// Returns json object with server status and number of requests in flight.
+pub async fn health(
+    Extension(lifecycle): Extension<Arc<Lifecycle>>,
+) -> (StatusCode, Json<HealthDto>) {
+    let (status_code, status) = match lifecycle.is_shutting_down() {
+        true => (StatusCode::SERVICE_UNAVAILABLE, "draining"),
+        false => (StatusCode::OK, "ok"),
+    };
+
+    let health_dto = HealthDto {
+        status,
+        in_flight: lifecycle.in_flight(),
+    };
+    (status_code, Json(health_dto))
+}
+
+/// Axum handler for database health check. Times trivial query, reports unhealthy with
+/// 503 status when query fails or exceeds APP_DB_LATENCY_THRESHOLD_MS.
+/// Returns json object with database status and query latency in milliseconds.
+pub async fn db_health(State(db): State<DatabaseConnection>) -> (StatusCode, Json<DbHealthDto>) {
+    let probe = async {
+        db.execute_unprepared("SELECT 1").await?;
+        Ok(())
+    };
+    check_db_latency(probe, get_db_latency_threshold_ms()).await
+}
+
+/// Time provided database `probe` and compare its latency with threshold (in milliseconds).
+async fn check_db_latency(
+    probe: impl Future<Output = Result<(), DbErr>>,
+    threshold_ms: u64,
+) -> (StatusCode, Json<DbHealthDto>) {
+    let start = Instant::now();
+    let result = probe.await;
+    let latency_ms = start.elapsed().as_millis() as u64;
+
+    let ok = result.is_ok() && latency_ms <= threshold_ms;
+    let status_code = match ok {
+        true => StatusCode::OK,
+        false => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    (status_code, Json(DbHealthDto { ok, latency_ms }))
+}
+
+/// Return APP_DB_LATENCY_THRESHOLD_MS from environment varibles, default value used
+/// if not provided or not valid
+fn get_db_latency_threshold_ms() -> u64 {
+    env::var(APP_DB_LATENCY_THRESHOLD_MS)
+        .ok()
+        .and_then(|val| val.parse().ok())
+        .unwrap_or(DEFAULT_DB_LATENCY_THRESHOLD_MS)
+}
+
+/// Struct describing JSON object, returned by handler. Contains server status.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthDto {
+    status: &'static str,
+    in_flight: usize,
+}
+
+/// Struct describing JSON object, returned by database health handler.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct DbHealthDto {
+    ok: bool,
+    latency_ms: u64,
+}
+
+#[cfg(test)]
+mod test_health {
+    use super::{health, HealthDto};
+    use crate::middleware::lifecycle::Lifecycle;
+    use axum::{extract::Extension, http::StatusCode, Json};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn ok_then_draining() {
+        let lifecycle = Arc::new(Lifecycle::default());
+
+        let (status_code, Json(result)) = health(Extension(lifecycle.clone())).await;
+        assert_eq!(status_code, StatusCode::OK);
+        assert_eq!(
+            result,
+            HealthDto {
+                status: "ok",
+                in_flight: 0
+            }
+        );
+
+        lifecycle.start_shutdown();
+
+        let (status_code, Json(result)) = health(Extension(lifecycle)).await;
+        assert_eq!(status_code, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(result.status, "draining");
+    }
+}
+
+#[cfg(test)]
+mod test_db_health {
+    use super::{check_db_latency, db_health, get_db_latency_threshold_ms};
+    use super::{APP_DB_LATENCY_THRESHOLD_MS, DEFAULT_DB_LATENCY_THRESHOLD_MS};
+    use crate::tests::{Operation::Migration, TestDataBuilder, TestErr};
+    use axum::{extract::State, http::StatusCode, Json};
+    use serial_test::serial;
+    use std::{env, time::Duration};
+
+    #[tokio::test]
+    #[serial]
+    async fn healthy_database() -> Result<(), TestErr> {
+        env::remove_var(APP_DB_LATENCY_THRESHOLD_MS);
+        let (connection, _) = TestDataBuilder::new().users(Migration).build().await?;
+
+        let (status_code, Json(result)) = db_health(State(connection)).await;
+        assert_eq!(status_code, StatusCode::OK);
+        assert!(result.ok);
+        assert!(result.latency_ms < DEFAULT_DB_LATENCY_THRESHOLD_MS);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn slow_query_unhealthy() {
+        let slow_probe = async {
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            Ok(())
+        };
+
+        let (status_code, Json(result)) = check_db_latency(slow_probe, 10).await;
+        assert_eq!(status_code, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(!result.ok);
+        assert!(result.latency_ms >= 30);
+    }
+
+    #[test]
+    #[serial]
+    fn threshold_from_env() {
+        env::set_var(APP_DB_LATENCY_THRESHOLD_MS, "25");
+        assert_eq!(get_db_latency_threshold_ms(), 25);
+        env::set_var(APP_DB_LATENCY_THRESHOLD_MS, "not a number");
+        assert_eq!(
+            get_db_latency_threshold_ms(),
+            DEFAULT_DB_LATENCY_THRESHOLD_MS
+        );
+        env::remove_var(APP_DB_LATENCY_THRESHOLD_MS);
+    }
+}
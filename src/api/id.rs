@@ -0,0 +1,80 @@
+use std::{
+    env,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use uuid::Uuid;
+
+const APP_SORTABLE_IDS: &str = "APP_SORTABLE_IDS";
+const MAX_SEQUENCE: u16 = 0x0FFF;
+
+/// Millisecond timestamp and sequence of the last time ordered id.
+static LAST_SORTABLE_ID: Mutex<(u64, u16)> = Mutex::new((0, 0));
+
+/// Generate identifier for new entity. Random (v4) by default, time ordered (v7 layout)
+/// when enabled with APP_SORTABLE_IDS. Both are stored in the same `Uuid` columns.
+pub fn new_id() -> Uuid {
+    match sortable_ids_enabled() {
+        true => new_sortable_id(),
+        false => Uuid::new_v4(),
+    }
+}
+
+/// Build UUIDv7: 48 bit unix timestamp in milliseconds, 12 bit sequence and random tail.
+/// Sequence keeps ids generated in the same millisecond (or after clock moved back)
+/// monotonically increasing within the process.
+fn new_sortable_id() -> Uuid {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|dur| dur.as_millis() as u64)
+        .unwrap_or_default();
+
+    let (millis, seq) = {
+        let mut last = LAST_SORTABLE_ID.lock().unwrap();
+        *last = match *last {
+            (last_millis, _) if now > last_millis => (now, 0),
+            (last_millis, seq) if seq < MAX_SEQUENCE => (last_millis, seq + 1),
+            (last_millis, _) => (last_millis + 1, 0),
+        };
+        *last
+    };
+
+    let mut bytes = *Uuid::new_v4().as_bytes();
+    bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+    bytes[6] = 0x70 | (seq >> 8) as u8;
+    bytes[7] = seq as u8;
+    bytes[8] = 0x80 | (bytes[8] & 0x3F);
+    Uuid::from_bytes(bytes)
+}
+
+/// Return APP_SORTABLE_IDS from environment varibles, random ids by default
+fn sortable_ids_enabled() -> bool {
+    env::var(APP_SORTABLE_IDS)
+        .map(|val| val.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod test_new_id {
+    use super::{new_id, APP_SORTABLE_IDS};
+    use serial_test::serial;
+    use std::env;
+
+    #[test]
+    #[serial]
+    fn random_by_default() {
+        env::remove_var(APP_SORTABLE_IDS);
+        assert_eq!(new_id().get_version_num(), 4);
+    }
+
+    #[test]
+    #[serial]
+    fn sortable_ids_monotonically_increasing() {
+        env::set_var(APP_SORTABLE_IDS, "true");
+        let ids: Vec<_> = (0..5000).map(|_| new_id()).collect();
+        env::remove_var(APP_SORTABLE_IDS);
+
+        assert!(ids.iter().all(|id| id.get_version_num() == 7));
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
@@ -0,0 +1,216 @@
+use axum::{Extension, Json};
+use serde::Serialize;
+use std::env;
+
+const DEFAULT_MAX_TAGS_PER_ARTICLE: usize = 10;
+const DEFAULT_MAX_BODY_LENGTH: usize = 65_536;
+const DEFAULT_MAX_PAGE_SIZE: u64 = 100;
+const DEFAULT_PAGE_SIZE: u64 = 20;
+const DEFAULT_MAX_EXPANDED_FOLLOWING: u64 = 100;
+const APP_MAX_TAGS_PER_ARTICLE: &str = "APP_MAX_TAGS_PER_ARTICLE";
+const APP_MAX_BODY_LENGTH: &str = "APP_MAX_BODY_LENGTH";
+const APP_MAX_PAGE_SIZE: &str = "APP_MAX_PAGE_SIZE";
+const DEFAULT_PAGE_LIMIT: &str = "DEFAULT_PAGE_LIMIT";
+const APP_MAX_EXPANDED_FOLLOWING: &str = "APP_MAX_EXPANDED_FOLLOWING";
+
+/// Axum handler for fetch limits applied by server, so clients could validate input in advance.
+/// Returns json object with configured limits.
+pub async fn get_limits(Extension(page_config): Extension<PageConfig>) -> Json<LimitsDto> {
+    Json(LimitsDto {
+        max_tags_per_article: get_max_tags_per_article(),
+        max_body_length: get_max_body_length(),
+        max_page_size: page_config.max_limit,
+        default_page_size: page_config.default_limit,
+    })
+}
+
+/// Page size applied to list endpoints. Loaded once on start (see PageConfig::from_env) and
+/// shared with handlers, so every repo query receives already resolved limit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageConfig {
+    pub default_limit: u64,
+    pub max_limit: u64,
+}
+
+impl PageConfig {
+    /// Read DEFAULT_PAGE_LIMIT and APP_MAX_PAGE_SIZE from environment variables, default
+    /// values used if not provided or not valid.
+    pub fn from_env() -> Self {
+        PageConfig {
+            default_limit: get_limit(DEFAULT_PAGE_LIMIT).unwrap_or(DEFAULT_PAGE_SIZE),
+            max_limit: get_limit(APP_MAX_PAGE_SIZE).unwrap_or(DEFAULT_MAX_PAGE_SIZE),
+        }
+    }
+
+    /// Resolve page size from `limit` query parameter. Default limit used if parameter
+    /// not provided or not valid, result capped by max limit.
+    pub fn page_limit(&self, limit: Option<&String>) -> u64 {
+        resolve_page_limit(limit, self.default_limit, self.max_limit)
+    }
+}
+
+impl Default for PageConfig {
+    fn default() -> Self {
+        PageConfig {
+            default_limit: DEFAULT_PAGE_SIZE,
+            max_limit: DEFAULT_MAX_PAGE_SIZE,
+        }
+    }
+}
+
+/// Return APP_MAX_TAGS_PER_ARTICLE from environment variables, default value used
+/// if not provided or not valid
+pub fn get_max_tags_per_article() -> usize {
+    get_limit(APP_MAX_TAGS_PER_ARTICLE).unwrap_or(DEFAULT_MAX_TAGS_PER_ARTICLE)
+}
+
+/// Return APP_MAX_BODY_LENGTH (in characters) from environment variables, default value
+/// used if not provided or not valid
+pub fn get_max_body_length() -> usize {
+    get_limit(APP_MAX_BODY_LENGTH).unwrap_or(DEFAULT_MAX_BODY_LENGTH)
+}
+
+/// Return APP_MAX_EXPANDED_FOLLOWING (max number of followed usernames returned with current
+/// user) from environment variables, default value used if not provided or not valid
+pub fn get_max_expanded_following() -> u64 {
+    get_limit(APP_MAX_EXPANDED_FOLLOWING).unwrap_or(DEFAULT_MAX_EXPANDED_FOLLOWING)
+}
+
+/// Parse page size from `limit` query parameter, provided default used if parameter not
+/// provided or not valid, result capped by provided maximum.
+fn resolve_page_limit(limit: Option<&String>, default: u64, max: u64) -> u64 {
+    limit
+        .and_then(|lm| lm.parse::<u64>().ok())
+        .unwrap_or(default)
+        .min(max)
+}
+
+/// Parse positive limit from environment variable of provided name.
+fn get_limit<T: std::str::FromStr + Default + PartialOrd>(name: &str) -> Option<T> {
+    env::var(name)
+        .ok()
+        .and_then(|val| val.trim().parse().ok())
+        .filter(|val| *val > T::default())
+}
+
+/// Struct describing JSON object, returned by handler. Contains limits applied by server.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LimitsDto {
+    max_tags_per_article: usize,
+    max_body_length: usize,
+    max_page_size: u64,
+    default_page_size: u64,
+}
+
+#[cfg(test)]
+mod test_get_limits {
+    use super::{get_limits, LimitsDto, PageConfig};
+    use super::{APP_MAX_BODY_LENGTH, APP_MAX_PAGE_SIZE, APP_MAX_TAGS_PER_ARTICLE};
+    use super::{
+        DEFAULT_MAX_BODY_LENGTH, DEFAULT_MAX_PAGE_SIZE, DEFAULT_MAX_TAGS_PER_ARTICLE,
+        DEFAULT_PAGE_SIZE,
+    };
+    use axum::{Extension, Json};
+    use serial_test::serial;
+    use std::env;
+
+    #[tokio::test]
+    #[serial]
+    async fn defaults_without_env() {
+        env::remove_var(APP_MAX_TAGS_PER_ARTICLE);
+        env::remove_var(APP_MAX_BODY_LENGTH);
+        env::set_var(APP_MAX_PAGE_SIZE, "0");
+
+        let Json(result) = get_limits(Extension(PageConfig::from_env())).await;
+        assert_eq!(
+            result,
+            LimitsDto {
+                max_tags_per_article: DEFAULT_MAX_TAGS_PER_ARTICLE,
+                max_body_length: DEFAULT_MAX_BODY_LENGTH,
+                max_page_size: DEFAULT_MAX_PAGE_SIZE,
+                default_page_size: DEFAULT_PAGE_SIZE,
+            }
+        );
+
+        env::remove_var(APP_MAX_PAGE_SIZE);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn reflects_env_overrides() {
+        env::set_var(APP_MAX_TAGS_PER_ARTICLE, "3");
+        env::set_var(APP_MAX_BODY_LENGTH, "1000");
+        env::set_var(APP_MAX_PAGE_SIZE, "50");
+
+        let Json(result) = get_limits(Extension(PageConfig::from_env())).await;
+        let value = serde_json::to_value(result).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "maxTagsPerArticle": 3,
+                "maxBodyLength": 1000,
+                "maxPageSize": 50,
+                "defaultPageSize": DEFAULT_PAGE_SIZE,
+            })
+        );
+
+        env::remove_var(APP_MAX_TAGS_PER_ARTICLE);
+        env::remove_var(APP_MAX_BODY_LENGTH);
+        env::remove_var(APP_MAX_PAGE_SIZE);
+    }
+}
+
+#[cfg(test)]
+mod test_page_config {
+    use super::{PageConfig, APP_MAX_PAGE_SIZE, DEFAULT_PAGE_LIMIT};
+    use serial_test::serial;
+    use std::env;
+
+    #[test]
+    #[serial]
+    fn loaded_from_env() {
+        env::set_var(DEFAULT_PAGE_LIMIT, "30");
+        env::set_var(APP_MAX_PAGE_SIZE, "40");
+
+        let page_config = PageConfig::from_env();
+        assert_eq!(
+            page_config,
+            PageConfig {
+                default_limit: 30,
+                max_limit: 40,
+            }
+        );
+        assert_eq!(page_config.page_limit(None), 30);
+        assert_eq!(page_config.page_limit(Some(&"50".to_owned())), 40);
+
+        env::remove_var(DEFAULT_PAGE_LIMIT);
+        env::remove_var(APP_MAX_PAGE_SIZE);
+        assert_eq!(PageConfig::from_env(), PageConfig::default());
+    }
+}
+
+#[cfg(test)]
+mod test_resolve_page_limit {
+    use super::resolve_page_limit;
+
+    #[test]
+    fn default_without_limit() {
+        assert_eq!(resolve_page_limit(None, 20, 100), 20);
+        assert_eq!(resolve_page_limit(Some(&"abc".to_owned()), 20, 100), 20);
+        assert_eq!(resolve_page_limit(Some(&"-5".to_owned()), 20, 100), 20);
+    }
+
+    #[test]
+    fn limit_within_max() {
+        assert_eq!(resolve_page_limit(Some(&"5".to_owned()), 20, 100), 5);
+        assert_eq!(resolve_page_limit(Some(&"100".to_owned()), 20, 100), 100);
+    }
+
+    #[test]
+    fn limit_above_max_clamped() {
+        assert_eq!(resolve_page_limit(Some(&"1000".to_owned()), 20, 100), 100);
+        // Default is capped as well
+        assert_eq!(resolve_page_limit(None, 50, 10), 10);
+    }
+}
@@ -0,0 +1,258 @@
+use crate::api::{error::ApiErr, extract::Path};
+use crate::middleware::auth::Token;
+use crate::repo::article::get_article_model_by_slug;
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+    Extension,
+};
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::broadcast::{self, error::RecvError, Receiver, Sender};
+use uuid::Uuid;
+
+const CHANNEL_CAPACITY: usize = 16;
+
+/// Broadcast channels of live updates, one per key (article or user) watched by at least one
+/// client. Channel created by first subscriber and dropped once no subscribers left.
+#[derive(Debug)]
+pub struct Hub<T> {
+    channels: Mutex<HashMap<Uuid, Sender<T>>>,
+}
+
+/// Favorites count updates keyed by article id.
+pub type FavoritesHub = Hub<i32>;
+
+/// Notifications keyed by id of notified user.
+pub type NotificationsHub = Hub<Notification>;
+
+impl<T> Default for Hub<T> {
+    fn default() -> Self {
+        Hub {
+            channels: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<T: Clone> Hub<T> {
+    /// Subscribe to updates published with `key`.
+    pub fn subscribe(&self, key: Uuid) -> Receiver<T> {
+        let mut channels = self.channels.lock().unwrap();
+        channels
+            .entry(key)
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// Send update to subscribers of `key`. Nothing is sent when key has no subscribers,
+    /// channel left without subscribers is dropped.
+    pub fn publish(&self, key: Uuid, update: T) {
+        let mut channels = self.channels.lock().unwrap();
+        let sent = channels.get(&key).map(|sender| sender.send(update).is_ok());
+        if sent == Some(false) {
+            channels.remove(&key);
+        }
+    }
+
+    /// Drop channel of `key` when its last subscriber is gone.
+    fn release(&self, key: Uuid) {
+        let mut channels = self.channels.lock().unwrap();
+        if channels
+            .get(&key)
+            .is_some_and(|sender| sender.receiver_count() == 0)
+        {
+            channels.remove(&key);
+        }
+    }
+}
+
+/// Notification pushed to user over WebSocket, tagged with its type.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Notification {
+    /// Article of notified user favorited by user with `username`.
+    #[serde(rename_all = "camelCase")]
+    ArticleFavorited { slug: String, username: String },
+}
+
+/// Axum handler for WebSocket of article favorites count. Client subscribed to the article
+/// before connection is upgraded, so no update published after handshake is missed.
+/// Returns switching protocols response on success, otherwise returns an `api error`.
+pub async fn favorites_ws(
+    ws: WebSocketUpgrade,
+    Path(slug): Path<String>,
+    maybe_token: Option<Extension<Token>>,
+    Extension(hub): Extension<Arc<FavoritesHub>>,
+    State(db): State<DatabaseConnection>,
+) -> Result<Response, ApiErr> {
+    let article = get_article_model_by_slug(&db, &slug, maybe_token.map(|tkn| tkn.id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+
+    let receiver = hub.subscribe(article.id);
+    Ok(ws.on_upgrade(move |socket| {
+        push_updates(socket, receiver, hub, article.id, |favorites_count| {
+            FavoritesCountDto { favorites_count }
+        })
+    }))
+}
+
+/// Axum handler for WebSocket of notifications of logged user, e.g. favorites of user
+/// articles. Browser clients authenticate with auth cookie (see AUTH_COOKIE_NAME).
+/// Returns switching protocols response.
+pub async fn notifications_ws(
+    ws: WebSocketUpgrade,
+    Extension(token): Extension<Token>,
+    Extension(hub): Extension<Arc<NotificationsHub>>,
+) -> Response {
+    let receiver = hub.subscribe(token.id);
+    ws.on_upgrade(move |socket| push_updates(socket, receiver, hub, token.id, |update| update))
+}
+
+/// Push updates of `key`, converted to JSON message with `to_message`, to client until
+/// client disconnects.
+async fn push_updates<T: Clone, M: Serialize>(
+    mut socket: WebSocket,
+    mut receiver: Receiver<T>,
+    hub: Arc<Hub<T>>,
+    key: Uuid,
+    to_message: impl Fn(T) -> M,
+) {
+    loop {
+        tokio::select! {
+            update = receiver.recv() => match update {
+                Ok(update) => {
+                    let Ok(message) = serde_json::to_string(&to_message(update)) else {
+                        break;
+                    };
+                    if socket.send(Message::Text(message)).await.is_err() {
+                        break;
+                    }
+                }
+                // Slow client missed some updates, favorites count is actual in the next one
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+        }
+    }
+
+    drop(receiver);
+    hub.release(key);
+}
+
+/// Struct describing JSON message, pushed to WebSocket client. Contains favorites count.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FavoritesCountDto {
+    favorites_count: i32,
+}
+
+#[cfg(test)]
+mod test_favorites_ws {
+    use super::{favorites_ws, FavoritesHub, NotificationsHub};
+    use crate::api::article::favorite_article;
+    use crate::api::extract::Path;
+    use crate::middleware::auth::Token;
+    use crate::repo::user_cache::UserCache;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, routing::get, Extension, Router, Server};
+    use futures::StreamExt;
+    use std::{net::TcpListener, sync::Arc, time::Duration};
+    use tokio::time::{sleep, timeout};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn favorite_pushes_count() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let user = users.unwrap().into_iter().next().unwrap();
+        let article = articles.unwrap().into_iter().next().unwrap();
+        let hub = Arc::new(FavoritesHub::default());
+
+        let app = Router::new()
+            .route("/api/articles/:slug/ws", get(favorites_ws))
+            .layer(Extension(hub.clone()))
+            .with_state(connection.clone());
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = Server::from_tcp(listener).unwrap();
+        tokio::spawn(server.serve(app.into_make_service()));
+
+        let url = format!("ws://{address}/api/articles/{}/ws", article.slug);
+        let (mut client, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+
+        let token = Token {
+            exp: 35,
+            id: user.id,
+        };
+        let _ = favorite_article(
+            Path(article.slug),
+            Extension(token),
+            Extension(hub.clone()),
+            Extension(Arc::new(NotificationsHub::default())),
+            Extension(Arc::new(UserCache::default())),
+            State(connection),
+        )
+        .await?;
+
+        let message = timeout(Duration::from_secs(5), client.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(message.into_text().unwrap(), r#"{"favoritesCount":1}"#);
+
+        // Channel dropped once client disconnects
+        client.close(None).await.unwrap();
+        timeout(Duration::from_secs(5), async {
+            while !hub.channels.lock().unwrap().is_empty() {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        Ok(())
+    }
+
+    #[test]
+    fn publish_without_subscribers() {
+        let hub = FavoritesHub::default();
+        let article_id = Uuid::new_v4();
+
+        let receiver = hub.subscribe(article_id);
+        drop(receiver);
+        hub.publish(article_id, 1);
+        assert!(hub.channels.lock().unwrap().is_empty());
+
+        let mut receiver = hub.subscribe(article_id);
+        hub.publish(article_id, 2);
+        assert_eq!(receiver.try_recv(), Ok(2));
+    }
+}
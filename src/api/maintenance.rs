@@ -0,0 +1,102 @@
+use crate::api::error::ApiErr;
+use crate::repo::maintenance::{find_dangling_rows, purge_dangling_rows};
+use axum::{extract::State, Json};
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+
+/// Axum handler for report join rows referencing not existing records (see find_dangling_rows
+/// for details). Only admin allowed to inspect dangling rows (see require_admin).
+/// Returns json object with number of dangling rows per table on success, otherwise returns an
+/// `api error`.
+pub async fn get_dangling_rows(
+    State(db): State<DatabaseConnection>,
+) -> Result<Json<DanglingRowsDto>, ApiErr> {
+    let report = find_dangling_rows(&db).await?;
+
+    let dangling_dto = DanglingRowsDto {
+        favorited_articles: report.favorited_articles.len(),
+        article_tags: report.article_tags.len(),
+    };
+    Ok(Json(dangling_dto))
+}
+
+/// Axum handler for delete join rows referencing not existing records (see purge_dangling_rows
+/// for details). Only admin allowed to purge dangling rows (see require_admin).
+/// Returns json object with number of deleted rows on success, otherwise returns an `api error`.
+pub async fn purge_dangling(
+    State(db): State<DatabaseConnection>,
+) -> Result<Json<PurgedRowsDto>, ApiErr> {
+    let deleted = purge_dangling_rows(&db).await?;
+
+    Ok(Json(PurgedRowsDto { deleted }))
+}
+
+/// Struct describing JSON object, returned by dangling rows report handler.
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DanglingRowsDto {
+    favorited_articles: usize,
+    article_tags: usize,
+}
+
+/// Struct describing JSON object, returned by dangling rows purge handler.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct PurgedRowsDto {
+    deleted: u64,
+}
+
+#[cfg(test)]
+mod test_dangling_rows {
+    use super::{get_dangling_rows, purge_dangling, DanglingRowsDto, PurgedRowsDto};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, Json};
+    use entity::entities::{article_tag, prelude::ArticleTag};
+    use sea_orm::{ConnectionTrait, EntityTrait, Set};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn report_and_purge() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Migration)
+            .tags(Insert(1))
+            .article_tags(Insert(vec![(1, 1)]))
+            .build()
+            .await?;
+        let article_id = articles.unwrap()[0].id;
+
+        // Link to missing tag could be inserted only with foreign keys disabled
+        connection
+            .execute_unprepared("PRAGMA foreign_keys = OFF")
+            .await?;
+        ArticleTag::insert(article_tag::ActiveModel {
+            article_id: Set(article_id),
+            tag_id: Set(Uuid::new_v4()),
+            ..Default::default()
+        })
+        .exec(&connection)
+        .await?;
+        connection
+            .execute_unprepared("PRAGMA foreign_keys = ON")
+            .await?;
+
+        let Json(report) = get_dangling_rows(State(connection.clone())).await?;
+        let expected = DanglingRowsDto {
+            favorited_articles: 0,
+            article_tags: 1,
+        };
+        assert_eq!(report, expected);
+
+        let Json(purged) = purge_dangling(State(connection.clone())).await?;
+        assert_eq!(purged, PurgedRowsDto { deleted: 1 });
+
+        let Json(report) = get_dangling_rows(State(connection)).await?;
+        assert_eq!(report.article_tags, 0);
+
+        Ok(())
+    }
+}
@@ -1,6 +1,15 @@
 pub mod article;
 pub mod comment;
 pub mod error;
+pub mod extract;
+pub mod health;
+pub mod id;
+pub mod limits;
+pub mod live;
+pub mod maintenance;
 pub mod profile;
+pub mod reaction;
+pub mod sanitize;
+pub mod sitemap;
 pub mod tags;
 pub mod user;
@@ -1,17 +1,21 @@
 use crate::middleware::auth::Token;
 use crate::repo::{
-    follower::{create_follower, delete_follower},
-    user::{get_profile_by_username, get_user_by_username, Profile},
+    block::{block_user as repo_block_user, unblock_user as repo_unblock_user},
+    follower::{create_follower, delete_follower, get_followers, get_following},
+    user::{
+        get_mutual_follows, get_profile_by_username, get_user_by_username, search_users, Profile,
+    },
 };
 use axum::{
-    extract::{Path, State},
+    extract::{Query, State},
     Extension, Json,
 };
 use entity::entities::{follower, user};
 use sea_orm::{ActiveValue::Set, DatabaseConnection};
 use serde::Serialize;
+use std::collections::HashMap;
 
-use super::error::ApiErr;
+use super::{error::ApiErr, extract::Path, limits::PageConfig};
 
 /// Axum handler for retrieve information about user with provided username. Optional
 /// token used to determine whether the logged in user is a follower of the profile.
@@ -31,6 +35,110 @@ pub async fn get_profile(
     Ok(Json(profile_dto))
 }
 
+/// Axum handler for search profiles which username or bio starts with `q` query parameter.
+/// Limit response by limit (capped by APP_MAX_PAGE_SIZE) and offset parameters. Optional token
+/// used to determine whether the logged in user is a follower of the profiles.
+/// Returns json object with list of profiles on success, otherwise returns an `api error`.
+pub async fn search_profiles(
+    Query(params): Query<HashMap<String, String>>,
+    maybe_token: Option<Extension<Token>>,
+    State(db): State<DatabaseConnection>,
+    Extension(page_config): Extension<PageConfig>,
+) -> Result<Json<ProfilesDto>, ApiErr> {
+    let query = params.get("q").map(|q| q.trim()).unwrap_or_default();
+
+    // Limit number of profiles (default is configured by DEFAULT_PAGE_LIMIT, capped by
+    // APP_MAX_PAGE_SIZE):
+    let limit = page_config.page_limit(params.get("limit"));
+
+    // Offset/skip number of profiles (default is 0):
+    let offset = params
+        .get("offset")
+        .map(|lm| lm.parse::<u64>())
+        .filter(|res| res.is_ok())
+        .map(|res| res.unwrap());
+
+    let profiles = search_users(&db, query, limit, offset, maybe_token.map(|tkn| tkn.id)).await?;
+
+    let profiles_dto = ProfilesDto { profiles };
+    Ok(Json(profiles_dto))
+}
+
+/// Axum handler for fetch profiles of users who both follow and are followed by user with
+/// provided username. Optional token used to determine whether the logged in user is a
+/// follower of the profiles.
+/// Returns json object with list of profiles on success, otherwise returns an `api error`.
+pub async fn mutual_follows(
+    State(db): State<DatabaseConnection>,
+    maybe_token: Option<Extension<Token>>,
+    Path(username): Path<String>,
+) -> Result<Json<ProfilesDto>, ApiErr> {
+    let user: user::Model = get_user_by_username(&db, &username)
+        .await?
+        .ok_or(ApiErr::UserNotExist)?;
+
+    let profiles = get_mutual_follows(&db, user.id, maybe_token.map(|tkn| tkn.id)).await?;
+
+    let profiles_dto = ProfilesDto { profiles };
+    Ok(Json(profiles_dto))
+}
+
+/// Axum handler for fetch profiles of users following user with provided username.
+/// Optional token used to determine whether the logged in user is a follower of the profiles.
+/// Returns json object with list of profiles on success, otherwise returns an `api error`.
+pub async fn list_followers(
+    State(db): State<DatabaseConnection>,
+    maybe_token: Option<Extension<Token>>,
+    Path(username): Path<String>,
+) -> Result<Json<ProfilesDto>, ApiErr> {
+    let user: user::Model = get_user_by_username(&db, &username)
+        .await?
+        .ok_or(ApiErr::UserNotExist)?;
+
+    let profiles = get_followers(&db, user.id, maybe_token.map(|tkn| tkn.id)).await?;
+
+    let profiles_dto = ProfilesDto { profiles };
+    Ok(Json(profiles_dto))
+}
+
+/// Axum handler for fetch profiles of users followed by user with provided username.
+/// Limit response by limit (capped by APP_MAX_PAGE_SIZE) and offset parameters.
+/// Optional token used to determine whether the logged in user is a follower of the profiles.
+/// Returns json object with list of profiles on success, otherwise returns an `api error`.
+pub async fn list_following(
+    Query(params): Query<HashMap<String, String>>,
+    State(db): State<DatabaseConnection>,
+    Extension(page_config): Extension<PageConfig>,
+    maybe_token: Option<Extension<Token>>,
+    Path(username): Path<String>,
+) -> Result<Json<ProfilesDto>, ApiErr> {
+    // Limit number of profiles (capped by APP_MAX_PAGE_SIZE):
+    let limit = params
+        .get("limit")
+        .and_then(|lm| lm.parse::<u64>().ok())
+        .unwrap_or(u64::MAX)
+        .min(page_config.max_limit);
+
+    // Offset/skip number of profiles (default is 0):
+    let offset = params.get("offset").and_then(|off| off.parse::<u64>().ok());
+
+    let user: user::Model = get_user_by_username(&db, &username)
+        .await?
+        .ok_or(ApiErr::UserNotExist)?;
+
+    let profiles = get_following(
+        &db,
+        user.id,
+        Some(limit),
+        offset,
+        maybe_token.map(|tkn| tkn.id),
+    )
+    .await?;
+
+    let profiles_dto = ProfilesDto { profiles };
+    Ok(Json(profiles_dto))
+}
+
 /// Axum handler for setting logged user as follower of provided (by username) user.
 /// Returns json object with profile on success, otherwise returns an `api error`.
 pub async fn follow_user(
@@ -87,23 +195,80 @@ pub async fn unfollow_user(
     Ok(Json(profile_dto))
 }
 
+/// Axum handler for blocking provided (by username) user, articles and comments of blocked
+/// user are hidden from logged user.
+/// Returns json object with profile on success, otherwise returns an `api error`.
+pub async fn block_user(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<Token>,
+    Path(username): Path<String>,
+) -> Result<Json<ProfileDto>, ApiErr> {
+    let current_user_id = token.id;
+
+    let blocked_user: user::Model = get_user_by_username(&db, &username)
+        .await?
+        .ok_or(ApiErr::UserNotExist)?;
+
+    if blocked_user.id == current_user_id {
+        return Err(ApiErr::InvalidInput("Can't block yourself"));
+    }
+
+    repo_block_user(&db, current_user_id, blocked_user.id).await?;
+
+    let profile = get_profile_by_username(&db, &username, Some(current_user_id))
+        .await?
+        .ok_or(ApiErr::UserNotExist)?;
+
+    let profile_dto = ProfileDto { profile };
+    Ok(Json(profile_dto))
+}
+
+/// Axum handler for unblock provided (by username) user.
+/// Returns json object with profile on success, otherwise returns an `api error`.
+pub async fn unblock_user(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<Token>,
+    Path(username): Path<String>,
+) -> Result<Json<ProfileDto>, ApiErr> {
+    let current_user_id = token.id;
+
+    let blocked_user: user::Model = get_user_by_username(&db, &username)
+        .await?
+        .ok_or(ApiErr::UserNotExist)?;
+
+    repo_unblock_user(&db, current_user_id, blocked_user.id).await?;
+
+    let profile = get_profile_by_username(&db, &username, Some(current_user_id))
+        .await?
+        .ok_or(ApiErr::UserNotExist)?;
+
+    let profile_dto = ProfileDto { profile };
+    Ok(Json(profile_dto))
+}
+
 /// Struct describing JSON object for profile routes requests. Contains user profile data.
 #[derive(Debug, PartialEq, Serialize)]
 pub struct ProfileDto {
     profile: Profile,
 }
 
+/// Struct describing JSON object, returned by handler. Contains list of profiles.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ProfilesDto {
+    profiles: Vec<Profile>,
+}
+
 #[cfg(test)]
 mod test_get_current_user {
     use super::{get_profile, ProfileDto};
     use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
     use crate::middleware::auth::Token;
     use crate::repo::user::Profile;
     use crate::tests::{
         Operation::{Create, Insert},
         TestData, TestDataBuilder, TestErr,
     };
-    use axum::extract::Path;
     use axum::{extract::State, Extension, Json};
     use dotenvy::dotenv;
     use entity::entities::user;
@@ -163,20 +328,247 @@ mod test_get_current_user {
     }
 }
 
+#[cfg(test)]
+mod test_search_profiles {
+    use super::search_profiles;
+    use crate::api::limits::PageConfig;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestDataBuilder, TestErr,
+    };
+    use axum::extract::Query;
+    use axum::{extract::State, Extension, Json};
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn search_existing_profiles() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(12))
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let params: HashMap<String, String> = [
+            ("q".to_owned(), "username1".to_owned()),
+            ("limit".to_owned(), "3".to_owned()),
+        ]
+        .into_iter()
+        .collect();
+
+        let result = search_profiles(
+            Query(params),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let Json(result) = result;
+
+        assert_eq!(result.profiles.len(), 3);
+        assert_eq!(result.profiles[0].username, "username1");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn search_without_query() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(2))
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let params: HashMap<String, String> = HashMap::new();
+
+        let result = search_profiles(
+            Query(params),
+            None,
+            State(connection),
+            Extension(PageConfig::default()),
+        )
+        .await?;
+        let Json(result) = result;
+
+        assert!(result.profiles.is_empty());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_mutual_follows {
+    use super::mutual_follows;
+    use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, Json};
+
+    #[tokio::test]
+    async fn get_only_mutual() -> Result<(), TestErr> {
+        // user1 and user2 follow each other, user3 only follows user1
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(3))
+            .followers(Insert(vec![(1, 2), (2, 1), (1, 3)]))
+            .build()
+            .await?;
+        let username = users.unwrap()[0].username.clone();
+
+        let result = mutual_follows(State(connection), None, Path(username)).await?;
+        let Json(result) = result;
+
+        assert_eq!(result.profiles.len(), 1);
+        assert_eq!(result.profiles[0].username, "username2");
+        assert!(!result.profiles[0].following);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_non_existing_user() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let result = mutual_follows(State(connection), None, Path("not exist".to_owned())).await;
+
+        assert!(matches!(result, Err(ApiErr::UserNotExist)));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_list_followers {
+    use super::{list_followers, list_following};
+    use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::api::limits::PageConfig;
+    use crate::middleware::auth::Token;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::extract::Query;
+    use axum::{extract::State, Extension, Json};
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn user_with_several_followers() -> Result<(), TestErr> {
+        // user2 and user3 follow user1, user2 follows user3
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(3))
+            .followers(Insert(vec![(1, 2), (1, 3), (3, 2)]))
+            .build()
+            .await?;
+        let users = users.unwrap();
+        let token = Token {
+            exp: 35,
+            id: users[1].id,
+        };
+
+        let Json(result) = list_followers(
+            State(connection.clone()),
+            Some(Extension(token)),
+            Path(users[0].username.clone()),
+        )
+        .await?;
+        assert_eq!(result.profiles.len(), 2);
+        assert_eq!(result.profiles[0].username, "username2");
+        assert!(!result.profiles[0].following);
+        assert_eq!(result.profiles[1].username, "username3");
+        assert!(result.profiles[1].following);
+
+        let Json(result) = list_following(
+            Query(HashMap::new()),
+            State(connection),
+            Extension(PageConfig::default()),
+            None,
+            Path(users[1].username.clone()),
+        )
+        .await?;
+        let usernames: Vec<&str> = result
+            .profiles
+            .iter()
+            .map(|prf| prf.username.as_str())
+            .collect();
+        assert_eq!(usernames, vec!["username1", "username3"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn user_without_followers() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .followers(Migration)
+            .build()
+            .await?;
+        let username = users.unwrap()[0].username.clone();
+
+        let Json(result) =
+            list_followers(State(connection.clone()), None, Path(username.clone())).await?;
+        assert!(result.profiles.is_empty());
+
+        let Json(result) = list_following(
+            Query(HashMap::new()),
+            State(connection),
+            Extension(PageConfig::default()),
+            None,
+            Path(username),
+        )
+        .await?;
+        assert!(result.profiles.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_non_existing_user() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let result = list_followers(
+            State(connection.clone()),
+            None,
+            Path("not exist".to_owned()),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::UserNotExist)));
+
+        let result = list_following(
+            Query(HashMap::new()),
+            State(connection),
+            Extension(PageConfig::default()),
+            None,
+            Path("not exist".to_owned()),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::UserNotExist)));
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test_follow_user {
     use super::{follow_user, ProfileDto};
     use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
     use crate::middleware::auth::Token;
     use crate::repo::user::Profile;
     use crate::tests::{
         Operation::{Insert, Migration},
         TestData, TestDataBuilder, TestErr,
     };
-    use axum::{
-        extract::{Path, State},
-        Extension, Json,
-    };
+    use axum::{extract::State, Extension, Json};
     use dotenvy::dotenv;
     use entity::entities::user;
 
@@ -245,16 +637,14 @@ mod test_follow_user {
 mod test_unfollow_user {
     use super::{unfollow_user, ProfileDto};
     use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
     use crate::middleware::auth::Token;
     use crate::repo::user::Profile;
     use crate::tests::{
         Operation::{Insert, Migration},
         TestData, TestDataBuilder, TestErr,
     };
-    use axum::{
-        extract::{Path, State},
-        Extension, Json,
-    };
+    use axum::{extract::State, Extension, Json};
     use dotenvy::dotenv;
     use entity::entities::user;
 
@@ -318,3 +708,95 @@ mod test_unfollow_user {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test_block_user {
+    use super::{block_user, unblock_user};
+    use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::middleware::auth::Token;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, Extension, Json};
+    use entity::entities::prelude::Block;
+    use sea_orm::EntityTrait;
+
+    #[tokio::test]
+    async fn block_and_unblock_existing_user() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .followers(Migration)
+            .build()
+            .await?;
+        let users = users.unwrap();
+        let token = Token {
+            exp: 35,
+            id: users[0].id,
+        };
+
+        let Json(result) = block_user(
+            State(connection.clone()),
+            Extension(token.clone()),
+            Path(users[1].username.clone()),
+        )
+        .await?;
+        assert_eq!(result.profile.username, users[1].username);
+        assert_eq!(Block::find().all(&connection).await?.len(), 1);
+
+        let Json(_) = unblock_user(
+            State(connection.clone()),
+            Extension(token),
+            Path(users[1].username.clone()),
+        )
+        .await?;
+        assert!(Block::find().all(&connection).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn block_self() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .followers(Migration)
+            .build()
+            .await?;
+        let user = users.unwrap().remove(0);
+        let token = Token {
+            exp: 35,
+            id: user.id,
+        };
+
+        let result = block_user(State(connection), Extension(token), Path(user.username)).await;
+
+        assert!(matches!(result, Err(ApiErr::InvalidInput(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn block_non_existing_user() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .followers(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        let result = block_user(
+            State(connection),
+            Extension(token),
+            Path("not exist username".to_owned()),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApiErr::UserNotExist)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,300 @@
+use super::{error::ApiErr, extract::Path};
+use crate::middleware::auth::Token;
+use crate::repo::{
+    article::get_article_model_by_slug,
+    reaction::{
+        add_reaction as repo_add_reaction, count_reactions_by_kind,
+        remove_reaction as repo_remove_reaction, ReactionCount,
+    },
+};
+use axum::{extract::State, Extension, Json};
+use entity::entities::reaction;
+use sea_orm::{ActiveValue::Set, DatabaseConnection};
+use serde::{Deserialize, Serialize};
+
+/// Axum handler for fetch article `reaction` counts grouped by kind.
+/// Returns json object with list of reaction counts on success, otherwise returns an `api error`.
+pub async fn list_reactions(
+    Path(slug): Path<String>,
+    maybe_token: Option<Extension<Token>>,
+    State(db): State<DatabaseConnection>,
+) -> Result<Json<ReactionsDto>, ApiErr> {
+    let reacted_article = get_article_model_by_slug(&db, &slug, maybe_token.map(|tkn| tkn.id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+
+    let reactions = count_reactions_by_kind(&db, reacted_article.id).await?;
+
+    let reactions_dto = ReactionsDto { reactions };
+    Ok(Json(reactions_dto))
+}
+
+/// Axum handler for adding reaction to article by logged user. Repeated reaction of the same
+/// kind is ignored.
+/// Returns json object with list of reaction counts on success, otherwise returns an `api error`.
+pub async fn add_reaction(
+    Path(slug): Path<String>,
+    Extension(token): Extension<Token>,
+    State(db): State<DatabaseConnection>,
+    Json(payload): Json<CreateReactionDto>,
+) -> Result<Json<ReactionsDto>, ApiErr> {
+    let kind = normalize_kind(&payload.reaction.kind)?;
+
+    let reacted_article = get_article_model_by_slug(&db, &slug, Some(token.id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+
+    let reaction_model = reaction::ActiveModel {
+        article_id: Set(reacted_article.id),
+        user_id: Set(token.id),
+        kind: Set(kind),
+    };
+
+    repo_add_reaction(&db, reaction_model).await?;
+
+    let reactions = count_reactions_by_kind(&db, reacted_article.id).await?;
+
+    let reactions_dto = ReactionsDto { reactions };
+    Ok(Json(reactions_dto))
+}
+
+/// Axum handler for removing reaction of provided kind from article by logged user.
+/// Returns json object with list of reaction counts on success, otherwise returns an `api error`.
+pub async fn remove_reaction(
+    Path((slug, kind)): Path<(String, String)>,
+    Extension(token): Extension<Token>,
+    State(db): State<DatabaseConnection>,
+) -> Result<Json<ReactionsDto>, ApiErr> {
+    let kind = normalize_kind(&kind)?;
+
+    let reacted_article = get_article_model_by_slug(&db, &slug, Some(token.id))
+        .await?
+        .ok_or(ApiErr::ArticleNotExist)?;
+
+    let reaction_model = reaction::ActiveModel {
+        article_id: Set(reacted_article.id),
+        user_id: Set(token.id),
+        kind: Set(kind),
+    };
+
+    repo_remove_reaction(&db, reaction_model).await?;
+
+    let reactions = count_reactions_by_kind(&db, reacted_article.id).await?;
+
+    let reactions_dto = ReactionsDto { reactions };
+    Ok(Json(reactions_dto))
+}
+
+/// Trim and lowercase reaction `kind`.
+/// Returns normalized kind on success, otherwise returns `InvalidInput` error for empty kind.
+fn normalize_kind(kind: &str) -> Result<String, ApiErr> {
+    let kind = kind.trim().to_lowercase();
+    if kind.is_empty() {
+        return Err(ApiErr::InvalidInput("Reaction kind must not be empty"));
+    }
+    Ok(kind)
+}
+
+/// Struct describing JSON object, returned by handler. Contains list of reaction counts.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ReactionsDto {
+    reactions: Vec<ReactionCount>,
+}
+
+/// Struct describing JSON object from reaction creation request. Contains reaction.
+#[derive(Debug, Deserialize)]
+pub struct CreateReactionDto {
+    reaction: CreateReaction,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct CreateReaction {
+    kind: String,
+}
+
+#[cfg(test)]
+mod test_reactions {
+    use super::{add_reaction, list_reactions, remove_reaction, CreateReaction, CreateReactionDto};
+    use crate::api::error::ApiErr;
+    use crate::api::extract::Path;
+    use crate::middleware::auth::Token;
+    use crate::repo::reaction::ReactionCount;
+    use crate::tests::{execute_migration, Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use axum::{extract::State, Extension, Json};
+
+    fn reaction_payload(kind: &str) -> Json<CreateReactionDto> {
+        Json(CreateReactionDto {
+            reaction: CreateReaction {
+                kind: kind.to_owned(),
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn add_and_remove_reactions() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .build()
+            .await?;
+        execute_migration(&connection, "m20231201_000009_create_reaction_table").await?;
+
+        let users = users.unwrap();
+        let slug = articles.unwrap()[0].slug.clone();
+        let token = |idx: usize| Token {
+            exp: 35,
+            id: users[idx].id,
+        };
+
+        let _result = add_reaction(
+            Path(slug.clone()),
+            Extension(token(0)),
+            State(connection.clone()),
+            reaction_payload("clap"),
+        )
+        .await?;
+        let _result = add_reaction(
+            Path(slug.clone()),
+            Extension(token(1)),
+            State(connection.clone()),
+            reaction_payload(" Clap "),
+        )
+        .await?;
+        let Json(result) = add_reaction(
+            Path(slug.clone()),
+            Extension(token(1)),
+            State(connection.clone()),
+            reaction_payload("heart"),
+        )
+        .await?;
+        assert_eq!(
+            result.reactions,
+            vec![
+                ReactionCount {
+                    kind: "clap".to_owned(),
+                    count: 2
+                },
+                ReactionCount {
+                    kind: "heart".to_owned(),
+                    count: 1
+                },
+            ]
+        );
+
+        let Json(result) = remove_reaction(
+            Path((slug.clone(), "clap".to_owned())),
+            Extension(token(0)),
+            State(connection.clone()),
+        )
+        .await?;
+        let Json(listed) = list_reactions(Path(slug), None, State(connection)).await?;
+        assert_eq!(result, listed);
+        assert_eq!(
+            listed.reactions,
+            vec![
+                ReactionCount {
+                    kind: "clap".to_owned(),
+                    count: 1
+                },
+                ReactionCount {
+                    kind: "heart".to_owned(),
+                    count: 1
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn repeated_reaction() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .build()
+            .await?;
+        execute_migration(&connection, "m20231201_000009_create_reaction_table").await?;
+
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+        let slug = articles.unwrap()[0].slug.clone();
+
+        for _ in 0..2 {
+            let Json(result) = add_reaction(
+                Path(slug.clone()),
+                Extension(token.clone()),
+                State(connection.clone()),
+                reaction_payload("clap"),
+            )
+            .await?;
+            assert_eq!(
+                result.reactions,
+                vec![ReactionCount {
+                    kind: "clap".to_owned(),
+                    count: 1
+                }]
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn empty_kind() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .build()
+            .await?;
+        execute_migration(&connection, "m20231201_000009_create_reaction_table").await?;
+
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+        let result = add_reaction(
+            Path(articles.unwrap()[0].slug.clone()),
+            Extension(token),
+            State(connection),
+            reaction_payload("  "),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApiErr::InvalidInput(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn not_existing_article() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .build()
+            .await?;
+        execute_migration(&connection, "m20231201_000009_create_reaction_table").await?;
+
+        let result = list_reactions(Path("slug".to_owned()), None, State(connection)).await;
+
+        assert!(matches!(result, Err(ApiErr::ArticleNotExist)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+use super::error::ApiErr;
+use ammonia::Builder;
+use pulldown_cmark::{Event, Parser, Tag};
+use std::{env, ops::Range, sync::OnceLock};
+
+pub const SANITIZE_BODY: &str = "SANITIZE_BODY";
+
+/// Elements removed together with their content.
+const CLEAN_CONTENT_TAGS: [&str; 2] = ["script", "style"];
+/// Maximum number of sanitizer passes, body still changing after the last one is rejected.
+const MAX_SANITIZE_PASSES: usize = 8;
+
+/// Sanitize article `body` before it is stored, when enabled with SANITIZE_BODY. Body is
+/// returned unchanged otherwise.
+/// Returns `InvalidInput` for body not settled within MAX_SANITIZE_PASSES.
+pub fn sanitize_body(body: String) -> Result<String, ApiErr> {
+    match sanitize_body_enabled() {
+        true => {
+            strip_dangerous_html(&body).ok_or(ApiErr::InvalidInput("Body could not be sanitized"))
+        }
+        false => Ok(body),
+    }
+}
+
+/// Allow-list sanitizer shared by all passes, built on first use.
+fn cleaner() -> &'static Builder<'static> {
+    static CLEANER: OnceLock<Builder<'static>> = OnceLock::new();
+    CLEANER.get_or_init(|| {
+        let mut cleaner = Builder::default();
+        cleaner
+            .link_rel(None)
+            .clean_content_tags(CLEAN_CONTENT_TAGS.into());
+        cleaner
+    })
+}
+
+/// Remove HTML able to run scripts from Markdown `text`. Text is parsed as CommonMark, so raw
+/// HTML is found exactly where Markdown renderer passes it through: HTML blocks and inline tags
+/// are cleaned with allow-list sanitizer (ammonia), links and images with URL rejected by the
+/// sanitizer are turned into plain text. The rest of Markdown (code included) is kept as is.
+/// Removed markup may uncover new Markdown, so text is sanitized until nothing changes.
+/// Returns `None` when text still changes after MAX_SANITIZE_PASSES.
+fn strip_dangerous_html(text: &str) -> Option<String> {
+    repeat_until_stable(text, MAX_SANITIZE_PASSES, |text| {
+        sanitize_once(cleaner(), text)
+    })
+}
+
+/// Apply `pass` to `text` until result stops changing, at most `max_passes` times.
+/// Returns `None` when result still changes after the last pass.
+fn repeat_until_stable(
+    text: &str,
+    max_passes: usize,
+    pass: impl Fn(&str) -> String,
+) -> Option<String> {
+    let mut current = text.to_owned();
+    for _ in 0..max_passes {
+        let next = pass(&current);
+        if next == current {
+            return Some(current);
+        }
+        current = next;
+    }
+    None
+}
+
+/// Single pass of strip_dangerous_html.
+fn sanitize_once(cleaner: &Builder, text: &str) -> String {
+    let mut edits: Vec<(Range<usize>, String)> = vec![];
+    // Inline element removed with its content, and its start
+    let mut dropped: Option<(String, usize)> = None;
+    for (event, range) in Parser::new(text).into_offset_iter() {
+        match event {
+            Event::Start(Tag::HtmlBlock) => {
+                let cleaned = cleaner.clean(&text[range.clone()]).to_string();
+                edits.push((range, cleaned));
+            }
+            Event::InlineHtml(tag) => {
+                let name = tag_name(&tag);
+                match &dropped {
+                    Some((dropped_name, start))
+                        if tag.starts_with("</") && *dropped_name == name =>
+                    {
+                        let start = *start;
+                        edits.retain(|(edit, _)| edit.start < start);
+                        edits.push((start..range.end, String::new()));
+                        dropped = None;
+                        continue;
+                    }
+                    None if !tag.starts_with("</")
+                        && CLEAN_CONTENT_TAGS.contains(&name.as_str()) =>
+                    {
+                        dropped = Some((name, range.start));
+                    }
+                    _ => {}
+                }
+                edits.push((range, clean_inline_tag(cleaner, &tag)));
+            }
+            Event::Start(Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. })
+                if !is_allowed_url(cleaner, &dest_url) =>
+            {
+                // Escaped bracket (angle bracket of autolink) leaves link as plain text
+                let at = range.start + text[range].find(['[', '<']).unwrap_or_default();
+                edits.push((at..at, "\\".to_owned()));
+            }
+            _ => {}
+        }
+    }
+
+    let mut sanitized = String::with_capacity(text.len());
+    let mut last = 0;
+    for (range, replacement) in edits {
+        sanitized.push_str(&text[last..range.start]);
+        sanitized.push_str(&replacement);
+        last = range.end;
+    }
+    sanitized.push_str(&text[last..]);
+    sanitized
+}
+
+/// Clean single inline `tag`. Sanitizer closes opened element, so closing tag is cut off,
+/// closing tag itself is kept only for allowed element.
+fn clean_inline_tag(cleaner: &Builder, tag: &str) -> String {
+    let name = tag_name(tag);
+    let closing_tag = format!("</{name}>");
+
+    if tag.starts_with("</") {
+        let allowed =
+            !name.is_empty() && !cleaner.clean(&format!("<{name}>")).to_string().is_empty();
+        return match allowed {
+            true => closing_tag,
+            false => String::new(),
+        };
+    }
+
+    let cleaned = cleaner.clean(tag).to_string();
+    match cleaned.strip_suffix(&closing_tag) {
+        Some(opening_tag) => opening_tag.to_owned(),
+        None => cleaned,
+    }
+}
+
+/// Lowercase element name of opening or closing `tag`.
+fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches(['<', '/'])
+        .chars()
+        .take_while(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Check `url` against URL schemes allowed by sanitizer.
+fn is_allowed_url(cleaner: &Builder, url: &str) -> bool {
+    let url = url.replace('&', "&amp;").replace('"', "&quot;");
+    cleaner
+        .clean(&format!("<a href=\"{url}\"></a>"))
+        .to_string()
+        .contains("href=")
+}
+
+/// Return SANITIZE_BODY from environment variables, disabled by default
+fn sanitize_body_enabled() -> bool {
+    env::var(SANITIZE_BODY)
+        .map(|val| val.trim() == "1" || val.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod test_strip_dangerous_html {
+    use super::strip_dangerous_html;
+
+    #[test]
+    fn script_and_handlers_removed() {
+        let body = "# Title\n\n<script>alert(1)</script>\n\ntext <img src=\"a.png\" \
+            onerror=\"alert(1)\"> <a href=\"javascript:alert(1)\" title='x'>link</a>";
+        assert_eq!(
+            strip_dangerous_html(body).unwrap(),
+            "# Title\n\n\n\ntext <img src=\"a.png\"> <a title=\"x\">link</a>"
+        );
+    }
+
+    #[test]
+    fn inline_script_content_removed() {
+        let body = "text<script>alert(1)</script> *a* <style>b{}</style><SCRIPT>[c](javascript:x)";
+        assert_eq!(
+            strip_dangerous_html(body).unwrap(),
+            "text *a* \\[c](javascript:x)"
+        );
+    }
+
+    #[test]
+    fn markdown_link_with_unsafe_url_disabled() {
+        let body = "[a](javascript:alert(1)) ![b](vbscript:x) <javascript:alert(1)> \
+            [c](https://example.com/?a=1&b=2)";
+        assert_eq!(
+            strip_dangerous_html(body).unwrap(),
+            "\\[a](javascript:alert(1)) !\\[b](vbscript:x) \\<javascript:alert(1)> \
+            [c](https://example.com/?a=1&b=2)"
+        );
+    }
+
+    #[test]
+    fn markdown_kept() {
+        let body = "1 < 2, <https://example.com>\n\n```html\n<script>x</script>\n```\n\n\
+            `<b onclick=x>` **bold** <b>html</b>";
+        assert_eq!(strip_dangerous_html(body).unwrap(), body);
+    }
+
+    #[test]
+    fn backticks_around_html_block() {
+        let body = "`\n\n<img src=x onerror=alert(1)>\n\n`";
+        assert_eq!(
+            strip_dangerous_html(body).unwrap(),
+            "`\n\n<img src=\"x\">\n\n`"
+        );
+
+        let body = "<div>\n`<img src=x onerror=alert(1)>`\n</div>";
+        assert_eq!(
+            strip_dangerous_html(body).unwrap(),
+            "<div>\n`<img src=\"x\">`\n</div>"
+        );
+    }
+
+    #[test]
+    fn svg_animation_removed() {
+        let body = "text <svg><a><animate attributeName=href values=javascript:alert(1)>x</a>";
+        assert_eq!(strip_dangerous_html(body).unwrap(), "text <a>x</a>");
+    }
+
+    #[test]
+    fn markdown_uncovered_by_removed_html_sanitized() {
+        let body = "<xmp>\n[a](javascript:alert(1))\n</xmp>";
+        assert_eq!(
+            strip_dangerous_html(body).unwrap(),
+            "\n\\[a](javascript:alert(1))\n"
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_repeat_until_stable {
+    use super::repeat_until_stable;
+
+    fn drop_first(text: &str) -> String {
+        text.chars().skip(1).collect()
+    }
+
+    #[test]
+    fn stable_within_passes() {
+        assert_eq!(
+            repeat_until_stable("abc", 8, drop_first),
+            Some(String::new())
+        );
+        assert_eq!(
+            repeat_until_stable("abc", 8, |text| text.to_owned()),
+            Some("abc".to_owned())
+        );
+    }
+
+    #[test]
+    fn still_changing_after_last_pass() {
+        assert_eq!(repeat_until_stable("abcdefghij", 8, drop_first), None);
+    }
+}
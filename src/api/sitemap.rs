@@ -0,0 +1,138 @@
+use super::error::ApiErr;
+use crate::repo::article::get_all_article_slugs;
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use sea_orm::DatabaseConnection;
+use std::env;
+
+/// Maximum number of urls allowed in a single sitemap by the protocol.
+const SITEMAP_MAX_URLS: u64 = 50_000;
+const DEFAULT_SITEMAP_BASE_URL: &str = "http://localhost:3000";
+const SITEMAP_BASE_URL: &str = "SITEMAP_BASE_URL";
+
+/// Axum handler for sitemap generation. Each `article` produces url entry with last
+/// modification date.
+/// Returns sitemap xml document on success, otherwise returns an `api error`.
+pub async fn get_sitemap(State(db): State<DatabaseConnection>) -> Result<Response, ApiErr> {
+    let slugs = get_all_article_slugs(&db, SITEMAP_MAX_URLS, None).await?;
+    let base_url = get_sitemap_base_url();
+
+    let mut sitemap = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for (slug, updated_at) in slugs {
+        sitemap.push_str("  <url>\n");
+        sitemap.push_str(&format!(
+            "    <loc>{}/article/{}</loc>\n",
+            xml_escape(&base_url),
+            xml_escape(&slug)
+        ));
+        if let Some(updated_at) = updated_at {
+            sitemap.push_str(&format!(
+                "    <lastmod>{}</lastmod>\n",
+                updated_at.format("%Y-%m-%d")
+            ));
+        }
+        sitemap.push_str("  </url>\n");
+    }
+    sitemap.push_str("</urlset>\n");
+
+    Ok(([(header::CONTENT_TYPE, "application/xml")], sitemap).into_response())
+}
+
+/// Return SITEMAP_BASE_URL from environment varibles or default url, without trailing slash
+fn get_sitemap_base_url() -> String {
+    env::var(SITEMAP_BASE_URL)
+        .ok()
+        .filter(|url| !url.is_empty())
+        .unwrap_or(DEFAULT_SITEMAP_BASE_URL.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Escape characters not allowed in xml text content
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod test_get_sitemap {
+    use super::*;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::body::HttpBody;
+    use serial_test::serial;
+
+    async fn sitemap_body(response: Response) -> String {
+        let bytes = response.into_body().data().await.unwrap().unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn get_existing_articles() -> Result<(), TestErr> {
+        env::set_var(SITEMAP_BASE_URL, "https://example.com/");
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1]))
+            .build()
+            .await?;
+
+        let response = get_sitemap(State(connection)).await?;
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/xml"
+        );
+        let body = sitemap_body(response).await;
+
+        assert!(body.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(body.trim_end().ends_with("</urlset>"));
+        assert_eq!(body.matches("<url>").count(), 2);
+        assert_eq!(body.matches("</url>").count(), 2);
+        for article in articles.unwrap() {
+            let loc = format!("<loc>https://example.com/article/{}</loc>", article.slug);
+            let lastmod = format!(
+                "<lastmod>{}</lastmod>",
+                article.updated_at.unwrap().format("%Y-%m-%d")
+            );
+            assert!(body.contains(&loc));
+            assert!(body.contains(&lastmod));
+        }
+
+        env::remove_var(SITEMAP_BASE_URL);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn get_no_articles() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Migration)
+            .articles(Migration)
+            .build()
+            .await?;
+
+        let response = get_sitemap(State(connection)).await?;
+        let body = sitemap_body(response).await;
+
+        assert!(body.contains("<urlset"));
+        assert!(!body.contains("<url>"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn escape_special_characters() {
+        assert_eq!(xml_escape("a&b<c>\"'"), "a&amp;b&lt;c&gt;&quot;&apos;");
+    }
+}
@@ -1,16 +1,54 @@
 use super::error::ApiErr;
-use crate::repo::tag::get_tags;
-use axum::{extract::State, Json};
+use crate::repo::tag::{get_tags, get_tags_count, TagSort};
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, HeaderValue},
+    Json,
+};
 use sea_orm::DatabaseConnection;
 use serde::Serialize;
+use std::collections::HashMap;
 
-/// Axum handler for fetch all existing `tag names`.
+/// Response header with total number of tags, regardless of limit and offset.
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+/// Axum handler for fetch existing `tag names` ordered by name, or most used first with
+/// `sort=usage`. Limit response by limit and offset parameters, all tags returned when not
+/// provided. Total number of tags is returned
+/// in `X-Total-Count` header.
 /// Returns json object with list of tag names on success, otherwise returns an `api error`.
-pub async fn list_tags(State(db): State<DatabaseConnection>) -> Result<Json<TagsDto>, ApiErr> {
-    let tags = get_tags(&db).await?;
+pub async fn list_tags(
+    Query(params): Query<HashMap<String, String>>,
+    State(db): State<DatabaseConnection>,
+) -> Result<(HeaderMap, Json<TagsDto>), ApiErr> {
+    // Limit number of tags (all by default):
+    let limit = params
+        .get("limit")
+        .map(|lm| lm.parse::<u64>())
+        .filter(|res| res.is_ok())
+        .map(|res| res.unwrap());
+
+    // Offset/skip number of tags (default is 0):
+    let offset = params
+        .get("offset")
+        .map(|lm| lm.parse::<u64>())
+        .filter(|res| res.is_ok())
+        .map(|res| res.unwrap());
+
+    // Sort order (by name by default):
+    let sort = params
+        .get("sort")
+        .and_then(|srt| srt.parse::<TagSort>().ok())
+        .unwrap_or_default();
+
+    let tags = get_tags(&db, sort, limit, offset).await?;
+    let tags_count = get_tags_count(&db).await?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(TOTAL_COUNT_HEADER, HeaderValue::from(tags_count));
 
     let tags_dto = TagsDto { tags };
-    Ok(Json(tags_dto))
+    Ok((headers, Json(tags_dto)))
 }
 
 /// Struct describing JSON object, returned by handler. Contains list of tag names.
@@ -21,12 +59,16 @@ pub struct TagsDto {
 
 #[cfg(test)]
 mod test_list_tags {
-    use super::{list_tags, TagsDto};
+    use super::{list_tags, TagsDto, TOTAL_COUNT_HEADER};
     use crate::tests::{
         Operation::{Insert, Migration},
         TestData, TestDataBuilder, TestErr,
     };
-    use axum::{extract::State, Json};
+    use axum::{
+        extract::{Query, State},
+        Json,
+    };
+    use std::collections::HashMap;
     use std::vec;
 
     #[tokio::test]
@@ -36,8 +78,8 @@ mod test_list_tags {
         let tags: Vec<String> = tags.unwrap().into_iter().map(|mdl| mdl.tag_name).collect();
         let expected = TagsDto { tags };
 
-        let result = list_tags(State(connection)).await?;
-        let Json(result) = result;
+        let result = list_tags(Query(HashMap::new()), State(connection)).await?;
+        let (_, Json(result)) = result;
 
         assert_eq!(result, expected);
 
@@ -50,10 +92,58 @@ mod test_list_tags {
         let tags: Vec<String> = vec![];
         let expected = TagsDto { tags };
 
-        let result = list_tags(State(connection)).await?;
-        let Json(result) = result;
+        let result = list_tags(Query(HashMap::new()), State(connection)).await?;
+        let (_, Json(result)) = result;
+
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_most_used_first() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1]))
+            .tags(Insert(3))
+            .article_tags(Insert(vec![(1, 3), (2, 3), (1, 2)]))
+            .build()
+            .await?;
+        let params: HashMap<String, String> = [("sort".to_owned(), "usage".to_owned())]
+            .into_iter()
+            .collect();
+        let expected = TagsDto {
+            tags: vec![
+                "tag_name3".to_owned(),
+                "tag_name2".to_owned(),
+                "tag_name1".to_owned(),
+            ],
+        };
+
+        let (_, Json(result)) = list_tags(Query(params), State(connection)).await?;
+
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn get_page_with_total_count() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new().tags(Insert(5)).build().await?;
+        let params: HashMap<String, String> = [
+            ("limit".to_owned(), "2".to_owned()),
+            ("offset".to_owned(), "1".to_owned()),
+        ]
+        .into_iter()
+        .collect();
+        let expected = TagsDto {
+            tags: vec!["tag_name2".to_owned(), "tag_name3".to_owned()],
+        };
+
+        let (headers, Json(result)) = list_tags(Query(params), State(connection)).await?;
 
         assert_eq!(result, expected);
+        assert_eq!(headers[TOTAL_COUNT_HEADER], "5");
 
         Ok(())
     }
@@ -66,14 +156,15 @@ mod test_errors {
         api::error::ApiErr,
         tests::{TestDataBuilder, TestErr},
     };
-    use axum::extract::State;
+    use axum::extract::{Query, State};
+    use std::collections::HashMap;
 
     #[tokio::test]
     async fn stale_connection() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new().build().await?;
         connection.clone().close().await?;
 
-        let result = list_tags(State(connection)).await;
+        let result = list_tags(Query(HashMap::new()), State(connection)).await;
 
         matches!(result, Err(ApiErr::DbErr(_)));
 
@@ -84,7 +175,7 @@ mod test_errors {
     async fn no_migration() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new().build().await?;
 
-        let result = list_tags(State(connection)).await;
+        let result = list_tags(Query(HashMap::new()), State(connection)).await;
 
         matches!(result, Err(ApiErr::DbErr(_)));
 
@@ -1,73 +1,142 @@
-use super::error::ApiErr;
-use crate::middleware::auth::{check_passwords, hash_password, Token};
-use crate::repo::user::{
-    create_user, get_user_by_email, get_user_by_id, get_user_with_token_by_id,
-    update_user as repo_update_user, UserWithToken,
+use super::{
+    error::{user_unique_err, ApiErr},
+    id::new_id,
+    limits::get_max_expanded_following,
+};
+use crate::middleware::auth::{auth_cookie, check_passwords, hash_password, Token};
+use crate::repo::{
+    favorited_article::delete_favorites_by_user,
+    follower::get_following,
+    user::{
+        apply_user_patch, delete_user_tx, get_user_by_email, get_user_by_login,
+        get_user_with_token_by_id, register_user_tx, UserPatch, UserWithToken,
+    },
+    user_cache::UserCache,
+};
+use axum::{
+    extract::{Query, State},
+    http::{header::SET_COOKIE, HeaderMap},
+    Extension, Json,
 };
-use axum::{extract::State, Extension, Json};
 use entity::entities::*;
-use sea_orm::{ActiveValue::Set, DatabaseConnection};
-use serde::{Deserialize, Serialize};
-use uuid::Uuid;
+use sea_orm::{ActiveValue::Set, DatabaseConnection, DbErr};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
 
-/// Axum handler for login user.
+/// Axum handler for login user. User identified by `login` (email or username), or by `email`
+/// when login not provided. Token additionally set as auth cookie, when configured.
 /// Returns json object with user on success, otherwise returns an `api error`.
 pub async fn login_user(
     State(db): State<DatabaseConnection>,
     Json(payload): Json<LoginUserDto>,
-) -> Result<Json<UserDto>, ApiErr> {
+) -> Result<(HeaderMap, Json<UserDto>), ApiErr> {
     let input = payload.user;
 
-    let current_user = get_user_by_email(&db, &input.email)
-        .await?
-        .ok_or(ApiErr::UserNotExist)?;
+    let maybe_user = match (&input.login, &input.email) {
+        (Some(login), _) => get_user_by_login(&db, login).await?,
+        (None, Some(email)) => get_user_by_email(&db, email).await?,
+        (None, None) => return Err(ApiErr::InvalidInput("Email or login required")),
+    };
+    let current_user = maybe_user.ok_or(ApiErr::UserNotExist)?;
 
     check_passwords(&input.password, &current_user.password).map_err(|_err| ApiErr::WrongPass)?;
 
     let user_dto = UserDto {
-        user: current_user.into(),
+        user: current_user.try_into()?,
     };
 
-    Ok(Json(user_dto))
+    Ok((auth_cookie_headers(&user_dto.user), Json(user_dto)))
 }
 
-/// Axum handler for register user.
+/// Axum handler for register user. Token additionally set as auth cookie, when configured.
 /// Returns json object with user on success, otherwise returns an `api error`.
 pub async fn register_user(
     State(db): State<DatabaseConnection>,
     Json(payload): Json<RegisterUserDto>,
-) -> Result<Json<UserDto>, ApiErr> {
+) -> Result<(HeaderMap, Json<UserDto>), ApiErr> {
     let input = payload.user;
+    validate_email(&input.email)?;
     let hashed_password = hash_password(&input.password).map_err(|_err| ApiErr::WrongPass)?;
 
     let user_model = user::ActiveModel {
-        id: Set(Uuid::new_v4()),
+        id: Set(new_id()),
         email: Set(input.email),
         username: Set(input.username),
         password: Set(hashed_password),
         ..Default::default()
     };
 
-    let user_res = create_user(&db, user_model).await?;
-    let current_user = get_user_with_token_by_id(&db, user_res.last_insert_id)
-        .await?
-        .ok_or(ApiErr::UserNotExist)?;
+    let current_user = register_user_tx(&db, user_model).await?;
 
     let user_dto = UserDto { user: current_user };
-    Ok(Json(user_dto))
+    Ok((auth_cookie_headers(&user_dto.user), Json(user_dto)))
 }
 
-/// Axum handler for retrieve information about logged user.
+/// Check that `email` has basic address shape: non-empty local part, single `@` and domain
+/// of non-empty dot separated labels, without whitespace.
+/// Returns `InvalidEmail` error for malformed address.
+fn validate_email(email: &str) -> Result<(), ApiErr> {
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && domain.split('.').all(|label| !label.is_empty())
+                && !email.chars().any(char::is_whitespace)
+        }
+        None => false,
+    };
+    match valid {
+        true => Ok(()),
+        false => Err(ApiErr::InvalidEmail),
+    }
+}
+
+/// Build response headers setting auth cookie with token of provided user.
+/// Headers are empty when auth cookie is not configured.
+fn auth_cookie_headers(user: &UserWithToken) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(cookie) = auth_cookie(&user.token) {
+        headers.insert(SET_COOKIE, cookie);
+    }
+    headers
+}
+
+/// Axum handler for retrieve information about logged user. With `expand=following` parameter
+/// usernames of followed users are returned as well, up to APP_MAX_EXPANDED_FOLLOWING (the rest
+/// available via following endpoint of profile).
 /// Returns json object with user on success, otherwise returns an `api error`.
 pub async fn get_current_user(
+    Query(params): Query<HashMap<String, String>>,
     State(db): State<DatabaseConnection>,
     Extension(token): Extension<Token>,
-) -> Result<Json<UserDto>, ApiErr> {
+) -> Result<Json<CurrentUserDto>, ApiErr> {
     let current_user = get_user_with_token_by_id(&db, token.id)
         .await?
         .ok_or(ApiErr::UserNotExist)?;
 
-    let user_dto = UserDto { user: current_user };
+    let expand_following = params
+        .get("expand")
+        .is_some_and(|exp| exp.split(',').any(|fld| fld.trim() == "following"));
+    let following = if expand_following {
+        let profiles = get_following(
+            &db,
+            token.id,
+            Some(get_max_expanded_following()),
+            None,
+            None,
+        )
+        .await?;
+        Some(profiles.into_iter().map(|prf| prf.username).collect())
+    } else {
+        None
+    };
+
+    let user_dto = CurrentUserDto {
+        user: current_user,
+        following,
+    };
     Ok(Json(user_dto))
 }
 
@@ -76,47 +145,82 @@ pub async fn get_current_user(
 pub async fn update_user(
     State(db): State<DatabaseConnection>,
     Extension(token): Extension<Token>,
+    Extension(user_cache): Extension<Arc<UserCache>>,
     Json(payload): Json<UpdateUserDto>,
 ) -> Result<Json<UserDto>, ApiErr> {
     let input = payload.user;
-
-    let user_before = get_user_by_id(&db, token.id)
-        .await?
-        .ok_or(ApiErr::UserNotExist)?;
-
-    let mut user_model: user::ActiveModel = user_before.into();
-
-    // TODO add macro for easy set active model
-    if input.email.is_some() {
-        user_model.email = Set(input.email.to_owned().unwrap());
-    }
-    if input.username.is_some() {
-        user_model.username = Set(input.username.to_owned().unwrap());
-    }
-    if input.bio.is_some() {
-        user_model.bio = Set(input.bio.to_owned());
-    }
-    if input.image.is_some() {
-        user_model.image = Set(input.image);
-    }
-    if input.password.is_some() {
-        user_model.password = Set(input.password.to_owned().unwrap());
+    if let Some(email) = &input.email {
+        validate_email(email)?;
     }
+    let hashed_password = input
+        .password
+        .map(|pass| hash_password(&pass))
+        .transpose()
+        .map_err(|_err| ApiErr::WrongPass)?;
+
+    let patch = UserPatch {
+        email: input.email,
+        username: input.username,
+        password: hashed_password,
+        bio: input.bio,
+        image: input.image,
+    };
 
-    let current_user = repo_update_user(&db, user_model).await?;
+    let current_user = apply_user_patch(&db, &user_cache, token.id, patch)
+        .await
+        .map_err(|err| match err {
+            DbErr::RecordNotFound(_) => ApiErr::UserNotExist,
+            err => user_unique_err(err),
+        })?;
 
     let user_dto = UserDto {
-        user: current_user.into(),
+        user: current_user.try_into()?,
     };
     Ok(Json(user_dto))
 }
 
+/// Axum handler for removing all favorites of logged user.
+/// Returns empty json object on success, otherwise returns an `api error`.
+pub async fn clear_favorites(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<Token>,
+) -> Result<Json<()>, ApiErr> {
+    delete_favorites_by_user(&db, token.id).await?;
+
+    Ok(Json(()))
+}
+
+/// Axum handler for deleting account of logged user together with articles, comments,
+/// favorites and follows of the user (all or nothing).
+/// Returns empty json object on success, otherwise returns an `api error`.
+pub async fn delete_current_user(
+    State(db): State<DatabaseConnection>,
+    Extension(token): Extension<Token>,
+    Extension(user_cache): Extension<Arc<UserCache>>,
+) -> Result<Json<()>, ApiErr> {
+    let delete_result = delete_user_tx(&db, &user_cache, token.id).await?;
+    if delete_result.rows_affected == 0 {
+        return Err(ApiErr::UserNotExist);
+    }
+
+    Ok(Json(()))
+}
+
 /// Struct describing JSON object, returned by handler. Contains user info with authentication token.
 #[derive(Debug, Serialize, PartialEq)]
 pub struct UserDto {
     user: UserWithToken,
 }
 
+/// Struct describing JSON object, returned by handler. Contains user info with authentication
+/// token and optional usernames of followed users (omitted unless requested).
+#[derive(Debug, Serialize, PartialEq)]
+pub struct CurrentUserDto {
+    user: UserWithToken,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    following: Option<Vec<String>>,
+}
+
 /// Struct describing JSON object from login request. Contains user loggin data.
 #[derive(Debug, Deserialize)]
 pub struct LoginUserDto {
@@ -125,7 +229,8 @@ pub struct LoginUserDto {
 
 #[derive(Clone, Debug, Deserialize)]
 struct LoginUser {
-    email: String,
+    email: Option<String>,
+    login: Option<String>,
     password: String,
 }
 
@@ -152,9 +257,21 @@ pub struct UpdateUserDto {
 struct UpdateUser {
     email: Option<String>,
     username: Option<String>,
-    bio: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    bio: Option<Option<String>>,
     password: Option<String>,
-    image: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    image: Option<Option<String>>,
+}
+
+/// Deserialize present field into `Some`, thus explicit `null` becomes `Some(None)`.
+/// Combined with `#[serde(default)]` omitted field becomes `None`.
+fn double_option<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 #[cfg(test)]
@@ -187,16 +304,19 @@ mod test_login_user {
         create_user(&connection, user_hashed).await?;
 
         // Actual test start
-        let expected = UserDto { user: user.into() };
+        let expected = UserDto {
+            user: user.try_into().unwrap(),
+        };
         let login_data = LoginUserDto {
             user: LoginUser {
-                email: "email1".to_owned(),
+                email: Some("email1".to_owned()),
+                login: None,
                 password: "password".to_owned(),
             },
         };
 
         let result = login_user(State(connection), Json(login_data)).await?;
-        let Json(result) = result;
+        let (_, Json(result)) = result;
 
         assert_eq!(result.user.email, expected.user.email);
 
@@ -209,7 +329,8 @@ mod test_login_user {
 
         let login_data = LoginUserDto {
             user: LoginUser {
-                email: "wrong email".to_owned(),
+                email: Some("wrong email".to_owned()),
+                login: None,
                 password: "password".to_owned(),
             },
         };
@@ -237,7 +358,8 @@ mod test_login_user {
         // Actual test start
         let login_data = LoginUserDto {
             user: LoginUser {
-                email: "email1".to_owned(),
+                email: Some("email1".to_owned()),
+                login: None,
                 password: "wrong password".to_owned(),
             },
         };
@@ -247,20 +369,98 @@ mod test_login_user {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn login_by_username_or_email() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) =
+            TestDataBuilder::new().users(Create(1)).build().await?;
+        let user: user::Model = users.unwrap().into_iter().next().unwrap();
+        let user_hashed: user::ActiveModel = user::Model {
+            password: hash_password("password").unwrap(),
+            ..user.clone()
+        }
+        .into();
+        create_user(&connection, user_hashed.reset_all()).await?;
+
+        let login_data = |email: Option<&str>, login: Option<&str>| LoginUserDto {
+            user: LoginUser {
+                email: email.map(str::to_owned),
+                login: login.map(str::to_owned),
+                password: "password".to_owned(),
+            },
+        };
+
+        for data in [
+            login_data(None, Some("username1")),
+            login_data(None, Some("email1")),
+            login_data(Some("email1"), None),
+        ] {
+            let (_, Json(result)) = login_user(State(connection.clone()), Json(data)).await?;
+            assert_eq!(result.user.username, user.username);
+        }
+
+        let result = login_user(State(connection), Json(login_data(None, None))).await;
+        assert!(matches!(result, Err(ApiErr::InvalidInput(_))));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_validate_email {
+    use super::validate_email;
+    use crate::api::error::ApiErr;
+
+    #[test]
+    fn valid_address() {
+        assert_eq!(validate_email("jake@jake.jake"), Ok(()));
+        assert_eq!(validate_email("first.last+tag@mail.example.com"), Ok(()));
+    }
+
+    #[test]
+    fn missing_at() {
+        assert_eq!(validate_email("notanemail"), Err(ApiErr::InvalidEmail));
+        assert_eq!(validate_email("jake.jake.jake"), Err(ApiErr::InvalidEmail));
+    }
+
+    #[test]
+    fn empty_address() {
+        assert_eq!(validate_email(""), Err(ApiErr::InvalidEmail));
+    }
+
+    #[test]
+    fn malformed_parts() {
+        for email in [
+            "@mail.com",
+            "jake@",
+            "jake@mail",
+            "jake@mail.",
+            "a@b@mail.com",
+            "ja ke@mail.com",
+        ] {
+            assert_eq!(validate_email(email), Err(ApiErr::InvalidEmail), "{email}");
+        }
+    }
 }
 
 #[cfg(test)]
 mod test_register_user {
-    use super::{register_user, RegisterUser, RegisterUserDto};
+    use super::{
+        login_user, register_user, LoginUser, LoginUserDto, RegisterUser, RegisterUserDto,
+    };
     use crate::api::error::ApiErr;
+    use crate::middleware::auth::AUTH_COOKIE_NAME;
     use crate::tests::{
         Operation::{Create, Insert},
         TestData, TestDataBuilder, TestErr,
     };
-    use axum::{extract::State, Json};
+    use axum::{extract::State, http::header::SET_COOKIE, Json};
     use dotenvy::dotenv;
-    use entity::entities::user;
-    use sea_orm::DbErr;
+    use entity::entities::{prelude::User, user};
+    use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+    use serial_test::serial;
+    use std::env;
 
     #[tokio::test]
     async fn register_new_user() -> Result<(), TestErr> {
@@ -268,58 +468,177 @@ mod test_register_user {
         let (connection, TestData { users, .. }) =
             TestDataBuilder::new().users(Create(1)).build().await?;
         let user: user::Model = users.unwrap().into_iter().next().unwrap();
+        let email = format!("{}@mail.com", user.email);
 
         let reg_data = RegisterUserDto {
             user: RegisterUser {
-                email: user.email.clone(),
+                email: email.clone(),
                 password: user.password,
                 username: user.username,
             },
         };
 
         let result = register_user(State(connection), Json(reg_data)).await?;
-        let Json(result) = result;
-        assert_eq!(result.user.email, user.email);
+        let (_, Json(result)) = result;
+        assert_eq!(result.user.email, email);
 
         Ok(())
     }
 
     #[tokio::test]
     async fn exist_user_with_email() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) =
+            TestDataBuilder::new().users(Create(1)).build().await?;
+        let user: user::Model = users.unwrap().into_iter().next().unwrap();
+        let reg_data = |username: String| RegisterUserDto {
+            user: RegisterUser {
+                email: "taken@mail.com".to_owned(),
+                password: user.password.clone(),
+                username,
+            },
+        };
+
+        let (_, Json(_)) = register_user(
+            State(connection.clone()),
+            Json(reg_data(user.username.clone())),
+        )
+        .await?;
+        let result = register_user(
+            State(connection),
+            Json(reg_data("other_username".to_owned())),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::EmailTaken)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn exist_user_with_username() -> Result<(), TestErr> {
         let (connection, TestData { users, .. }) =
             TestDataBuilder::new().users(Insert(1)).build().await?;
         let user: user::Model = users.unwrap().into_iter().next().unwrap();
 
         let reg_data = RegisterUserDto {
             user: RegisterUser {
-                email: user.email,
+                email: "other@mail.com".to_owned(),
                 password: user.password,
-                username: "other_username".to_owned(),
+                username: user.username,
             },
         };
 
         let result = register_user(State(connection), Json(reg_data)).await;
-        matches!(result, Err(ApiErr::DbErr(DbErr::Exec(_))));
+        assert!(matches!(result, Err(ApiErr::UsernameTaken)));
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn exist_user_with_username() -> Result<(), TestErr> {
+    async fn concurrent_duplicate_registration() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
         let (connection, TestData { users, .. }) =
-            TestDataBuilder::new().users(Insert(1)).build().await?;
+            TestDataBuilder::new().users(Create(1)).build().await?;
         let user: user::Model = users.unwrap().into_iter().next().unwrap();
+        let reg_data = |username: &str| RegisterUserDto {
+            user: RegisterUser {
+                email: "same@mail.com".to_owned(),
+                password: user.password.clone(),
+                username: username.to_owned(),
+            },
+        };
 
-        let reg_data = RegisterUserDto {
+        let (first, second) = tokio::join!(
+            register_user(State(connection.clone()), Json(reg_data("first"))),
+            register_user(State(connection.clone()), Json(reg_data("second"))),
+        );
+        assert_eq!(first.is_ok() as u8 + second.is_ok() as u8, 1);
+        assert!([first, second]
+            .into_iter()
+            .any(|result| matches!(result, Err(ApiErr::EmailTaken))));
+        let stored = User::find()
+            .filter(user::Column::Email.eq("same@mail.com"))
+            .count(&connection)
+            .await?;
+        assert_eq!(stored, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn auth_cookie_set_when_configured() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) =
+            TestDataBuilder::new().users(Create(2)).build().await?;
+        let mut users = users.unwrap().into_iter();
+        let reg_data = |user: user::Model| RegisterUserDto {
             user: RegisterUser {
-                email: "other_email".to_owned(),
+                email: format!("{}@mail.com", user.email),
                 password: user.password,
                 username: user.username,
             },
         };
 
-        let result = register_user(State(connection), Json(reg_data)).await;
-        matches!(result, Err(ApiErr::DbErr(DbErr::Exec(_))));
+        env::remove_var(AUTH_COOKIE_NAME);
+        let (headers, _) = register_user(
+            State(connection.clone()),
+            Json(reg_data(users.next().unwrap())),
+        )
+        .await?;
+        assert!(headers.get(SET_COOKIE).is_none());
+
+        env::set_var(AUTH_COOKIE_NAME, "jwt");
+        let result = register_user(State(connection), Json(reg_data(users.next().unwrap()))).await;
+        env::remove_var(AUTH_COOKIE_NAME);
+        let (headers, Json(result)) = result?;
+        let cookie = headers[SET_COOKIE].to_str().unwrap();
+        assert!(cookie.starts_with(&format!("jwt={};", result.user.token)));
+        assert!(cookie.contains("HttpOnly"));
+        assert!(cookie.contains("Secure"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn login_and_register_share_shape() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) =
+            TestDataBuilder::new().users(Create(1)).build().await?;
+        let user: user::Model = users.unwrap().into_iter().next().unwrap();
+
+        let email = format!("{}@mail.com", user.email);
+
+        let reg_data = RegisterUserDto {
+            user: RegisterUser {
+                email: email.clone(),
+                password: user.password.clone(),
+                username: user.username,
+            },
+        };
+        let login_data = LoginUserDto {
+            user: LoginUser {
+                email: Some(email),
+                login: None,
+                password: user.password,
+            },
+        };
+
+        let (_, Json(registered)) =
+            register_user(State(connection.clone()), Json(reg_data)).await?;
+        let (_, Json(logged)) = login_user(State(connection), Json(login_data)).await?;
+
+        let mut registered = serde_json::to_value(registered).unwrap();
+        let mut logged = serde_json::to_value(logged).unwrap();
+        let keys = |value: &serde_json::Value| -> Vec<String> {
+            value["user"].as_object().unwrap().keys().cloned().collect()
+        };
+        assert_eq!(keys(&registered), keys(&logged));
+
+        // Tokens are issued at different moments, so only other fields must match
+        registered["user"]["token"].take();
+        logged["user"]["token"].take();
+        assert_eq!(registered, logged);
 
         Ok(())
     }
@@ -331,12 +650,17 @@ mod test_get_current_user {
     use crate::api::error::ApiErr;
     use crate::middleware::auth::Token;
     use crate::tests::{
-        Operation::{Create, Insert},
+        Operation::{Create, Insert, Migration},
         TestData, TestDataBuilder, TestErr,
     };
-    use axum::{extract::State, Extension, Json};
+    use axum::{
+        extract::{Query, State},
+        Extension, Json,
+    };
     use dotenvy::dotenv;
     use entity::entities::user;
+    use std::collections::HashMap;
+    use uuid::Uuid;
 
     #[tokio::test]
     async fn get_existing_user() -> Result<(), TestErr> {
@@ -350,8 +674,11 @@ mod test_get_current_user {
         };
 
         // Actual test start
-        let expected = UserDto { user: user.into() };
-        let result = get_current_user(State(connection), Extension(token)).await?;
+        let expected = UserDto {
+            user: user.try_into().unwrap(),
+        };
+        let result =
+            get_current_user(Query(HashMap::new()), State(connection), Extension(token)).await?;
         let Json(result) = result;
 
         assert_eq!(result.user.email, expected.user.email);
@@ -370,18 +697,100 @@ mod test_get_current_user {
             id: user.id,
         };
 
-        let result = get_current_user(State(connection), Extension(token)).await;
+        let result =
+            get_current_user(Query(HashMap::new()), State(connection), Extension(token)).await;
         matches!(result, Err(ApiErr::UserNotExist));
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn following_omitted_by_default() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .followers(Insert(vec![(2, 1)]))
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        let Json(result) =
+            get_current_user(Query(HashMap::new()), State(connection), Extension(token)).await?;
+        assert_eq!(result.following, None);
+        let value = serde_json::to_value(result).unwrap();
+        assert!(value.get("following").is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn expand_following() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(4))
+            .followers(Insert(vec![(4, 1), (2, 1), (1, 3)]))
+            .build()
+            .await?;
+        let users = users.unwrap();
+        let params = HashMap::from([("expand".to_owned(), "following".to_owned())]);
+
+        let token = Token {
+            exp: 35,
+            id: users[0].id,
+        };
+        let Json(result) = get_current_user(
+            Query(params.clone()),
+            State(connection.clone()),
+            Extension(token),
+        )
+        .await?;
+        let value = serde_json::to_value(result).unwrap();
+        assert_eq!(
+            value["following"],
+            serde_json::json!(["username2", "username4"])
+        );
+
+        // User without followed users gets empty list
+        let token = Token {
+            exp: 35,
+            id: users[1].id,
+        };
+        let Json(result) =
+            get_current_user(Query(params), State(connection), Extension(token)).await?;
+        assert_eq!(result.following, Some(vec![]));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn expand_following_of_non_existing_user() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: Uuid::new_v4(),
+        };
+        let params = HashMap::from([("expand".to_owned(), "following".to_owned())]);
+
+        let result = get_current_user(Query(params), State(connection), Extension(token)).await;
+        assert!(matches!(result, Err(ApiErr::UserNotExist)));
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test_update_user {
-    use super::{update_user, UpdateUser, UpdateUserDto, UserDto};
+    use super::{
+        login_user, update_user, LoginUser, LoginUserDto, UpdateUser, UpdateUserDto, UserDto,
+    };
     use crate::api::error::ApiErr;
     use crate::middleware::auth::Token;
+    use crate::repo::user_cache::UserCache;
     use crate::tests::{
         Operation::{Create, Insert},
         TestData, TestDataBuilder, TestErr,
@@ -389,6 +798,8 @@ mod test_update_user {
     use axum::{extract::State, Extension, Json};
     use dotenvy::dotenv;
     use entity::entities::user;
+    use serde_json::json;
+    use std::sync::Arc;
 
     #[tokio::test]
     async fn update_existing_user() -> Result<(), TestErr> {
@@ -412,8 +823,16 @@ mod test_update_user {
         };
 
         // Actual test start
-        let expected = UserDto { user: user.into() };
-        let result = update_user(State(connection), Extension(token), Json(payload)).await?;
+        let expected = UserDto {
+            user: user.try_into().unwrap(),
+        };
+        let result = update_user(
+            State(connection),
+            Extension(token),
+            Extension(Arc::new(UserCache::default())),
+            Json(payload),
+        )
+        .await?;
         let Json(result) = result;
 
         assert_eq!(result.user.username, expected.user.username);
@@ -441,10 +860,254 @@ mod test_update_user {
         };
 
         // Actual test start
-        let result = update_user(State(connection), Extension(token), Json(payload)).await;
+        let result = update_user(
+            State(connection),
+            Extension(token),
+            Extension(Arc::new(UserCache::default())),
+            Json(payload),
+        )
+        .await;
 
         matches!(result, Err(ApiErr::UserNotExist));
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn update_with_invalid_email() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) =
+            TestDataBuilder::new().users(Insert(1)).build().await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        let payload = UpdateUserDto {
+            user: UpdateUser {
+                email: Some("notanemail".to_owned()),
+                ..Default::default()
+            },
+        };
+        let result = update_user(
+            State(connection),
+            Extension(token),
+            Extension(Arc::new(UserCache::default())),
+            Json(payload),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApiErr::InvalidEmail)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn bio_clearing_semantics() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) =
+            TestDataBuilder::new().users(Insert(1)).build().await?;
+        let user: user::Model = users.unwrap().into_iter().next().unwrap();
+        let token = Token {
+            exp: 35,
+            id: user.id,
+        };
+
+        // Omitted field leaves value unchanged
+        let payload: UpdateUserDto =
+            serde_json::from_value(json!({ "user": { "username": "renamed" } })).unwrap();
+        let Json(result) = update_user(
+            State(connection.clone()),
+            Extension(token.clone()),
+            Extension(Arc::new(UserCache::default())),
+            Json(payload),
+        )
+        .await?;
+        assert_eq!(result.user.bio, user.bio);
+        assert_eq!(result.user.image, user.image);
+
+        // Value sets new bio
+        let payload: UpdateUserDto =
+            serde_json::from_value(json!({ "user": { "bio": "new bio" } })).unwrap();
+        let Json(result) = update_user(
+            State(connection.clone()),
+            Extension(token.clone()),
+            Extension(Arc::new(UserCache::default())),
+            Json(payload),
+        )
+        .await?;
+        assert_eq!(result.user.bio, Some("new bio".to_owned()));
+
+        // Explicit null clears bio only
+        let payload: UpdateUserDto =
+            serde_json::from_value(json!({ "user": { "bio": null } })).unwrap();
+        let Json(result) = update_user(
+            State(connection),
+            Extension(token),
+            Extension(Arc::new(UserCache::default())),
+            Json(payload),
+        )
+        .await?;
+        assert_eq!(result.user.bio, None);
+        assert_eq!(result.user.image, user.image);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn login_with_updated_password() -> Result<(), TestErr> {
+        dotenv().expect(".env file not found");
+        let (connection, TestData { users, .. }) =
+            TestDataBuilder::new().users(Insert(1)).build().await?;
+        let user: user::Model = users.unwrap().into_iter().next().unwrap();
+        let token = Token {
+            exp: 35,
+            id: user.id,
+        };
+
+        let payload: UpdateUserDto =
+            serde_json::from_value(json!({ "user": { "password": "new password" } })).unwrap();
+        let Json(updated) = update_user(
+            State(connection.clone()),
+            Extension(token),
+            Extension(Arc::new(UserCache::default())),
+            Json(payload),
+        )
+        .await?;
+        assert_eq!(updated.user.email, user.email);
+
+        let login_data = LoginUserDto {
+            user: LoginUser {
+                email: Some(user.email.clone()),
+                login: None,
+                password: "new password".to_owned(),
+            },
+        };
+        let (_, Json(result)) = login_user(State(connection), Json(login_data)).await?;
+        assert_eq!(result.user.email, user.email);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_clear_favorites {
+    use super::clear_favorites;
+    use crate::middleware::auth::Token;
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use axum::{extract::State, Extension};
+    use entity::entities::prelude::FavoritedArticle;
+    use sea_orm::EntityTrait;
+
+    #[tokio::test]
+    async fn clear_only_current_user_favorites() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2]))
+            .favorited_articles(Insert(vec![(1, 1), (2, 1), (1, 2), (2, 2)]))
+            .build()
+            .await?;
+        let users = users.unwrap();
+        let token = Token {
+            exp: 35,
+            id: users[0].id,
+        };
+
+        let _result = clear_favorites(State(connection.clone()), Extension(token)).await?;
+
+        let remaining = FavoritedArticle::find().all(&connection).await?;
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.iter().all(|mdl| mdl.user_id == users[1].id));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_delete_current_user {
+    use super::delete_current_user;
+    use crate::api::error::ApiErr;
+    use crate::middleware::auth::Token;
+    use crate::repo::user::get_user_by_id;
+    use crate::repo::user_cache::UserCache;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use axum::{extract::State, Extension};
+    use entity::entities::prelude::{Article, Comment, FavoritedArticle, Follower};
+    use sea_orm::EntityTrait;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn delete_user_with_content() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2]))
+            .comments(Insert(vec![(1, 2), (2, 1)]))
+            .favorited_articles(Insert(vec![(1, 2), (2, 1)]))
+            .followers(Insert(vec![(1, 2), (2, 1)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+        let users = users.unwrap();
+        let token = Token {
+            exp: 35,
+            id: users[0].id,
+        };
+
+        let _result = delete_current_user(
+            State(connection.clone()),
+            Extension(token),
+            Extension(Arc::new(UserCache::default())),
+        )
+        .await?;
+
+        assert!(
+            get_user_by_id(&connection, &UserCache::default(), users[0].id)
+                .await?
+                .is_none()
+        );
+        let articles = Article::find().all(&connection).await?;
+        assert_eq!(articles.len(), 1);
+        assert!(articles.iter().all(|mdl| mdl.author_id == users[1].id));
+        // Comment of user2 on article of user1 removed together with the article
+        assert!(Comment::find().all(&connection).await?.is_empty());
+        assert!(FavoritedArticle::find().all(&connection).await?.is_empty());
+        assert!(Follower::find().all(&connection).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_already_deleted_user() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Migration)
+            .comments(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let token = Token {
+            exp: 35,
+            id: users.unwrap()[0].id,
+        };
+
+        let _result = delete_current_user(
+            State(connection.clone()),
+            Extension(token.clone()),
+            Extension(Arc::new(UserCache::default())),
+        )
+        .await?;
+        let result = delete_current_user(
+            State(connection),
+            Extension(token),
+            Extension(Arc::new(UserCache::default())),
+        )
+        .await;
+        assert!(matches!(result, Err(ApiErr::UserNotExist)));
+
+        Ok(())
+    }
 }
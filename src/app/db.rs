@@ -1,7 +1,7 @@
 #[cfg(feature = "seed")]
 use crate::seed::{empty_all_tables, populate_seeds};
 use migration::{Migrator, MigratorTrait};
-use sea_orm::{ConnectOptions, Database, DatabaseConnection, DbErr};
+use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbErr};
 use std::env;
 
 const DATABASE_URL: &str = "DATABASE_URL";
@@ -15,9 +15,13 @@ pub async fn start() -> Result<DatabaseConnection, DbErr> {
         .to_owned();
 
     let connection: DatabaseConnection = Database::connect(connect_options).await?;
-    println!("The database connection established on {url}");
+    println!(
+        "The database connection established on {url} ({:?} backend)",
+        connection.get_database_backend()
+    );
 
-    Migrator::up(&connection, None).await?;
+    let applied = migrate(&connection).await?;
+    println!("Applied {applied} pending migration(s)");
 
     #[cfg(feature = "seed")]
     {
@@ -30,3 +34,56 @@ pub async fn start() -> Result<DatabaseConnection, DbErr> {
 
     Ok(connection)
 }
+
+/// Apply pending migrations. Refuse to proceed when database contains applied migration
+/// unknown to the binary, i.e. schema is ahead of the code.
+/// Returns number of applied migrations on success, otherwise returns an `database error`.
+pub async fn migrate(db: &DatabaseConnection) -> Result<usize, DbErr> {
+    // Fails with `DbErr::Custom` when applied migration file is missing
+    let pending = Migrator::get_pending_migrations(db).await?.len();
+    if pending > 0 {
+        Migrator::up(db, None).await?;
+    }
+
+    Ok(pending)
+}
+
+#[cfg(test)]
+mod test_migrate {
+    use super::{migrate, Migrator, MigratorTrait};
+    use sea_orm::{ConnectionTrait, Database, DbErr, Statement};
+
+    #[tokio::test]
+    async fn apply_twice() -> Result<(), DbErr> {
+        let connection = Database::connect("sqlite::memory:").await?;
+
+        let applied = migrate(&connection).await?;
+        assert_eq!(applied, Migrator::migrations().len());
+
+        let applied = migrate(&connection).await?;
+        assert_eq!(applied, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unknown_migration_in_database() -> Result<(), DbErr> {
+        let connection = Database::connect("sqlite::memory:").await?;
+        migrate(&connection).await?;
+
+        let backend = connection.get_database_backend();
+        connection
+            .execute(Statement::from_string(
+                backend,
+                "INSERT INTO seaql_migrations (version, applied_at) \
+                 VALUES ('m29991231_000001_unknown', 0)",
+            ))
+            .await?;
+
+        let result = migrate(&connection).await;
+        assert!(result.is_err_and(|err| matches!(err, DbErr::Custom(msg)
+            if msg.contains("m29991231_000001_unknown"))));
+
+        Ok(())
+    }
+}
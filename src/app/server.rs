@@ -1,72 +1,273 @@
 use crate::api::{
     article::{
-        create_article, delete_article, favorite_article, feed_articles, get_article,
-        list_articles, unfavorite_article, update_article,
+        add_article_tag, archive_articles, articles_by_slugs, create_article, delete_article,
+        delete_article_tag, favorite_article, feed_articles, get_article_versioned, import_article,
+        list_articles, list_drafts, list_favorite_articles, list_own_articles,
+        recently_tagged_articles, related_articles, transfer_article_author, trending_articles,
+        unfavorite_article, update_article,
+    },
+    comment::{create_comment, delete_comment, list_comments, update_comment},
+    health::{db_health, health},
+    limits::{get_limits, PageConfig},
+    live::{favorites_ws, notifications_ws, FavoritesHub, NotificationsHub},
+    maintenance::{get_dangling_rows, purge_dangling},
+    profile::{
+        block_user, follow_user, get_profile, list_followers, list_following, mutual_follows,
+        search_profiles, unblock_user, unfollow_user,
+    },
+    reaction::{add_reaction, list_reactions, remove_reaction},
+    sitemap::get_sitemap,
+    tags::{list_tags, TOTAL_COUNT_HEADER},
+    user::{
+        clear_favorites, delete_current_user, get_current_user, login_user, register_user,
+        update_user,
     },
-    comment::{create_comment, delete_comment, list_comments},
-    profile::{follow_user, get_profile, unfollow_user},
-    tags::list_tags,
-    user::{get_current_user, login_user, register_user, update_user},
 };
-use crate::middleware::auth::{auth, optional_auth};
+use crate::middleware::{
+    admin::require_admin,
+    auth::{auth, optional_auth},
+    lifecycle::{track_in_flight, Lifecycle},
+    logging::log_request_body,
+    rate_limit::{limit_comments, CommentRateLimiter},
+    version::{api_version, DEPRECATION_HEADER},
+};
+use crate::repo::user_cache::UserCache;
 use axum::{
+    http::HeaderName,
     middleware::from_fn,
     routing::{delete, get, post, put},
-    Router,
+    Extension, Router,
 };
 use sea_orm::DatabaseConnection;
 use std::env;
 use std::net::{IpAddr, SocketAddr};
 use std::str::FromStr;
-use tower::ServiceBuilder;
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(unix)]
+use tokio::signal::unix::{signal, SignalKind};
+use tower::{
+    make::Shared,
+    util::{option_layer, Either},
+    Layer, ServiceBuilder,
+};
+use tower_http::{
+    cors::{Any, CorsLayer},
+    normalize_path::{NormalizePath, NormalizePathLayer},
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+};
 
 const DEFAULT_APP_PORT: u16 = 3000;
 const DEFAULT_APP_HOST: &str = "127.0.0.1";
 const APP_PORT: &str = "APP_PORT";
 const APP_HOST: &str = "APP_HOST";
+const APP_TRIM_TRAILING_SLASH: &str = "APP_TRIM_TRAILING_SLASH";
+const APP_CORS_EXPOSE_HEADERS: &str = "APP_CORS_EXPOSE_HEADERS";
+const REQUEST_ID_HEADER: &str = "x-request-id";
+const SHUTDOWN_DRAIN_PERIOD: Duration = Duration::from_secs(5);
 
 pub async fn start(connection: DatabaseConnection) {
+    let lifecycle = Arc::new(Lifecycle::default());
+    let app = app(connection, lifecycle.clone());
+
+    let addr = get_socket_address();
+    println!("Server listening on {addr}");
+    axum::Server::bind(&addr)
+        .serve(Shared::new(app))
+        .with_graceful_shutdown(shutdown_signal(lifecycle))
+        .await
+        .unwrap();
+}
+
+/// Resolves on Ctrl+C or SIGTERM (sent by Docker and Kubernetes on stop) after draining
+/// period. During draining `/health` reports 503, so load balancer stops sending traffic,
+/// then server stops accepting connections and waits for requests in flight to complete.
+async fn shutdown_signal(lifecycle: Arc<Lifecycle>) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal(SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    lifecycle.start_shutdown();
+    println!(
+        "Shutting down, draining {} requests in flight",
+        lifecycle.in_flight()
+    );
+    tokio::time::sleep(SHUTDOWN_DRAIN_PERIOD).await;
+}
+
+/// Build application service. Trailing slashes are trimmed from request path
+/// before routing unless disabled with APP_TRIM_TRAILING_SLASH.
+fn app(
+    connection: DatabaseConnection,
+    lifecycle: Arc<Lifecycle>,
+) -> Either<NormalizePath<Router>, Router> {
+    let normalize_path =
+        option_layer(trim_trailing_slash_enabled().then(NormalizePathLayer::trim_trailing_slash));
+    normalize_path.layer(router(connection, lifecycle))
+}
+
+/// Build router with all api routes
+fn router(connection: DatabaseConnection, lifecycle: Arc<Lifecycle>) -> Router {
     let optional_auth_routes = Router::new()
         .route("/api/users", post(register_user))
         .route("/api/users/login", post(login_user))
+        .route("/api/profiles/search", get(search_profiles))
         .route("/api/profiles/:username", get(get_profile))
+        .route("/api/profiles/:username/mutuals", get(mutual_follows))
+        .route("/api/profiles/:username/followers", get(list_followers))
+        .route("/api/profiles/:username/following", get(list_following))
         .route("/api/articles", get(list_articles))
-        .route("/api/articles/:slug", get(get_article))
+        .route("/api/articles/trending", get(trending_articles))
+        .route("/api/articles/by-slugs", post(articles_by_slugs))
+        .route(
+            "/api/articles/:slug",
+            get(get_article_versioned).route_layer(from_fn(api_version)),
+        )
+        .route("/api/articles/:slug/related", get(related_articles))
         .route("/api/articles/:slug/comments", get(list_comments))
+        .route("/api/articles/:slug/ws", get(favorites_ws))
+        .route("/api/articles/:slug/reactions", get(list_reactions))
         .route("/api/tags", get(list_tags))
+        .route("/api/tags/:tag/recent", get(recently_tagged_articles))
+        .route("/api/archive/:year/:month", get(archive_articles))
+        .route("/api/limits", get(get_limits))
+        .route("/sitemap.xml", get(get_sitemap))
         .layer(ServiceBuilder::new().layer(from_fn(optional_auth)));
 
-    let auth_routes = Router::new()
-        .route("/api/user", put(update_user).get(get_current_user))
-        .route(
+    // Auth applied to each method router, so not allowed method responds
+    // 405 with `Allow` header before token is checked
+    let auth_routes = [
+        (
+            "/api/user",
+            put(update_user)
+                .get(get_current_user)
+                .delete(delete_current_user),
+        ),
+        (
+            "/api/user/favorites",
+            get(list_favorite_articles).delete(clear_favorites),
+        ),
+        ("/api/user/drafts", get(list_drafts)),
+        ("/api/user/articles", get(list_own_articles)),
+        ("/api/user/notifications/ws", get(notifications_ws)),
+        (
             "/api/profiles/:username/follow",
             post(follow_user).delete(unfollow_user),
-        )
-        .route("/api/articles", post(create_article))
-        .route("/api/articles/feed", get(feed_articles))
-        .route(
+        ),
+        (
+            "/api/profiles/:username/block",
+            post(block_user).delete(unblock_user),
+        ),
+        ("/api/articles", post(create_article)),
+        ("/api/articles/import", post(import_article)),
+        ("/api/articles/feed", get(feed_articles)),
+        (
             "/api/articles/:slug",
             put(update_article).delete(delete_article),
-        )
-        .route(
+        ),
+        (
             "/api/articles/:slug/favorite",
             post(favorite_article).delete(unfavorite_article),
-        )
-        .route("/api/articles/:slug/comments", post(create_comment))
-        .route("/api/articles/:slug/comments/:id", delete(delete_comment))
-        .layer(ServiceBuilder::new().layer(from_fn(auth)));
+        ),
+        ("/api/articles/:slug/tags", post(add_article_tag)),
+        ("/api/articles/:slug/tags/:tag", delete(delete_article_tag)),
+        (
+            "/api/articles/:slug/comments",
+            post(create_comment).layer(from_fn(limit_comments)),
+        ),
+        ("/api/articles/:slug/reactions", post(add_reaction)),
+        (
+            "/api/articles/:slug/reactions/:kind",
+            delete(remove_reaction),
+        ),
+        (
+            "/api/articles/:slug/comments/:id",
+            put(update_comment).delete(delete_comment),
+        ),
+        (
+            "/api/admin/articles/:slug/author",
+            put(transfer_article_author).layer(from_fn(require_admin)),
+        ),
+        (
+            "/api/admin/maintenance/dangling",
+            get(get_dangling_rows)
+                .delete(purge_dangling)
+                .layer(from_fn(require_admin)),
+        ),
+    ]
+    .into_iter()
+    .fold(Router::new(), |router, (path, method_router)| {
+        router.route(path, method_router.route_layer(from_fn(auth)))
+    });
 
-    let app = Router::new()
+    Router::new()
         .merge(auth_routes)
         .merge(optional_auth_routes)
-        .with_state(connection);
+        .route("/health", get(health))
+        .route("/health/db", get(db_health))
+        .layer(from_fn(log_request_body))
+        .layer(from_fn(track_in_flight))
+        .layer(Extension(lifecycle))
+        .layer(Extension(PageConfig::from_env()))
+        .layer(Extension(Arc::new(CommentRateLimiter::from_env())))
+        .layer(Extension(Arc::new(UserCache::from_env())))
+        .layer(Extension(Arc::new(FavoritesHub::default())))
+        .layer(Extension(Arc::new(NotificationsHub::default())))
+        // Request id provided by client is kept, otherwise generated, and echoed in response
+        .layer(PropagateRequestIdLayer::new(HeaderName::from_static(
+            REQUEST_ID_HEADER,
+        )))
+        .layer(SetRequestIdLayer::new(
+            HeaderName::from_static(REQUEST_ID_HEADER),
+            MakeRequestUuid,
+        ))
+        .layer(cors_layer())
+        .with_state(connection)
+}
 
-    let addr = get_socket_address();
-    println!("Server listening on {addr}");
-    axum::Server::bind(&addr)
-        .serve(app.into_make_service())
-        .await
-        .unwrap();
+/// Build CORS layer allowing any origin. Custom response headers (pagination, request id)
+/// exposed to browser scripts, see APP_CORS_EXPOSE_HEADERS.
+fn cors_layer() -> CorsLayer {
+    CorsLayer::new()
+        .allow_origin(Any)
+        .allow_methods(Any)
+        .allow_headers(Any)
+        .expose_headers(get_cors_expose_headers())
+}
+
+/// Return APP_CORS_EXPOSE_HEADERS (comma separated) from environment varibles or default
+/// pagination, request id and deprecation headers. Invalid header names are skipped.
+fn get_cors_expose_headers() -> Vec<HeaderName> {
+    env::var(APP_CORS_EXPOSE_HEADERS)
+        .unwrap_or_else(|_| [TOTAL_COUNT_HEADER, REQUEST_ID_HEADER, DEPRECATION_HEADER].join(","))
+        .split(',')
+        .filter_map(|name| HeaderName::from_str(name.trim()).ok())
+        .collect()
+}
+
+/// Return APP_TRIM_TRAILING_SLASH from environment varibles, enabled by default
+fn trim_trailing_slash_enabled() -> bool {
+    env::var(APP_TRIM_TRAILING_SLASH).map_or(true, |value| {
+        !matches!(value.to_lowercase().as_str(), "false" | "0" | "off")
+    })
 }
 
 /// Return APP_PORT from environment varibles or defalt port (3000)
@@ -148,3 +349,267 @@ mod get_socket_address_tests {
         assert_eq!(Ok(get_socket_address()), expected.parse());
     }
 }
+
+#[cfg(test)]
+mod trim_trailing_slash_enabled_tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn when_env_set() {
+        env::set_var(APP_TRIM_TRAILING_SLASH, "false");
+        assert!(!trim_trailing_slash_enabled());
+    }
+
+    #[test]
+    #[serial]
+    fn when_env_not_set() {
+        env::remove_var(APP_TRIM_TRAILING_SLASH);
+        assert!(trim_trailing_slash_enabled());
+    }
+}
+
+#[cfg(test)]
+mod get_cors_expose_headers_tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn when_env_set() {
+        env::set_var(APP_CORS_EXPOSE_HEADERS, "x-page, ,x-per-page");
+        assert_eq!(get_cors_expose_headers(), ["x-page", "x-per-page"]);
+        env::remove_var(APP_CORS_EXPOSE_HEADERS);
+    }
+
+    #[test]
+    #[serial]
+    fn when_env_not_set() {
+        env::remove_var(APP_CORS_EXPOSE_HEADERS);
+        assert_eq!(
+            get_cors_expose_headers(),
+            [TOTAL_COUNT_HEADER, REQUEST_ID_HEADER, DEPRECATION_HEADER]
+        );
+    }
+}
+
+#[cfg(test)]
+mod app_tests {
+    use super::*;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestDataBuilder, TestErr,
+    };
+    use axum::{
+        body::{Body, HttpBody},
+        http::{
+            header::{
+                ACCEPT, ACCESS_CONTROL_ALLOW_ORIGIN, ACCESS_CONTROL_EXPOSE_HEADERS, ALLOW, ORIGIN,
+            },
+            Request, StatusCode,
+        },
+    };
+    use serial_test::serial;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    #[serial]
+    async fn not_allowed_method_and_unknown_path() -> Result<(), TestErr> {
+        env::remove_var(APP_TRIM_TRAILING_SLASH);
+        let (connection, _) = TestDataBuilder::new().tags(Insert(2)).build().await?;
+        let app = app(connection, Arc::new(Lifecycle::default()));
+
+        // HEAD is served by every GET route
+        for (method, uri, allow) in [
+            ("PUT", "/api/tags", "GET,HEAD"),
+            ("PATCH", "/api/user", "PUT,GET,HEAD,DELETE"),
+        ] {
+            let request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED, "{uri}");
+            assert_eq!(response.headers()[ALLOW], allow, "{uri}");
+        }
+
+        let request = Request::builder()
+            .uri("/api/unknown")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(response.headers().get(ALLOW).is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn unparsable_path_parameter() -> Result<(), TestErr> {
+        env::remove_var(APP_TRIM_TRAILING_SLASH);
+        let (connection, _) = TestDataBuilder::new().tags(Insert(2)).build().await?;
+        let app = app(connection, Arc::new(Lifecycle::default()));
+
+        let request = Request::builder()
+            .uri("/api/archive/abc/1")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let bytes = response.into_body().data().await.unwrap().unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert!(body["error"].is_string());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn tags_with_and_without_trailing_slash() -> Result<(), TestErr> {
+        env::remove_var(APP_TRIM_TRAILING_SLASH);
+        let (connection, _) = TestDataBuilder::new().tags(Insert(2)).build().await?;
+        let app = app(connection, Arc::new(Lifecycle::default()));
+
+        for uri in ["/api/tags", "/api/tags/"] {
+            let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{uri}");
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn cross_origin_exposes_pagination_headers() -> Result<(), TestErr> {
+        env::remove_var(APP_TRIM_TRAILING_SLASH);
+        env::remove_var(APP_CORS_EXPOSE_HEADERS);
+        let (connection, _) = TestDataBuilder::new().tags(Insert(2)).build().await?;
+        let app = app(connection, Arc::new(Lifecycle::default()));
+
+        let request = Request::builder()
+            .uri("/api/tags")
+            .header(ORIGIN, "http://example.com")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let headers = response.headers();
+        assert_eq!(headers[ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+        let exposed = headers[ACCESS_CONTROL_EXPOSE_HEADERS].to_str().unwrap();
+        assert!(exposed.contains(TOTAL_COUNT_HEADER));
+        assert!(exposed.contains(REQUEST_ID_HEADER));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn request_id_in_response() -> Result<(), TestErr> {
+        env::remove_var(APP_TRIM_TRAILING_SLASH);
+        let (connection, _) = TestDataBuilder::new().tags(Insert(2)).build().await?;
+        let app = app(connection, Arc::new(Lifecycle::default()));
+
+        let request = Request::builder()
+            .uri("/api/tags")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let generated = response.headers()[REQUEST_ID_HEADER].to_str().unwrap();
+        assert!(Uuid::parse_str(generated).is_ok());
+
+        let request = Request::builder()
+            .uri("/api/tags")
+            .header(REQUEST_ID_HEADER, "client-id")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "client-id");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn article_versions() -> Result<(), TestErr> {
+        env::remove_var(APP_TRIM_TRAILING_SLASH);
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let app = app(connection, Arc::new(Lifecycle::default()));
+
+        let request = Request::builder()
+            .uri("/api/articles/title1")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().contains_key(DEPRECATION_HEADER));
+
+        let request = Request::builder()
+            .uri("/api/articles/title1")
+            .header(ACCEPT, "application/vnd.conduit.v2+json")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key(DEPRECATION_HEADER));
+        let bytes = response.into_body().data().await.unwrap().unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert!(body["article"].get("authorId").is_some());
+
+        let request = Request::builder()
+            .uri("/api/tags")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key(DEPRECATION_HEADER));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn health_draining_on_shutdown() -> Result<(), TestErr> {
+        env::remove_var(APP_TRIM_TRAILING_SLASH);
+        let (connection, _) = TestDataBuilder::new().build().await?;
+        let lifecycle = Arc::new(Lifecycle::default());
+        let app = app(connection, lifecycle.clone());
+        let health_request = || {
+            Request::builder()
+                .uri("/health")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(health_request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = response.into_body().data().await.unwrap().unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        // Health request itself is in flight
+        assert_eq!(body, serde_json::json!({"status": "ok", "inFlight": 1}));
+        assert_eq!(lifecycle.in_flight(), 0);
+
+        lifecycle.start_shutdown();
+
+        let response = app.oneshot(health_request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let bytes = response.into_body().data().await.unwrap().unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["status"], "draining");
+
+        Ok(())
+    }
+}
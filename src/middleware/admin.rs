@@ -0,0 +1,101 @@
+use crate::api::error::ApiErr;
+use crate::middleware::auth::Token;
+use axum::{
+    extract::Extension,
+    http::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::env;
+use uuid::Uuid;
+
+const APP_ADMIN_IDS: &str = "APP_ADMIN_IDS";
+
+/// Reject request with 403 status unless token belongs to admin (see APP_ADMIN_IDS).
+/// Token must be already extracted by `auth` middleware.
+pub async fn require_admin<B>(
+    Extension(token): Extension<Token>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    if is_admin(token.id, &get_admin_ids()) {
+        next.run(request).await
+    } else {
+        ApiErr::Forbidden.into_response()
+    }
+}
+
+/// Check whether user with provided id is among provided admins.
+fn is_admin(user_id: Uuid, admin_ids: &[Uuid]) -> bool {
+    admin_ids.contains(&user_id)
+}
+
+/// Return APP_ADMIN_IDS (comma separated user ids) from environment varibles,
+/// invalid ids are skipped. No admins when not set.
+fn get_admin_ids() -> Vec<Uuid> {
+    env::var(APP_ADMIN_IDS)
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|id| Uuid::parse_str(id.trim()).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod test_require_admin {
+    use super::{get_admin_ids, is_admin, require_admin, APP_ADMIN_IDS};
+    use crate::middleware::auth::Token;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        middleware::from_fn,
+        routing::put,
+        Extension, Router,
+    };
+    use serial_test::serial;
+    use std::env;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    #[test]
+    fn admin_among_provided() {
+        let admin_id = Uuid::new_v4();
+        assert!(is_admin(admin_id, &[Uuid::new_v4(), admin_id]));
+        assert!(!is_admin(Uuid::new_v4(), &[admin_id]));
+        assert!(!is_admin(admin_id, &[]));
+    }
+
+    #[test]
+    #[serial]
+    fn parse_admin_ids() {
+        let admin_id = Uuid::new_v4();
+        env::set_var(APP_ADMIN_IDS, format!(" {admin_id} ,not-uuid,"));
+        assert_eq!(get_admin_ids(), vec![admin_id]);
+
+        env::remove_var(APP_ADMIN_IDS);
+        assert_eq!(get_admin_ids(), vec![]);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn reject_not_admin() {
+        let admin_id = Uuid::new_v4();
+        env::set_var(APP_ADMIN_IDS, admin_id.to_string());
+
+        for (user_id, expected) in [
+            (admin_id, StatusCode::OK),
+            (Uuid::new_v4(), StatusCode::FORBIDDEN),
+        ] {
+            let app = Router::new()
+                .route("/admin", put(|| async {}).layer(from_fn(require_admin)))
+                .layer(Extension(Token {
+                    exp: 35,
+                    id: user_id,
+                }));
+            let request = Request::put("/admin").body(Body::empty()).unwrap();
+            let response = app.oneshot(request).await.unwrap();
+            assert_eq!(response.status(), expected);
+        }
+
+        env::remove_var(APP_ADMIN_IDS);
+    }
+}
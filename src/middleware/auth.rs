@@ -2,16 +2,18 @@ use argon2::{
     password_hash::{PasswordHasher, SaltString},
     Argon2, PasswordHash, PasswordVerifier,
 };
-use axum::extract::rejection::TypedHeaderRejection;
+use axum::extract::rejection::{TypedHeaderRejection, TypedHeaderRejectionReason};
 use axum::TypedHeader;
 use axum::{
-    headers::authorization::{Authorization, Credentials},
+    headers::{
+        authorization::{Authorization, Credentials},
+        Cookie,
+    },
     http::{HeaderValue, Method, Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use bytes::Bytes;
-use chrono::Duration;
 use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use rand_core::OsRng;
 use sea_orm::prelude::Uuid;
@@ -19,6 +21,12 @@ use serde::{Deserialize, Serialize};
 use std::env;
 
 const SECRET_KEY: &str = "SECRET_KEY";
+pub const AUTH_COOKIE_NAME: &str = "AUTH_COOKIE_NAME";
+pub const AUTH_COOKIE_SECURE: &str = "AUTH_COOKIE_SECURE";
+/// Lifetime of issued token in seconds.
+const TOKEN_TTL_SECS: i64 = 100;
+/// Allowed clock skew in seconds, expired token still accepted within leeway.
+const TOKEN_LEEWAY_SECS: i64 = 60;
 
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Token {
@@ -37,13 +45,7 @@ impl Credentials for Token {
         );
 
         let tkn_str = value.to_str().unwrap().replace("Token ", "");
-        decode(
-            &tkn_str,
-            &DecodingKey::from_secret(get_secret_key().as_bytes()),
-            &Validation::new(Algorithm::HS256),
-        )
-        .ok()
-        .map(|data| data.claims)
+        decode_token(&tkn_str)
     }
 
     fn encode(&self) -> HeaderValue {
@@ -58,13 +60,23 @@ impl Credentials for Token {
     }
 }
 
+/// Middleware requiring token for all methods except GET. Token read from `Authorization`
+/// header, or from auth cookie (see AUTH_COOKIE_NAME) when header is absent.
 pub async fn auth<B: std::fmt::Debug>(
     maybe_token: Result<TypedHeader<Authorization<Token>>, TypedHeaderRejection>,
+    maybe_cookie: Option<TypedHeader<Cookie>>,
     mut request: Request<B>,
     next: Next<B>,
 ) -> Result<Response, StatusCode> {
+    let maybe_token = match maybe_token {
+        Err(err) if matches!(err.reason(), TypedHeaderRejectionReason::Missing) => {
+            token_from_cookie(maybe_cookie).ok_or(err)
+        }
+        res => res.map(|TypedHeader(Authorization(token))| token),
+    };
+
     match maybe_token {
-        Ok(TypedHeader(Authorization(token))) => {
+        Ok(token) => {
             request.extensions_mut().insert(token);
             let response = next.run(request).await;
             Ok(response)
@@ -79,22 +91,78 @@ pub async fn auth<B: std::fmt::Debug>(
     }
 }
 
+/// Middleware reading optional token from `Authorization` header, or from auth cookie
+/// (see AUTH_COOKIE_NAME) when header is absent.
 pub async fn optional_auth<B: std::fmt::Debug>(
     maybe_token: Option<TypedHeader<Authorization<Token>>>,
+    maybe_cookie: Option<TypedHeader<Cookie>>,
     mut request: Request<B>,
     next: Next<B>,
 ) -> Result<Response, StatusCode> {
-    if let Some(TypedHeader(Authorization(token))) = maybe_token {
+    let maybe_token = match maybe_token {
+        Some(TypedHeader(Authorization(token))) => Some(token),
+        None => token_from_cookie(maybe_cookie),
+    };
+    if let Some(token) = maybe_token {
         request.extensions_mut().insert(token);
     }
     let response = next.run(request).await;
     Ok(response)
 }
 
+/// Build `Set-Cookie` header value with provided token for auth cookie. Cookie marked `Secure`
+/// unless disabled for local development over plain HTTP (see AUTH_COOKIE_SECURE).
+/// Returns `None` when auth cookie is not configured (see AUTH_COOKIE_NAME).
+pub fn auth_cookie(token: &str) -> Option<HeaderValue> {
+    let name = get_auth_cookie_name()?;
+    let secure = match auth_cookie_secure() {
+        true => "; Secure",
+        false => "",
+    };
+    HeaderValue::from_str(&format!(
+        "{name}={token}; HttpOnly; Path=/; SameSite=Lax{secure}"
+    ))
+    .ok()
+}
+
+/// Decode token from auth cookie, if cookie configured and present.
+fn token_from_cookie(maybe_cookie: Option<TypedHeader<Cookie>>) -> Option<Token> {
+    let name = get_auth_cookie_name()?;
+    let TypedHeader(cookie) = maybe_cookie?;
+    cookie.get(&name).and_then(decode_token)
+}
+
+/// Decode and validate claims of provided JWT at current system time.
+fn decode_token(token: &str) -> Option<Token> {
+    decode_token_at(token, now_timestamp())
+}
+
+/// Decode and validate claims of provided JWT at provided unix timestamp `now`.
+/// Token expired more than TOKEN_LEEWAY_SECS before `now` is rejected.
+fn decode_token_at(token: &str, now: i64) -> Option<Token> {
+    // Expiry checked against provided time instead of system clock
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.validate_exp = false;
+
+    decode::<Token>(
+        token,
+        &DecodingKey::from_secret(get_secret_key().as_bytes()),
+        &validation,
+    )
+    .ok()
+    .map(|data| data.claims)
+    .filter(|claims| claims.exp as i64 + TOKEN_LEEWAY_SECS >= now)
+}
+
+/// Create JWT for user with provided `id`, issued at current system time.
 pub fn create_token(id: &Uuid) -> Result<String, jsonwebtoken::errors::Error> {
-    let now = chrono::Local::now();
-    let expires_at = now + Duration::seconds(100);
-    let exp = expires_at.timestamp() as usize;
+    create_token_at(id, now_timestamp())
+}
+
+/// Create JWT for user with provided `id`, issued at provided unix timestamp `now`.
+/// Token expires TOKEN_TTL_SECS after `now`.
+fn create_token_at(id: &Uuid, now: i64) -> Result<String, jsonwebtoken::errors::Error> {
+    let exp = (now + TOKEN_TTL_SECS) as usize;
     let claims = Token { exp, id: *id };
     let token_header = Header::default();
 
@@ -104,6 +172,11 @@ pub fn create_token(id: &Uuid) -> Result<String, jsonwebtoken::errors::Error> {
     encode(&token_header, &claims, &key)
 }
 
+/// Current unix timestamp in seconds according to system clock.
+fn now_timestamp() -> i64 {
+    chrono::Local::now().timestamp()
+}
+
 pub fn hash_password(pass: &str) -> Result<String, argon2::password_hash::Error> {
     let salt = SaltString::generate(&mut OsRng);
     Argon2::default()
@@ -120,3 +193,195 @@ pub fn check_passwords(tested: &str, real: &str) -> Result<(), argon2::password_
 fn get_secret_key() -> String {
     env::var(SECRET_KEY).expect("env variable SECRET_KEY should be set for JWT generation")
 }
+
+/// Return AUTH_COOKIE_NAME from environment varibles, cookie auth disabled when not set or empty
+fn get_auth_cookie_name() -> Option<String> {
+    env::var(AUTH_COOKIE_NAME)
+        .ok()
+        .filter(|name| !name.is_empty())
+}
+
+/// Return AUTH_COOKIE_SECURE from environment varibles, enabled by default
+fn auth_cookie_secure() -> bool {
+    env::var(AUTH_COOKIE_SECURE).map_or(true, |value| {
+        !matches!(value.to_lowercase().as_str(), "false" | "0" | "off")
+    })
+}
+
+#[cfg(test)]
+mod test_auth_cookie {
+    use super::{auth_cookie, AUTH_COOKIE_NAME, AUTH_COOKIE_SECURE};
+    use serial_test::serial;
+    use std::env;
+
+    #[test]
+    #[serial]
+    fn secure_unless_disabled() {
+        env::set_var(AUTH_COOKIE_NAME, "jwt");
+
+        env::remove_var(AUTH_COOKIE_SECURE);
+        let cookie = auth_cookie("token").unwrap();
+        assert_eq!(
+            cookie.to_str().unwrap(),
+            "jwt=token; HttpOnly; Path=/; SameSite=Lax; Secure"
+        );
+
+        env::set_var(AUTH_COOKIE_SECURE, "false");
+        let cookie = auth_cookie("token").unwrap();
+        assert_eq!(
+            cookie.to_str().unwrap(),
+            "jwt=token; HttpOnly; Path=/; SameSite=Lax"
+        );
+
+        env::remove_var(AUTH_COOKIE_SECURE);
+        env::remove_var(AUTH_COOKIE_NAME);
+        assert!(auth_cookie("token").is_none());
+    }
+}
+
+#[cfg(test)]
+mod test_token_extraction {
+    use super::{create_token, optional_auth, Token, AUTH_COOKIE_NAME};
+    use axum::{
+        body::{Body, HttpBody},
+        http::{
+            header::{AUTHORIZATION, COOKIE},
+            Request,
+        },
+        middleware::from_fn,
+        routing::get,
+        Extension, Router,
+    };
+    use dotenvy::dotenv;
+    use serial_test::serial;
+    use std::env;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    /// Send request with provided headers, returns id from extracted token or empty string.
+    async fn extracted_id(headers: &[(&str, String)]) -> String {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|maybe_token: Option<Extension<Token>>| async move {
+                    maybe_token.map_or(String::new(), |tkn| tkn.id.to_string())
+                }),
+            )
+            .layer(from_fn(optional_auth));
+
+        let request = headers
+            .iter()
+            .fold(Request::builder().uri("/"), |req, (name, value)| {
+                req.header(*name, value)
+            })
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let body = response.into_body().data().await;
+        body.map_or(String::new(), |bytes| {
+            String::from_utf8(bytes.unwrap().to_vec()).unwrap()
+        })
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn token_from_header() {
+        dotenv().expect(".env file not found");
+        env::remove_var(AUTH_COOKIE_NAME);
+        let id = Uuid::new_v4();
+        let token = create_token(&id).unwrap();
+
+        let result = extracted_id(&[(AUTHORIZATION.as_str(), format!("Token {token}"))]).await;
+
+        assert_eq!(result, id.to_string());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn token_from_cookie() {
+        dotenv().expect(".env file not found");
+        env::set_var(AUTH_COOKIE_NAME, "jwt");
+        let id = Uuid::new_v4();
+        let token = create_token(&id).unwrap();
+
+        let result = extracted_id(&[(COOKIE.as_str(), format!("theme=dark; jwt={token}"))]).await;
+        env::remove_var(AUTH_COOKIE_NAME);
+
+        assert_eq!(result, id.to_string());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn header_takes_precedence() {
+        dotenv().expect(".env file not found");
+        env::set_var(AUTH_COOKIE_NAME, "jwt");
+        let header_id = Uuid::new_v4();
+        let cookie_id = Uuid::new_v4();
+        let header_token = create_token(&header_id).unwrap();
+        let cookie_token = create_token(&cookie_id).unwrap();
+
+        let result = extracted_id(&[
+            (AUTHORIZATION.as_str(), format!("Token {header_token}")),
+            (COOKIE.as_str(), format!("jwt={cookie_token}")),
+        ])
+        .await;
+        env::remove_var(AUTH_COOKIE_NAME);
+
+        assert_eq!(result, header_id.to_string());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn cookie_ignored_when_not_configured() {
+        dotenv().expect(".env file not found");
+        env::remove_var(AUTH_COOKIE_NAME);
+        let token = create_token(&Uuid::new_v4()).unwrap();
+
+        let result = extracted_id(&[(COOKIE.as_str(), format!("jwt={token}"))]).await;
+
+        assert_eq!(result, "");
+    }
+}
+
+#[cfg(test)]
+mod test_token_expiry {
+    use super::{
+        create_token_at, decode_token, decode_token_at, now_timestamp, TOKEN_LEEWAY_SECS,
+        TOKEN_TTL_SECS,
+    };
+    use dotenvy::dotenv;
+    use uuid::Uuid;
+
+    const ISSUED_AT: i64 = 1_700_000_000;
+
+    #[test]
+    fn valid_until_expiry_and_leeway() {
+        dotenv().expect(".env file not found");
+        let id = Uuid::new_v4();
+        let token = create_token_at(&id, ISSUED_AT).unwrap();
+
+        let claims = decode_token_at(&token, ISSUED_AT).unwrap();
+        assert_eq!(claims.id, id);
+        assert_eq!(claims.exp as i64, ISSUED_AT + TOKEN_TTL_SECS);
+
+        let last_valid = ISSUED_AT + TOKEN_TTL_SECS + TOKEN_LEEWAY_SECS;
+        assert!(decode_token_at(&token, last_valid).is_some());
+        assert!(decode_token_at(&token, last_valid + 1).is_none());
+    }
+
+    #[test]
+    fn minted_in_the_past_is_expired() {
+        dotenv().expect(".env file not found");
+        let token = create_token_at(&Uuid::new_v4(), ISSUED_AT).unwrap();
+
+        assert!(decode_token(&token).is_none());
+    }
+
+    #[test]
+    fn minted_now_is_valid() {
+        dotenv().expect(".env file not found");
+        let token = create_token_at(&Uuid::new_v4(), now_timestamp()).unwrap();
+
+        assert!(decode_token(&token).is_some());
+    }
+}
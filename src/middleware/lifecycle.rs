@@ -0,0 +1,85 @@
+use axum::{extract::Extension, http::Request, middleware::Next, response::Response};
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+
+/// Server state shared between requests. Tracks number of requests in flight
+/// and whether server is shutting down (draining).
+#[derive(Debug, Default)]
+pub struct Lifecycle {
+    in_flight: AtomicUsize,
+    shutting_down: AtomicBool,
+}
+
+impl Lifecycle {
+    /// Number of requests currently processed by server.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Whether shutdown started, thus server only completes existing requests.
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Mark server as shutting down.
+    pub fn start_shutdown(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Decrements in flight counter when request completes, even if handler panics.
+struct InFlightGuard(Arc<Lifecycle>);
+
+impl InFlightGuard {
+    fn new(lifecycle: Arc<Lifecycle>) -> Self {
+        lifecycle.in_flight.fetch_add(1, Ordering::SeqCst);
+        Self(lifecycle)
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Count request as in flight while it is processed.
+pub async fn track_in_flight<B>(
+    Extension(lifecycle): Extension<Arc<Lifecycle>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let _guard = InFlightGuard::new(lifecycle);
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod test_lifecycle {
+    use super::{InFlightGuard, Lifecycle};
+    use std::sync::Arc;
+
+    #[test]
+    fn in_flight_guard() {
+        let lifecycle = Arc::new(Lifecycle::default());
+
+        let first = InFlightGuard::new(lifecycle.clone());
+        let second = InFlightGuard::new(lifecycle.clone());
+        assert_eq!(lifecycle.in_flight(), 2);
+
+        drop(first);
+        assert_eq!(lifecycle.in_flight(), 1);
+        drop(second);
+        assert_eq!(lifecycle.in_flight(), 0);
+    }
+
+    #[test]
+    fn shutdown_flag() {
+        let lifecycle = Lifecycle::default();
+        assert!(!lifecycle.is_shutting_down());
+
+        lifecycle.start_shutdown();
+        assert!(lifecycle.is_shutting_down());
+    }
+}
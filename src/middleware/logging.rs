@@ -0,0 +1,151 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::FromRequest,
+    http::{Method, Request, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::Value;
+use std::env;
+
+const APP_LOG_REQUEST_BODY: &str = "APP_LOG_REQUEST_BODY";
+const REDACTED: &str = "[REDACTED]";
+const SENSITIVE_FIELDS: [&str; 3] = ["password", "currentPassword", "newPassword"];
+
+/// Log method, path and body of request when enabled with APP_LOG_REQUEST_BODY.
+/// Sensitive fields of JSON body are redacted, non JSON bodies are not logged.
+pub async fn log_request_body(
+    request: Request<Body>,
+    next: Next<Body>,
+) -> Result<Response, Response> {
+    if !log_request_body_enabled() {
+        return Ok(next.run(request).await);
+    }
+
+    let (parts, body) = request.into_parts();
+    let bytes = Bytes::from_request(Request::new(body), &())
+        .await
+        .map_err(IntoResponse::into_response)?;
+
+    println!("{}", request_log_line(&parts.method, &parts.uri, &bytes));
+
+    let request = Request::from_parts(parts, Body::from(bytes));
+    Ok(next.run(request).await)
+}
+
+/// Build log line for request with redacted body.
+fn request_log_line(method: &Method, uri: &Uri, body: &[u8]) -> String {
+    let body = match body.is_empty() {
+        true => String::new(),
+        false => redact_body(body).unwrap_or_else(|| "[non-JSON body omitted]".to_owned()),
+    };
+    format!("{method} {} {body}", uri.path())
+        .trim_end()
+        .to_owned()
+}
+
+/// Parse body as JSON and replace values of sensitive fields (at any depth) with
+/// `[REDACTED]`. Returns `None` when body is not valid JSON.
+fn redact_body(body: &[u8]) -> Option<String> {
+    let mut value: Value = serde_json::from_slice(body).ok()?;
+    redact_value(&mut value);
+    Some(value.to_string())
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.iter_mut().for_each(
+                |(key, val)| match SENSITIVE_FIELDS.contains(&key.as_str()) {
+                    true => *val = Value::String(REDACTED.to_owned()),
+                    false => redact_value(val),
+                },
+            )
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+/// Return APP_LOG_REQUEST_BODY from environment varibles, disabled by default
+fn log_request_body_enabled() -> bool {
+    env::var(APP_LOG_REQUEST_BODY)
+        .map(|val| val.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod test_request_log_line {
+    use super::request_log_line;
+    use axum::http::{Method, Uri};
+
+    #[test]
+    fn register_body_redacted() {
+        let body = r#"{"user":{"username":"jake","email":"jake@jake.jake","password":"jakejake"}}"#;
+
+        let result = request_log_line(
+            &Method::POST,
+            &Uri::from_static("/api/users"),
+            body.as_bytes(),
+        );
+
+        assert!(result.starts_with("POST /api/users "));
+        assert!(result.contains(r#""username":"jake""#));
+        assert!(result.contains(r#""password":"[REDACTED]""#));
+        assert!(!result.contains("jakejake"));
+    }
+
+    #[test]
+    fn nested_password_fields_redacted() {
+        let body = r#"{"user":{"currentPassword":"old-secret","newPassword":"new-secret"}}"#;
+
+        let result = request_log_line(
+            &Method::PUT,
+            &Uri::from_static("/api/user"),
+            body.as_bytes(),
+        );
+
+        assert!(!result.contains("old-secret"));
+        assert!(!result.contains("new-secret"));
+    }
+
+    #[test]
+    fn non_json_body_omitted() {
+        let result = request_log_line(
+            &Method::POST,
+            &Uri::from_static("/api/users/login"),
+            b"password=secret",
+        );
+
+        assert_eq!(result, "POST /api/users/login [non-JSON body omitted]");
+    }
+
+    #[test]
+    fn empty_body() {
+        let result = request_log_line(&Method::GET, &Uri::from_static("/api/tags"), b"");
+
+        assert_eq!(result, "GET /api/tags");
+    }
+}
+
+#[cfg(test)]
+mod test_log_request_body_enabled {
+    use super::{log_request_body_enabled, APP_LOG_REQUEST_BODY};
+    use serial_test::serial;
+    use std::env;
+
+    #[test]
+    #[serial]
+    fn when_env_set() {
+        env::set_var(APP_LOG_REQUEST_BODY, "true");
+        assert!(log_request_body_enabled());
+        env::remove_var(APP_LOG_REQUEST_BODY);
+    }
+
+    #[test]
+    #[serial]
+    fn when_env_not_set() {
+        env::remove_var(APP_LOG_REQUEST_BODY);
+        assert!(!log_request_body_enabled());
+    }
+}
@@ -1 +1,6 @@
+pub mod admin;
 pub mod auth;
+pub mod lifecycle;
+pub mod logging;
+pub mod rate_limit;
+pub mod version;
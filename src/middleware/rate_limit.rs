@@ -0,0 +1,242 @@
+use crate::api::extract::Path;
+use crate::middleware::auth::Token;
+use axum::{
+    extract::Extension,
+    http::{header::RETRY_AFTER, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    env,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+const APP_COMMENT_RATE_LIMIT: &str = "APP_COMMENT_RATE_LIMIT";
+const COMMENT_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// In-memory limit of comments created by user on single article during time window.
+/// Every (user, article) pair has own bucket of recent comment times. Disabled when
+/// limit not configured, see APP_COMMENT_RATE_LIMIT.
+#[derive(Debug)]
+pub struct CommentRateLimiter {
+    limit: Option<usize>,
+    window: Duration,
+    hits: Mutex<HashMap<(Uuid, String), VecDeque<Instant>>>,
+}
+
+impl CommentRateLimiter {
+    /// Create limiter allowing `limit` comments per `window`, `None` disables limiting.
+    pub fn new(limit: Option<usize>, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            hits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create limiter allowing APP_COMMENT_RATE_LIMIT comments per minute.
+    pub fn from_env() -> Self {
+        Self::new(get_comment_rate_limit(), COMMENT_RATE_WINDOW)
+    }
+
+    /// Register comment of user on article (identified by slug) at time `now`.
+    /// Returns time to wait when limit already reached, comment is not counted then.
+    fn check(&self, user_id: Uuid, slug: &str, now: Instant) -> Result<(), Duration> {
+        let Some(limit) = self.limit else {
+            return Ok(());
+        };
+
+        let mut hits = self.hits.lock().unwrap();
+        // Forget comments outside of window, so buckets of idle users are dropped
+        hits.retain(|_, times| {
+            while times
+                .front()
+                .is_some_and(|&time| now.duration_since(time) >= self.window)
+            {
+                times.pop_front();
+            }
+            !times.is_empty()
+        });
+
+        let times = hits.entry((user_id, slug.to_owned())).or_default();
+        match times.front() {
+            Some(&oldest) if times.len() >= limit => Err(self.window - now.duration_since(oldest)),
+            _ => {
+                times.push_back(now);
+                Ok(())
+            }
+        }
+    }
+
+    /// Forget comment of user on article registered at time `now`, e.g. when comment
+    /// was rejected and should not count towards the limit.
+    fn release(&self, user_id: Uuid, slug: &str, now: Instant) {
+        let mut hits = self.hits.lock().unwrap();
+        let key = (user_id, slug.to_owned());
+        if let Some(times) = hits.get_mut(&key) {
+            if let Some(pos) = times.iter().rposition(|&time| time == now) {
+                times.remove(pos);
+            }
+            if times.is_empty() {
+                hits.remove(&key);
+            }
+        }
+    }
+}
+
+/// Reject comment with 429 status and `Retry-After` header (in seconds) when user
+/// exceeded comment rate limit for the article. Only successfully created comments are
+/// counted, slot reserved for request is released when it is not successful.
+pub async fn limit_comments<B>(
+    Extension(limiter): Extension<Arc<CommentRateLimiter>>,
+    Extension(token): Extension<Token>,
+    Path(slug): Path<String>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let now = Instant::now();
+    match limiter.check(token.id, &slug, now) {
+        Ok(()) => {
+            let response = next.run(request).await;
+            if !response.status().is_success() {
+                limiter.release(token.id, &slug, now);
+            }
+            response
+        }
+        Err(retry_after) => {
+            let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(RETRY_AFTER, seconds.to_string())],
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Return APP_COMMENT_RATE_LIMIT from environment varibles, limiting disabled
+/// when not set, not valid or zero
+fn get_comment_rate_limit() -> Option<usize> {
+    env::var(APP_COMMENT_RATE_LIMIT)
+        .ok()
+        .and_then(|val| val.trim().parse().ok())
+        .filter(|&limit| limit > 0)
+}
+
+#[cfg(test)]
+mod test_comment_rate_limiter {
+    use super::{
+        get_comment_rate_limit, limit_comments, CommentRateLimiter, APP_COMMENT_RATE_LIMIT,
+    };
+    use crate::middleware::auth::Token;
+    use axum::{
+        body::Body,
+        http::{header::RETRY_AFTER, Request, StatusCode},
+        middleware::from_fn,
+        routing::post,
+        Extension, Router,
+    };
+    use serial_test::serial;
+    use std::{
+        env,
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    /// Send comment request of user to article, returns response status and `Retry-After`.
+    async fn comment(app: &Router, user_id: Uuid, slug: &str) -> (StatusCode, Option<String>) {
+        let request = Request::post(format!("/api/articles/{slug}/comments"))
+            .extension(Token {
+                exp: 35,
+                id: user_id,
+            })
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .map(|value| value.to_str().unwrap().to_owned());
+        (response.status(), retry_after)
+    }
+
+    #[tokio::test]
+    async fn limit_per_user_and_article() {
+        let limiter = CommentRateLimiter::new(Some(2), Duration::from_secs(60));
+        let app = Router::new()
+            .route(
+                "/api/articles/:slug/comments",
+                post(|| async {}).layer(from_fn(limit_comments)),
+            )
+            .layer(Extension(Arc::new(limiter)));
+        let (user, other_user) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert_eq!(comment(&app, user, "title1").await.0, StatusCode::OK);
+        assert_eq!(comment(&app, user, "title1").await.0, StatusCode::OK);
+        let (status, retry_after) = comment(&app, user, "title1").await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(retry_after.as_deref(), Some("60"));
+
+        // Other article and other user not affected
+        assert_eq!(comment(&app, user, "title2").await.0, StatusCode::OK);
+        assert_eq!(comment(&app, other_user, "title1").await.0, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn rejected_comments_not_counted() {
+        let limiter = Arc::new(CommentRateLimiter::new(Some(1), Duration::from_secs(60)));
+        let app = Router::new()
+            .route(
+                "/api/articles/:slug/comments",
+                post(|| async { StatusCode::UNPROCESSABLE_ENTITY }).layer(from_fn(limit_comments)),
+            )
+            .layer(Extension(limiter.clone()));
+        let user = Uuid::new_v4();
+
+        for _ in 0..3 {
+            let (status, _) = comment(&app, user, "title1").await;
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        }
+        assert!(limiter.check(user, "title1", Instant::now()).is_ok());
+    }
+
+    #[test]
+    fn allowed_again_after_window() {
+        let limiter = CommentRateLimiter::new(Some(1), Duration::from_secs(60));
+        let user = Uuid::new_v4();
+        let start = Instant::now();
+
+        assert!(limiter.check(user, "title1", start).is_ok());
+        let retry_after = limiter.check(user, "title1", start + Duration::from_secs(20));
+        assert_eq!(retry_after, Err(Duration::from_secs(40)));
+        assert!(limiter
+            .check(user, "title1", start + Duration::from_secs(60))
+            .is_ok());
+    }
+
+    #[test]
+    fn disabled_without_limit() {
+        let limiter = CommentRateLimiter::new(None, Duration::from_secs(60));
+        let user = Uuid::new_v4();
+        let now = Instant::now();
+
+        assert!((0..100).all(|_| limiter.check(user, "title1", now).is_ok()));
+    }
+
+    #[test]
+    #[serial]
+    fn limit_from_env() {
+        env::remove_var(APP_COMMENT_RATE_LIMIT);
+        assert_eq!(get_comment_rate_limit(), None);
+        env::set_var(APP_COMMENT_RATE_LIMIT, "0");
+        assert_eq!(get_comment_rate_limit(), None);
+        env::set_var(APP_COMMENT_RATE_LIMIT, "5");
+        assert_eq!(get_comment_rate_limit(), Some(5));
+        env::remove_var(APP_COMMENT_RATE_LIMIT);
+    }
+}
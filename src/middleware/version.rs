@@ -0,0 +1,128 @@
+use axum::{
+    http::{header::ACCEPT, HeaderMap, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+
+pub const API_VERSION_HEADER: &str = "x-api-version";
+pub const DEPRECATION_HEADER: &str = "x-deprecation";
+const VENDOR_MEDIA_TYPE_PREFIX: &str = "application/vnd.conduit.v";
+const DEPRECATION_NOTICE: &str = "API v1 is deprecated, request v2 with X-API-Version header";
+
+/// Version of api requested by client. Defaults to `V1` when not specified
+/// or not recognized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ApiVersion {
+    #[default]
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    /// Determine requested version from `X-API-Version` header, falling back to
+    /// vendor media type of `Accept` header (e.g. `application/vnd.conduit.v2+json`).
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let from_version_header = headers
+            .get(API_VERSION_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().trim_start_matches(['v', 'V']).to_owned());
+        let from_accept_header = || {
+            headers
+                .get(ACCEPT)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| {
+                    value.split(',').find_map(|media_type| {
+                        media_type
+                            .trim()
+                            .strip_prefix(VENDOR_MEDIA_TYPE_PREFIX)
+                            .and_then(|rest| rest.split('+').next())
+                            .map(str::to_owned)
+                    })
+                })
+        };
+
+        match from_version_header.or_else(from_accept_header).as_deref() {
+            Some("2") => ApiVersion::V2,
+            _ => ApiVersion::V1,
+        }
+    }
+}
+
+/// Store requested `ApiVersion` in request extensions for versioned handlers.
+/// Responses to v1 requests are marked with `X-Deprecation` header.
+pub async fn api_version<B>(mut request: Request<B>, next: Next<B>) -> Response {
+    let version = ApiVersion::from_headers(request.headers());
+    request.extensions_mut().insert(version);
+
+    let mut response = next.run(request).await;
+    if version == ApiVersion::V1 {
+        response.headers_mut().insert(
+            DEPRECATION_HEADER,
+            HeaderValue::from_static(DEPRECATION_NOTICE),
+        );
+    }
+    response
+}
+
+#[cfg(test)]
+mod test_api_version {
+    use super::*;
+    use axum::{body::Body, middleware::from_fn, routing::get, Extension, Router};
+    use tower::ServiceExt;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|&(name, value)| (name.parse().unwrap(), HeaderValue::from_static(value)))
+            .collect()
+    }
+
+    #[test]
+    fn version_from_headers() {
+        assert_eq!(ApiVersion::from_headers(&headers(&[])), ApiVersion::V1);
+        assert_eq!(
+            ApiVersion::from_headers(&headers(&[(API_VERSION_HEADER, "2")])),
+            ApiVersion::V2
+        );
+        assert_eq!(
+            ApiVersion::from_headers(&headers(&[(API_VERSION_HEADER, "v1")])),
+            ApiVersion::V1
+        );
+        assert_eq!(
+            ApiVersion::from_headers(&headers(&[(
+                "accept",
+                "text/html, application/vnd.conduit.v2+json"
+            )])),
+            ApiVersion::V2
+        );
+        assert_eq!(
+            ApiVersion::from_headers(&headers(&[("accept", "application/json")])),
+            ApiVersion::V1
+        );
+    }
+
+    #[tokio::test]
+    async fn deprecation_header_on_v1_only() {
+        let app =
+            Router::new()
+                .route(
+                    "/",
+                    get(|Extension(version): Extension<ApiVersion>| async move {
+                        format!("{version:?}")
+                    }),
+                )
+                .layer(from_fn(api_version));
+
+        let request = Request::builder().uri("/").body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert!(response.headers().contains_key(DEPRECATION_HEADER));
+
+        let request = Request::builder()
+            .uri("/")
+            .header(API_VERSION_HEADER, "2")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert!(!response.headers().contains_key(DEPRECATION_HEADER));
+    }
+}
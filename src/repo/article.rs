@@ -1,158 +1,594 @@
-use super::user::{author_followed_by_current_user, Profile};
+use super::{
+    article_tag::get_article_tags_for_ids,
+    block::author_not_blocked,
+    follower::get_followed_user_ids,
+    tag::tag_name_in,
+    time::serialize_utc,
+    user::{author_followed_by_current_user, get_user_by_id, Profile},
+    user_cache::UserCache,
+};
+use chrono::{Months, NaiveDate, Utc};
 use entity::entities::{
     article, article_tag, favorited_article,
-    prelude::{Article, ArticleTag, FavoritedArticle, Tag},
-    tag, user,
+    prelude::{Article, ArticleTag, FavoritedArticle},
+    user,
 };
-use migration::{Alias, SimpleExpr};
+use migration::{Alias, Func, LikeExpr, SimpleExpr};
 use sea_orm::{
-    entity::prelude::DateTime, prelude::Expr, query::*, ColumnTrait, DatabaseConnection, DbErr,
-    DeleteResult, EntityTrait, FromQueryResult, ModelTrait, QueryFilter, RelationTrait,
+    entity::prelude::DateTime,
+    prelude::Expr,
+    query::*,
+    ActiveValue::{NotSet, Set, Unchanged},
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, DeleteResult, EntityTrait,
+    FromQueryResult, QueryFilter, RelationTrait,
 };
 use serde::Serialize;
-use std::vec;
+use slug::slugify;
+use std::{collections::HashSet, str::FromStr, vec};
 use uuid::Uuid;
 
-const DEFAULT_PAGE_LIMIT: u64 = 20;
 const DEFAULT_PAGE_OFFSET: u64 = 0;
 
 /// Fetch `articles` with additional info (see ArticleWithAuthor for details). Optional parameters
-/// used for filter records by tag name, author name, user who liked aticle. Limit response by
-/// limit and offset parameters. Ordered by most recent first.
-/// Returns vec of `articles` on success, otherwise returns an `database error`.
-pub async fn get_articles_with_filters(
+/// used for filter records by tag names, author name, user who liked aticle and text searched
+/// in title, description or body (case-insensitive). Tag names matched
+/// according to `tag_match` (see TagMatch for details). Limit response by
+/// limit and offset parameters. Ordered according to `sort` (see ArticleSort for details).
+/// Articles of authors blocked by current user are excluded.
+/// Total number of matching articles is counted by window function of the same query. Separate
+/// count query made only for page past the end, as it has no rows to carry the total.
+/// Returns `Page` of `articles` on success, otherwise returns an `database error`.
+#[allow(clippy::too_many_arguments)]
+pub async fn get_articles_page(
     db: &DatabaseConnection,
-    tag_name: Option<&String>,
+    tag_names: &[String],
+    tag_match: TagMatch,
     author_name: Option<&String>,
     user_who_liked_it: Option<&String>,
-    limit: Option<u64>,
+    search: Option<&String>,
+    sort: ArticleSort,
+    limit: u64,
     offset: Option<u64>,
     current_user_id: Option<Uuid>,
+) -> Result<Page<ArticleWithAuthor>, DbErr> {
+    let offset = offset.unwrap_or(DEFAULT_PAGE_OFFSET);
+
+    let rows = extended_articles_query(current_user_id)
+        .filter(articles_filter(
+            tag_names,
+            tag_match,
+            author_name,
+            user_who_liked_it,
+            search,
+            current_user_id,
+        ))
+        // Evaluated after grouping, thus counts articles matching filters
+        .column_as(Expr::cust("COUNT(*) OVER ()"), "total")
+        .limit(limit)
+        .offset(offset)
+        .order_by_desc(sort.column())
+        .into_model::<ModelWithTotal>()
+        .all(db)
+        .await?;
+
+    let total = match rows.first() {
+        Some(row) => row.total as u64,
+        None if offset == 0 => 0,
+        None => {
+            get_articles_count(
+                db,
+                tag_names,
+                tag_match,
+                author_name,
+                user_who_liked_it,
+                search,
+                current_user_id,
+            )
+            .await?
+        }
+    };
+
+    let art_extended: Vec<ModelExtended> = rows.into_iter().map(|row| row.model).collect();
+    let art_extended = resolve_following(db, art_extended, current_user_id).await?;
+
+    let items = attach_tags(db, art_extended).await?;
+
+    Ok(Page {
+        items,
+        total,
+        limit,
+        offset,
+    })
+}
+
+/// Fetch `articles` created by followed users. Optional tag names used for filter records,
+/// matched according to `tag_match` (see TagMatch for details). Limit response by limit and
+/// offset parameters. Ordered by most recent first.
+/// Returns vec of `articles` on success, otherwise returns an `database error`.
+pub async fn get_articles_feed(
+    db: &DatabaseConnection,
+    tag_names: &[String],
+    tag_match: TagMatch,
+    limit: u64,
+    offset: Option<u64>,
+    current_user_id: Uuid,
 ) -> Result<Vec<ArticleWithAuthor>, DbErr> {
-    let art_extended = Article::find()
-        .join(JoinType::LeftJoin, article::Relation::User.def())
-        .column(user::Column::Username)
-        .column(user::Column::Bio)
-        .column(user::Column::Image)
-        .filter(article_author(author_name))
-        .filter(article_has_tag(tag_name))
-        .filter(article_liked_by_user(user_who_liked_it))
-        .column_as(
-            author_followed_by_current_user(current_user_id),
-            "following",
-        )
-        .column_as(article_liked_by_current_user(current_user_id), "favorited")
-        .join(
-            JoinType::LeftJoin,
-            favorited_article::Relation::Article.def().rev(),
-        )
-        .column_as(article_favorites_count(), "favorites_count")
-        .group_by(favorited_article::Column::ArticleId)
-        .group_by(article::Column::Id)
-        .group_by(user::Column::Username)
-        .group_by(user::Column::Id)
-        .limit(limit.or(Some(DEFAULT_PAGE_LIMIT)))
+    let mut art_extended = extended_articles_query(Some(current_user_id))
+        .filter(feed_filter(tag_names, tag_match, current_user_id))
+        .limit(limit)
         .offset(offset.or(Some(DEFAULT_PAGE_OFFSET)))
         .order_by_desc(article::Column::UpdatedAt)
         .into_model::<ModelExtended>()
         .all(db)
         .await?;
+    // Feed contains only articles of followed authors
+    art_extended
+        .iter_mut()
+        .for_each(|art| art.author.following = true);
 
-    let art_models: Vec<article::Model> = art_extended
-        .clone()
-        .into_iter()
-        .map(|mde| mde.into())
-        .collect();
+    let res = attach_tags(db, art_extended).await?;
 
-    let tags = art_models.load_many_to_many(Tag, ArticleTag, db).await?;
+    Ok(res)
+}
 
-    let res: Vec<ArticleWithAuthor> = art_extended
-        .into_iter()
-        .zip(tags.into_iter())
-        .map(|inf| inf.into())
-        .collect();
+/// Fetch unpublished `articles` (drafts) of provided author. Limit response by limit and offset
+/// parameters. Ordered by most recent first.
+/// Returns vec of `articles` on success, otherwise returns an `database error`.
+pub async fn get_drafts(
+    db: &DatabaseConnection,
+    author_id: Uuid,
+    limit: u64,
+    offset: Option<u64>,
+) -> Result<Vec<ArticleWithAuthor>, DbErr> {
+    get_author_articles(db, author_id, Some(false), limit, offset).await
+}
 
-    Ok(res)
+/// Fetch all `articles` of provided author, both published and drafts. Limit response by
+/// limit and offset parameters. Ordered by most recent first.
+/// Returns vec of `articles` on success, otherwise returns an `database error`.
+pub async fn get_own_articles(
+    db: &DatabaseConnection,
+    author_id: Uuid,
+    limit: u64,
+    offset: Option<u64>,
+) -> Result<Vec<ArticleWithAuthor>, DbErr> {
+    get_author_articles(db, author_id, None, limit, offset).await
 }
 
-/// Fetch `articles` created by followed users. Limit response by limit and offset parameters.
-/// Ordered by most recent first. Returns vec of `articles` on success, otherwise returns an `database error`.
-pub async fn get_articles_feed(
+/// Fetch `articles` of provided author as seen by the author, optionally filtered by
+/// `published` flag. Shared by drafts and own articles listings.
+async fn get_author_articles(
     db: &DatabaseConnection,
-    limit: Option<u64>,
+    author_id: Uuid,
+    published: Option<bool>,
+    limit: u64,
     offset: Option<u64>,
-    current_user_id: Uuid,
 ) -> Result<Vec<ArticleWithAuthor>, DbErr> {
-    let art_extended = Article::find()
-        .join(JoinType::LeftJoin, article::Relation::User.def())
-        .column(user::Column::Username)
-        .column(user::Column::Bio)
-        .column(user::Column::Image)
-        .filter(author_followed_by_current_user(Some(current_user_id)))
-        .column_as(Expr::val(true), "following")
-        .column_as(
-            article_liked_by_current_user(Some(current_user_id)),
-            "favorited",
-        )
+    let art_extended = extended_articles_query(Some(author_id))
+        .filter(article::Column::AuthorId.eq(author_id))
+        .filter(article_not_deleted())
+        .apply_if(published, |query, published| {
+            query.filter(article::Column::Published.eq(published))
+        })
+        .limit(limit)
+        .offset(offset.or(Some(DEFAULT_PAGE_OFFSET)))
+        .order_by_desc(article::Column::UpdatedAt)
+        .into_model::<ModelExtended>()
+        .all(db)
+        .await?;
+    let art_extended = resolve_following(db, art_extended, Some(author_id)).await?;
+
+    let res = attach_tags(db, art_extended).await?;
+
+    Ok(res)
+}
+
+/// Fetch `articles` favorited since provided time with additional info (see ArticleWithAuthor
+/// for details). Ranked by favorites gained since that time, most recently updated first on ties.
+/// Limit response by limit parameter. Optional identifier used to determine whether the logged
+/// in user is a follower of the author and whether the article is liked by that user.
+/// Articles of authors blocked by current user are excluded.
+/// Returns vec of `articles` on success, otherwise returns an `database error`.
+pub async fn get_trending_articles(
+    db: &DatabaseConnection,
+    since: DateTime,
+    limit: u64,
+    current_user_id: Option<Uuid>,
+) -> Result<Vec<ArticleWithAuthor>, DbErr> {
+    let recent_favorites = article_favorites_count_since(since);
+
+    let art_extended = extended_articles_query(current_user_id)
+        .filter(article_visible_to_current_user(current_user_id))
+        .filter(author_not_blocked(
+            current_user_id,
+            article::Column::AuthorId,
+        ))
+        .having(Expr::expr(recent_favorites.clone()).gt(0))
+        .limit(limit)
+        .order_by(recent_favorites, Order::Desc)
+        .order_by_desc(article::Column::UpdatedAt)
+        .into_model::<ModelExtended>()
+        .all(db)
+        .await?;
+    let art_extended = resolve_following(db, art_extended, current_user_id).await?;
+
+    let res = attach_tags(db, art_extended).await?;
+
+    Ok(res)
+}
+
+/// Fetch `articles` tagged with provided tag name with additional info (see ArticleWithAuthor
+/// for details). Ordered by time the tag was added to article, most recent first. Limit response
+/// by limit parameter. Optional identifier used to determine whether the logged in user is
+/// a follower of the author and whether the article is liked by that user.
+/// Articles of authors blocked by current user are excluded.
+/// Returns vec of `articles` on success, otherwise returns an `database error`.
+pub async fn get_recently_tagged_articles(
+    db: &DatabaseConnection,
+    tag_name: &str,
+    limit: u64,
+    current_user_id: Option<Uuid>,
+) -> Result<Vec<ArticleWithAuthor>, DbErr> {
+    let art_extended = extended_articles_query(current_user_id)
         .join(
-            JoinType::LeftJoin,
-            favorited_article::Relation::Article.def().rev(),
+            JoinType::InnerJoin,
+            article_tag::Relation::Article.def().rev(),
         )
-        .column_as(article_favorites_count(), "favorites_count")
-        .group_by(favorited_article::Column::ArticleId)
-        .group_by(article::Column::Id)
-        .group_by(user::Column::Username)
-        .group_by(user::Column::Id)
-        .limit(limit.or(Some(DEFAULT_PAGE_LIMIT)))
+        .join(JoinType::InnerJoin, article_tag::Relation::Tag.def())
+        .filter(tag_name_in([tag_name.to_owned()]))
+        .filter(article_visible_to_current_user(current_user_id))
+        .filter(author_not_blocked(
+            current_user_id,
+            article::Column::AuthorId,
+        ))
+        .limit(limit)
+        .order_by(article_tagged_at(), Order::Desc)
+        .into_model::<ModelExtended>()
+        .all(db)
+        .await?;
+    let art_extended = resolve_following(db, art_extended, current_user_id).await?;
+
+    let res = attach_tags(db, art_extended).await?;
+
+    Ok(res)
+}
+
+/// Fetch `articles` created within provided month of year with additional info (see
+/// ArticleWithAuthor for details). Ordered by time of creation, most recent first. Limit response
+/// by limit and offset parameters. Optional identifier used to determine whether the logged in
+/// user is a follower of the author and whether the article is liked by that user.
+/// Returns vec of `articles` on success (empty for not existing month), otherwise returns an
+/// `database error`.
+pub async fn get_articles_by_month(
+    db: &DatabaseConnection,
+    year: i32,
+    month: u32,
+    limit: u64,
+    offset: Option<u64>,
+    current_user_id: Option<Uuid>,
+) -> Result<Vec<ArticleWithAuthor>, DbErr> {
+    let Some((start, end)) = month_bounds(year, month) else {
+        return Ok(vec![]);
+    };
+
+    let art_extended = extended_articles_query(current_user_id)
+        .filter(article::Column::CreatedAt.gte(start))
+        .filter(article::Column::CreatedAt.lt(end))
+        .filter(article_visible_to_current_user(current_user_id))
+        .filter(author_not_blocked(
+            current_user_id,
+            article::Column::AuthorId,
+        ))
+        .limit(limit)
         .offset(offset.or(Some(DEFAULT_PAGE_OFFSET)))
+        .order_by_desc(article::Column::CreatedAt)
+        .into_model::<ModelExtended>()
+        .all(db)
+        .await?;
+    let art_extended = resolve_following(db, art_extended, current_user_id).await?;
+
+    let res = attach_tags(db, art_extended).await?;
+
+    Ok(res)
+}
+
+/// Fetch `articles` sharing tags with article of provided id with additional info (see
+/// ArticleWithAuthor for details). Article itself excluded. Ranked by number of shared tags,
+/// most recently updated first on ties. Limit response by limit parameter. Optional identifier
+/// used to determine whether the logged in user is a follower of the author and whether
+/// the article is liked by that user.
+/// Articles of authors blocked by current user are excluded.
+/// Returns vec of `articles` on success, otherwise returns an `database error`.
+pub async fn get_related_articles(
+    db: &DatabaseConnection,
+    article_id: Uuid,
+    limit: u64,
+    current_user_id: Option<Uuid>,
+) -> Result<Vec<ArticleWithAuthor>, DbErr> {
+    let shared_tags = article_shared_tags_count(article_id);
+
+    let art_extended = extended_articles_query(current_user_id)
+        .filter(article::Column::Id.ne(article_id))
+        .filter(Expr::expr(shared_tags.clone()).gt(0))
+        .filter(article_visible_to_current_user(current_user_id))
+        .filter(author_not_blocked(
+            current_user_id,
+            article::Column::AuthorId,
+        ))
+        .limit(limit)
+        .order_by(shared_tags, Order::Desc)
         .order_by_desc(article::Column::UpdatedAt)
         .into_model::<ModelExtended>()
         .all(db)
         .await?;
+    let art_extended = resolve_following(db, art_extended, current_user_id).await?;
 
-    let art_models: Vec<article::Model> = art_extended
-        .clone()
-        .into_iter()
-        .map(|mde| mde.into())
-        .collect();
+    let res = attach_tags(db, art_extended).await?;
 
-    let tags = art_models.load_many_to_many(Tag, ArticleTag, db).await?;
+    Ok(res)
+}
 
-    let res: Vec<ArticleWithAuthor> = art_extended
-        .into_iter()
-        .zip(tags.into_iter())
-        .map(|inf| inf.into())
-        .collect();
+/// Fetch `articles` with additional info (see ArticleWithAuthor for details) for the provided
+/// `slugs` in single query. Missing slugs (and drafts of other authors) are skipped, found
+/// articles returned in order of requested slugs. Optional identifier used to determine whether
+/// the logged in user is a follower of the author and whether the article is liked by that user.
+/// Articles of authors blocked by current user are excluded.
+/// Returns vec of `articles` on success, otherwise returns an `database error`.
+pub async fn get_articles_by_slugs(
+    db: &DatabaseConnection,
+    slugs: &[String],
+    current_user_id: Option<Uuid>,
+) -> Result<Vec<ArticleWithAuthor>, DbErr> {
+    if slugs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut art_extended = extended_articles_query(current_user_id)
+        .filter(article::Column::Slug.is_in(slugs))
+        .filter(article_visible_to_current_user(current_user_id))
+        .filter(author_not_blocked(
+            current_user_id,
+            article::Column::AuthorId,
+        ))
+        .into_model::<ModelExtended>()
+        .all(db)
+        .await?;
+    art_extended.sort_by_key(|art| slugs.iter().position(|slug| *slug == art.slug));
+    let art_extended = resolve_following(db, art_extended, current_user_id).await?;
+
+    let res = attach_tags(db, art_extended).await?;
 
     Ok(res)
 }
 
+/// Fetch `articles` selected the same way as `get_articles_page`, ordered by time of
+/// update and id (most recent first) and located strictly after provided `cursor` (time of
+/// update and id of last article of previous page), first page fetched without cursor. Unlike
+/// offset, cursor is not shifted by articles inserted concurrently.
+/// Returns `CursorPage` of `articles` on success, otherwise returns an `database error`.
+#[allow(clippy::too_many_arguments)]
+pub async fn get_articles_after(
+    db: &DatabaseConnection,
+    tag_names: &[String],
+    tag_match: TagMatch,
+    author_name: Option<&String>,
+    user_who_liked_it: Option<&String>,
+    search: Option<&String>,
+    cursor: Option<(DateTime, Uuid)>,
+    limit: u64,
+    current_user_id: Option<Uuid>,
+) -> Result<CursorPage<ArticleWithAuthor>, DbErr> {
+    let art_extended = extended_articles_query(current_user_id)
+        .filter(articles_filter(
+            tag_names,
+            tag_match,
+            author_name,
+            user_who_liked_it,
+            search,
+            current_user_id,
+        ))
+        .filter(article_after(cursor))
+        .limit(limit)
+        .order_by_desc(article::Column::UpdatedAt)
+        .order_by_desc(article::Column::Id)
+        .into_model::<ModelExtended>()
+        .all(db)
+        .await?;
+
+    let art_extended = resolve_following(db, art_extended, current_user_id).await?;
+
+    // Only full page could be followed by other articles
+    let next_cursor = match art_extended.len() as u64 == limit {
+        true => art_extended
+            .last()
+            .and_then(|art| art.updated_at.map(|time| (time, art.id))),
+        false => None,
+    };
+
+    let items = attach_tags(db, art_extended).await?;
+
+    Ok(CursorPage { items, next_cursor })
+}
+
 /// Count `articles` with additional info (see ArticleWithAuthor for details). Optional parameters used
-/// for filter records by tag name, author name, user who liked aticle. Useful for limit/offset pagination.
+/// for filter records by tag names, author name, user who liked aticle. Filtered the same way as
+/// `get_articles_page`, thus drafts counted only for their author and articles of authors
+/// blocked by current user are excluded. Useful for limit/offset pagination.
+/// Count is not affected by limit and offset, e.g. for 5 matching articles `get_articles_page`
+/// with limit 2 returns 2 articles, while `get_articles_count` returns 5.
 /// Returns quantity of `articles` on success, otherwise returns an `database error`.
 pub async fn get_articles_count(
     db: &DatabaseConnection,
-    tag_name: Option<&String>,
+    tag_names: &[String],
+    tag_match: TagMatch,
     author_name: Option<&String>,
     user_who_liked_it: Option<&String>,
+    search: Option<&String>,
     current_user_id: Option<Uuid>,
 ) -> Result<u64, DbErr> {
     Article::find()
         .join(JoinType::LeftJoin, article::Relation::User.def())
-        .filter(article_author(author_name))
-        .filter(article_has_tag(tag_name))
-        .filter(article_liked_by_user(user_who_liked_it))
-        .filter(if current_user_id.is_some() {
-            author_followed_by_current_user(current_user_id)
-        } else {
-            true.into()
+        .filter(articles_filter(
+            tag_names,
+            tag_match,
+            author_name,
+            user_who_liked_it,
+            search,
+            current_user_id,
+        ))
+        .count(db)
+        .await
+}
+
+/// Count `articles` of feed (see get_articles_feed for details) with the same tag filter.
+/// Count is not affected by limit and offset.
+/// Returns quantity of `articles` on success, otherwise returns an `database error`.
+pub async fn get_feed_count(
+    db: &DatabaseConnection,
+    tag_names: &[String],
+    tag_match: TagMatch,
+    current_user_id: Uuid,
+) -> Result<u64, DbErr> {
+    Article::find()
+        .join(JoinType::LeftJoin, article::Relation::User.def())
+        .filter(feed_filter(tag_names, tag_match, current_user_id))
+        .count(db)
+        .await
+}
+
+/// Count unpublished `articles` (drafts) of provided author (see get_drafts for details).
+/// Count is not affected by limit and offset.
+/// Returns quantity of `articles` on success, otherwise returns an `database error`.
+pub async fn get_drafts_count(db: &DatabaseConnection, author_id: Uuid) -> Result<u64, DbErr> {
+    count_author_articles(db, author_id, Some(false)).await
+}
+
+/// Count all `articles` of provided author, both published and drafts (see get_own_articles
+/// for details). Count is not affected by limit and offset.
+/// Returns quantity of `articles` on success, otherwise returns an `database error`.
+pub async fn get_own_articles_count(
+    db: &DatabaseConnection,
+    author_id: Uuid,
+) -> Result<u64, DbErr> {
+    count_author_articles(db, author_id, None).await
+}
+
+/// Count `articles` of provided author, optionally filtered by `published` flag.
+async fn count_author_articles(
+    db: &DatabaseConnection,
+    author_id: Uuid,
+    published: Option<bool>,
+) -> Result<u64, DbErr> {
+    Article::find()
+        .filter(article::Column::AuthorId.eq(author_id))
+        .filter(article_not_deleted())
+        .apply_if(published, |query, published| {
+            query.filter(article::Column::Published.eq(published))
         })
         .count(db)
         .await
 }
 
+/// Count `articles` favorited since provided time (see get_trending_articles for details).
+/// Count is not affected by limit.
+/// Returns quantity of `articles` on success, otherwise returns an `database error`.
+pub async fn get_trending_count(
+    db: &DatabaseConnection,
+    since: DateTime,
+    current_user_id: Option<Uuid>,
+) -> Result<u64, DbErr> {
+    let favorited_since = FavoritedArticle::find()
+        .select_only()
+        .column(favorited_article::Column::ArticleId)
+        .filter(favorited_article::Column::CreatedAt.gte(since))
+        .into_query();
+
+    Article::find()
+        .filter(article::Column::Id.in_subquery(favorited_since))
+        .filter(article_visible_to_current_user(current_user_id))
+        .filter(author_not_blocked(
+            current_user_id,
+            article::Column::AuthorId,
+        ))
+        .count(db)
+        .await
+}
+
+/// Count `articles` tagged with provided tag name (see get_recently_tagged_articles for
+/// details). Count is not affected by limit.
+/// Returns quantity of `articles` on success, otherwise returns an `database error`.
+pub async fn get_recently_tagged_count(
+    db: &DatabaseConnection,
+    tag_name: &str,
+    current_user_id: Option<Uuid>,
+) -> Result<u64, DbErr> {
+    let tagged = ArticleTag::find()
+        .select_only()
+        .column(article_tag::Column::ArticleId)
+        .join(JoinType::InnerJoin, article_tag::Relation::Tag.def())
+        .filter(tag_name_in([tag_name.to_owned()]))
+        .into_query();
+
+    Article::find()
+        .filter(article::Column::Id.in_subquery(tagged))
+        .filter(article_visible_to_current_user(current_user_id))
+        .filter(author_not_blocked(
+            current_user_id,
+            article::Column::AuthorId,
+        ))
+        .count(db)
+        .await
+}
+
+/// Count `articles` created within provided month of year (see get_articles_by_month for
+/// details). Count is not affected by limit and offset.
+/// Returns quantity of `articles` on success (0 for not existing month), otherwise returns an
+/// `database error`.
+pub async fn get_articles_by_month_count(
+    db: &DatabaseConnection,
+    year: i32,
+    month: u32,
+    current_user_id: Option<Uuid>,
+) -> Result<u64, DbErr> {
+    let Some((start, end)) = month_bounds(year, month) else {
+        return Ok(0);
+    };
+
+    Article::find()
+        .filter(article::Column::CreatedAt.gte(start))
+        .filter(article::Column::CreatedAt.lt(end))
+        .filter(article_visible_to_current_user(current_user_id))
+        .filter(author_not_blocked(
+            current_user_id,
+            article::Column::AuthorId,
+        ))
+        .count(db)
+        .await
+}
+
+/// Count `articles` sharing tags with article of provided id (see get_related_articles for
+/// details). Count is not affected by limit.
+/// Returns quantity of `articles` on success, otherwise returns an `database error`.
+pub async fn get_related_count(
+    db: &DatabaseConnection,
+    article_id: Uuid,
+    current_user_id: Option<Uuid>,
+) -> Result<u64, DbErr> {
+    Article::find()
+        .filter(article::Column::Id.ne(article_id))
+        .filter(Expr::expr(article_shared_tags_count(article_id)).gt(0))
+        .filter(article_visible_to_current_user(current_user_id))
+        .filter(author_not_blocked(
+            current_user_id,
+            article::Column::AuthorId,
+        ))
+        .count(db)
+        .await
+}
+
 /// Fetch `article` with additional info (see ArticleWithAuthor for details) for the provided `slug`.
 /// Optional identifier used to determine whether the logged in user is a follower of the profile.
+/// Drafts are found only for their author.
 /// Returns optional `article` on success, otherwise returns an `database error`.
 pub async fn get_article_by_slug(
     db: &DatabaseConnection,
@@ -161,6 +597,7 @@ pub async fn get_article_by_slug(
 ) -> Result<Option<ArticleWithAuthor>, DbErr> {
     let art_extended = Article::find()
         .filter(article::Column::Slug.eq(slug))
+        .filter(article_visible_to_current_user(current_user_id))
         .join(JoinType::LeftJoin, article::Relation::User.def())
         .column(user::Column::Username)
         .column(user::Column::Bio)
@@ -183,19 +620,13 @@ pub async fn get_article_by_slug(
         .one(db)
         .await?;
 
-    if art_extended.is_none() {
-        return Ok(None);
-    }
-
-    let model: article::Model = art_extended.clone().unwrap().into();
-    let tags = model.find_related(Tag).all(db).await?;
-    let res: ArticleWithAuthor = (art_extended.unwrap(), tags).into();
-
-    Ok(Some(res))
+    let mut res = attach_tags(db, art_extended.into_iter().collect()).await?;
+    Ok(res.pop())
 }
 
 /// Fetch `article` with additional info (see ArticleWithAuthor for details) for the provided `id`.
 /// Optional identifier used to determine whether the logged in user is a follower of the profile.
+/// Drafts are found only for their author.
 /// Returns optional `article` on success, otherwise returns an `database error`.
 pub async fn get_article_by_id(
     db: &DatabaseConnection,
@@ -203,6 +634,7 @@ pub async fn get_article_by_id(
     current_user_id: Option<Uuid>,
 ) -> Result<Option<ArticleWithAuthor>, DbErr> {
     let art_extended = Article::find_by_id(id)
+        .filter(article_visible_to_current_user(current_user_id))
         .join(JoinType::LeftJoin, article::Relation::User.def())
         .column(user::Column::Username)
         .column(user::Column::Bio)
@@ -225,62 +657,235 @@ pub async fn get_article_by_id(
         .one(db)
         .await?;
 
-    if art_extended.is_none() {
-        return Ok(None);
-    }
-
-    let model: article::Model = art_extended.clone().unwrap().into();
-    let tags = model.find_related(Tag).all(db).await?;
-    let res: ArticleWithAuthor = (art_extended.unwrap(), tags).into();
-
-    Ok(Some(res))
+    let mut res = attach_tags(db, art_extended.into_iter().collect()).await?;
+    Ok(res.pop())
 }
 
-/// Fetch `article` for the provided `slug`.
+/// Fetch `article` for the provided `slug`. Optional identifier of logged in user used
+/// to find drafts, only their author allowed to see them.
 /// Returns optional `article` on success, otherwise returns an `database error`.
 pub async fn get_article_model_by_slug(
     db: &DatabaseConnection,
     slug: &str,
+    current_user_id: Option<Uuid>,
 ) -> Result<Option<article::Model>, DbErr> {
     Article::find()
         .filter(article::Column::Slug.eq(slug))
+        .filter(article_visible_to_current_user(current_user_id))
         .one(db)
         .await
 }
 
-/// Insert `article` for the provided `ActiveModel`. Reject models with existing slug.
-/// Returns `InsertResult` with last inserted id on success, otherwise
-/// returns an `database error`.
-/// Empty slug(or title, or description, or body), produces error as not allowed on database level.
-/// See [`InsertResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.InsertResult.html)
-/// documentation for more details.
-pub async fn create_article(
+/// Check whether `user` with provided `user_id` is the author of `article` identified by
+/// `slug_or_id` (article id in textual form or article slug). Drafts of other authors are
+/// treated as not existing.
+/// Returns optional result of check, `None` if article not exist, otherwise returns an `database error`.
+#[allow(dead_code)]
+pub async fn is_article_author(
     db: &DatabaseConnection,
-    article: article::ActiveModel,
-) -> Result<InsertResult<article::ActiveModel>, DbErr> {
-    Article::insert(article).exec(db).await
+    slug_or_id: &str,
+    user_id: Uuid,
+) -> Result<Option<bool>, DbErr> {
+    let article_filter = match Uuid::parse_str(slug_or_id) {
+        Ok(id) => article::Column::Id.eq(id),
+        Err(_) => article::Column::Slug.eq(slug_or_id),
+    };
+
+    let author_id = Article::find()
+        .filter(article_filter)
+        .filter(article_visible_to_current_user(Some(user_id)))
+        .select_only()
+        .column(article::Column::AuthorId)
+        .into_tuple::<Uuid>()
+        .one(db)
+        .await?;
+
+    Ok(author_id.map(|id| id == user_id))
 }
 
-/// Update `article` for the provided `ActiveModel`.
-/// Returns `article` on success, otherwise returns an `database error`.
-/// Reject models with non existing username or email.
-pub async fn update_article(
+/// Build slug from provided `title`, not used by other articles: slugified title, followed by
+/// `-2`, `-3`, ... suffix when already taken. Article with `except_id` (e.g. article being renamed)
+/// is ignored, so it may keep its own slug.
+/// Returns unique slug on success, otherwise returns an `database error`.
+pub async fn generate_unique_slug(
     db: &DatabaseConnection,
-    article: article::ActiveModel,
-) -> Result<article::Model, DbErr> {
-    Article::update(article).exec(db).await
+    title: &str,
+    except_id: Option<Uuid>,
+) -> Result<String, DbErr> {
+    let base = match slugify(title) {
+        base if base.is_empty() => "article".to_owned(),
+        base => base,
+    };
+
+    // Slugified text contains only alphanumerics and dashes, thus safe for like pattern
+    let taken: HashSet<String> = Article::find()
+        .select_only()
+        .column(article::Column::Slug)
+        .filter(
+            Condition::any()
+                .add(article::Column::Slug.eq(&base))
+                .add(article::Column::Slug.like(format!("{base}-%"))),
+        )
+        .apply_if(except_id, |query, id| {
+            query.filter(article::Column::Id.ne(id))
+        })
+        .into_tuple::<String>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    let slug = std::iter::once(base.clone())
+        .chain((2..).map(|idx| format!("{base}-{idx}")))
+        .find(|slug| !taken.contains(slug))
+        .expect("finite set of taken slugs");
+    Ok(slug)
 }
 
-/// Delete `article` for the provided `ActiveModel`.
-/// Returns `DeleteResult` with affected rows count on success, otherwise
+/// Fetch id of the latest `article` by provided author with exactly the same title and body,
+/// created not earlier than `since`. Used to detect duplicate submissions.
+/// Returns optional article id on success, otherwise returns an `database error`.
+pub async fn get_recent_duplicate_article_id(
+    db: &DatabaseConnection,
+    author_id: Uuid,
+    title: &str,
+    body: &str,
+    since: DateTime,
+) -> Result<Option<Uuid>, DbErr> {
+    Article::find()
+        .select_only()
+        .column(article::Column::Id)
+        .filter(article::Column::AuthorId.eq(author_id))
+        .filter(article::Column::Title.eq(title))
+        .filter(article::Column::Body.eq(body))
+        .filter(article::Column::CreatedAt.gte(since))
+        .filter(article_not_deleted())
+        .order_by_desc(article::Column::CreatedAt)
+        .into_tuple::<Uuid>()
+        .one(db)
+        .await
+}
+
+/// Fetch `slug` and `updated at` pairs of all `articles`, ordered by slug. Only these two columns
+/// are selected, so the query stays cheap for sitemap generation. Limit response by limit
+/// and offset parameters.
+/// Returns vec of `(slug, updated at)` pairs on success, otherwise returns an `database error`.
+pub async fn get_all_article_slugs(
+    db: &DatabaseConnection,
+    limit: u64,
+    offset: Option<u64>,
+) -> Result<Vec<(String, Option<DateTime>)>, DbErr> {
+    Article::find()
+        .select_only()
+        .column(article::Column::Slug)
+        .column(article::Column::UpdatedAt)
+        .filter(article_visible_to_current_user(None))
+        .order_by_asc(article::Column::Slug)
+        .limit(limit)
+        .offset(offset.unwrap_or(DEFAULT_PAGE_OFFSET))
+        .into_tuple()
+        .all(db)
+        .await
+}
+
+/// Insert `article` for the provided `ActiveModel`. Reject models with existing slug.
+/// Returns `InsertResult` with last inserted id on success, otherwise
 /// returns an `database error`.
-/// See [`DeleteResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.DeleteResult.html)
+/// Empty slug(or title, or description, or body), produces error as not allowed on database level.
+/// See [`InsertResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.InsertResult.html)
+/// documentation for more details.
+pub async fn create_article<C: ConnectionTrait>(
+    db: &C,
+    article: article::ActiveModel,
+) -> Result<InsertResult<article::ActiveModel>, DbErr> {
+    Article::insert(article).exec(db).await
+}
+
+/// Update only fields of `article` present in the provided `patch` (see ArticlePatch for
+/// details), other columns are left as stored, so article is not fetched before update.
+/// Returns updated `article` on success, `RecordNotUpdated` for non existing article,
+/// otherwise returns an `database error`.
+pub async fn patch_article<C: ConnectionTrait>(
+    db: &C,
+    id: Uuid,
+    patch: ArticlePatch,
+) -> Result<article::Model, DbErr> {
+    let article_model = article::ActiveModel {
+        id: Unchanged(id),
+        slug: patch.slug.map_or(NotSet, Set),
+        title: patch.title.map_or(NotSet, Set),
+        description: patch.description.map_or(NotSet, Set),
+        body: patch.body.map_or(NotSet, Set),
+        published: patch.published.map_or(NotSet, Set),
+        updated_at: patch.updated_at.map_or(NotSet, |time| Set(Some(time))),
+        ..Default::default()
+    };
+
+    Article::update(article_model).exec(db).await
+}
+
+/// Move `article` with provided id to another author, time of update set to current time.
+/// Returns updated `article` on success, `RecordNotFound` for non existing author,
+/// `RecordNotUpdated` for non existing article, otherwise returns an `database error`.
+pub async fn transfer_article(
+    db: &DatabaseConnection,
+    user_cache: &UserCache,
+    article_id: Uuid,
+    new_author_id: Uuid,
+) -> Result<article::Model, DbErr> {
+    get_user_by_id(db, user_cache, new_author_id)
+        .await?
+        .ok_or(DbErr::RecordNotFound(format!(
+            "New author {new_author_id} of article {article_id} not found"
+        )))?;
+
+    let article_model = article::ActiveModel {
+        id: Unchanged(article_id),
+        author_id: Set(new_author_id),
+        updated_at: Set(Some(Utc::now().naive_utc())),
+        ..Default::default()
+    };
+
+    Article::update(article_model).exec(db).await
+}
+
+/// Soft delete `article` for the provided `ActiveModel`: set `deleted at` time instead of
+/// removing the row, so external references and history (tags, favorites, comments and
+/// reactions) are kept. Deleted article is excluded from all read queries.
+/// Returns `UpdateResult` with affected rows count on success (0 for already deleted article),
+/// otherwise returns an `database error`.
+/// See [`UpdateResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.UpdateResult.html)
 /// documentation for more details.
 pub async fn delete_article(
     db: &DatabaseConnection,
     article: article::ActiveModel,
+) -> Result<UpdateResult, DbErr> {
+    Article::update_many()
+        .col_expr(
+            article::Column::DeletedAt,
+            Expr::value(Utc::now().naive_utc()),
+        )
+        .filter(article::Column::Id.eq(article.id.unwrap()))
+        .filter(article_not_deleted())
+        .exec(db)
+        .await
+}
+
+/// Delete all `articles` written by user with provided `author_id`, soft deleted ones
+/// included. Rows are removed, so tags, favorites, comments and reactions of the articles
+/// are removed by database cascade.
+/// Returns `DeleteResult` with affected rows count on success, otherwise
+/// returns an `database error`.
+/// See [`DeleteResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.DeleteResult.html)
+/// documentation for more details.
+pub async fn delete_articles_by_author<C: ConnectionTrait>(
+    db: &C,
+    author_id: Uuid,
 ) -> Result<DeleteResult, DbErr> {
-    Article::delete(article).exec(db).await
+    Article::delete_many()
+        .filter(article::Column::AuthorId.eq(author_id))
+        .exec(db)
+        .await
 }
 
 /// Delete all existing `follower records` from database.
@@ -293,35 +898,210 @@ pub async fn empty_article_table(db: &DatabaseConnection) -> Result<DeleteResult
     Article::delete_many().exec(db).await
 }
 
+/// Assemble `articles` with their tag names. Tags are matched to articles by article id,
+/// so order of loaded tags never affects association.
+async fn attach_tags(
+    db: &DatabaseConnection,
+    art_extended: Vec<ModelExtended>,
+) -> Result<Vec<ArticleWithAuthor>, DbErr> {
+    let article_ids = art_extended.iter().map(|art| art.id).collect();
+    let mut tags_by_article = get_article_tags_for_ids(db, article_ids).await?;
+
+    let articles = art_extended
+        .into_iter()
+        .map(|art| {
+            let tag_list = tags_by_article.remove(&art.id).unwrap_or_default();
+            (art, tag_list).into()
+        })
+        .collect();
+    Ok(articles)
+}
+
+/// Build query of `articles` with additional info (see ModelExtended for details): author
+/// profile, whether article liked by current user and favorites count. Author `following`
+/// flag is false, listings resolve it afterwards (see resolve_following). Listings add only
+/// own filters and ordering.
+fn extended_articles_query(current_user_id: Option<Uuid>) -> Select<Article> {
+    Article::find()
+        .join(JoinType::LeftJoin, article::Relation::User.def())
+        .column(user::Column::Username)
+        .column(user::Column::Bio)
+        .column(user::Column::Image)
+        .column_as(Expr::val(false), "following")
+        .column_as(article_liked_by_current_user(current_user_id), "favorited")
+        .join(
+            JoinType::LeftJoin,
+            favorited_article::Relation::Article.def().rev(),
+        )
+        .column_as(article_favorites_count(), "favorites_count")
+        .group_by(favorited_article::Column::ArticleId)
+        .group_by(article::Column::Id)
+        .group_by(user::Column::Username)
+        .group_by(user::Column::Id)
+}
+
+/// Returns condition combining tag name, author name, user who liked article and search text
+/// filters with visibility of drafts and blocked authors for current user. Shared by listing
+/// and counting queries, so both always select the same `articles`.
+fn articles_filter(
+    tag_names: &[String],
+    tag_match: TagMatch,
+    author_name: Option<&String>,
+    user_who_liked_it: Option<&String>,
+    search: Option<&String>,
+    current_user_id: Option<Uuid>,
+) -> Condition {
+    Condition::all()
+        .add(article_author(author_name))
+        .add(article_has_tags(tag_names, tag_match))
+        .add(article_liked_by_user(user_who_liked_it))
+        .add(article_contains_text(search))
+        .add(article_visible_to_current_user(current_user_id))
+        .add(author_not_blocked(
+            current_user_id,
+            article::Column::AuthorId,
+        ))
+}
+
+/// Returns condition selecting feed articles: visible articles of users followed (and not
+/// blocked) by current user, tagged according to provided tag names.
+fn feed_filter(tag_names: &[String], tag_match: TagMatch, current_user_id: Uuid) -> Condition {
+    Condition::all()
+        .add(author_followed_by_current_user(Some(current_user_id)))
+        .add(article_visible_to_current_user(Some(current_user_id)))
+        .add(author_not_blocked(
+            Some(current_user_id),
+            article::Column::AuthorId,
+        ))
+        .add(article_has_tags(tag_names, tag_match))
+}
+
+/// Returns start (inclusive) and end (exclusive) time of provided month of year, `None` for not
+/// existing month.
+fn month_bounds(year: i32, month: u32) -> Option<(DateTime, DateTime)> {
+    let start = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let end = start.checked_add_months(Months::new(1))?;
+    Some((start.and_hms_opt(0, 0, 0)?, end.and_hms_opt(0, 0, 0)?))
+}
+
+/// Returns condition selecting articles located after provided cursor (time of update and id)
+/// in articles ordered by time of update and id, most recent first. Articles without time of
+/// update never follow cursor. Return `true` if the cursor is not specified.
+fn article_after(cursor: Option<(DateTime, Uuid)>) -> Condition {
+    match cursor {
+        Some((updated_at, id)) => Condition::any()
+            .add(article::Column::UpdatedAt.lt(updated_at))
+            .add(
+                Condition::all()
+                    .add(article::Column::UpdatedAt.eq(updated_at))
+                    .add(article::Column::Id.lt(id)),
+            ),
+        None => Condition::all(),
+    }
+}
+
 /// Returns expression for determine whether the user is a author of the article.
 /// Return `true` if the author name is not specified since used as a filter.
 fn article_author(author_name: Option<&String>) -> SimpleExpr {
     match author_name {
-        Some(name) => user::Column::Username.like(name),
+        Some(name) => user::Column::Username.eq(name),
         None => true.into(),
     }
 }
 
-/// Returns expression for determine whether the article is tagged by provided tag.
-/// Return `true` if the tag name is not specified since used as a filter.
-fn article_has_tag(tag_name: Option<&String>) -> SimpleExpr {
-    match tag_name {
-        Some(name) => article::Column::Id.in_subquery(
-            Article::find()
-                .join(
-                    JoinType::LeftJoin,
-                    article_tag::Relation::Article.def().rev(),
+/// Returns condition for determine whether the article title, description or body contains
+/// provided text, case-insensitive. Wildcards of the text are matched literally.
+/// Return `true` if the text is not specified since used as a filter.
+fn article_contains_text(text: Option<&String>) -> Condition {
+    match text {
+        Some(text) => {
+            let escaped = text
+                .to_lowercase()
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            let pattern = format!("%{escaped}%");
+
+            [
+                article::Column::Title,
+                article::Column::Description,
+                article::Column::Body,
+            ]
+            .into_iter()
+            .fold(Condition::any(), |cond, col| {
+                cond.add(
+                    Expr::expr(Func::lower(Expr::col((article::Entity, col))))
+                        .like(LikeExpr::new(&pattern).escape('\\')),
+                )
+            })
+        }
+        None => Condition::all(),
+    }
+}
+
+/// Returns expression for determine whether the article is tagged by provided tags.
+/// All or any of the tags required depending on `tag_match`. Tag names compared case
+/// insensitively.
+/// Return `true` if the tag names are not specified since used as a filter.
+fn article_has_tags(tag_names: &[String], tag_match: TagMatch) -> SimpleExpr {
+    let mut names = tag_names.to_vec();
+    names.sort_by_key(|name| name.to_lowercase());
+    names.dedup_by_key(|name| name.to_lowercase());
+
+    if names.is_empty() {
+        return true.into();
+    }
+
+    let tagged = ArticleTag::find()
+        .join(JoinType::InnerJoin, article_tag::Relation::Tag.def())
+        .filter(tag_name_in(names.clone()))
+        .select_only();
+
+    match tag_match {
+        TagMatch::All => article::Column::Id.in_subquery(
+            tagged
+                .column(article_tag::Column::ArticleId)
+                .group_by(article_tag::Column::ArticleId)
+                .having(
+                    Expr::col(article_tag::Column::TagId)
+                        .count()
+                        .eq(names.len() as i32),
+                )
+                .into_query(),
+        ),
+        TagMatch::Any => Expr::exists(
+            tagged
+                .column(article_tag::Column::ArticleId)
+                .filter(
+                    Expr::col((ArticleTag, article_tag::Column::ArticleId))
+                        .equals((Article, article::Column::Id)),
                 )
-                .join(JoinType::LeftJoin, article_tag::Relation::Tag.def())
-                .filter(tag::Column::TagName.like(name))
-                .select_only()
-                .column(article::Column::Id)
                 .into_query(),
         ),
-        None => true.into(),
     }
 }
 
+/// Returns expression for count tags of the article shared with article of provided id.
+fn article_shared_tags_count(article_id: Uuid) -> SimpleExpr {
+    let source_tags = ArticleTag::find()
+        .select_only()
+        .column(article_tag::Column::TagId)
+        .filter(article_tag::Column::ArticleId.eq(article_id))
+        .into_query();
+
+    let shared_tags = ArticleTag::find()
+        .select_only()
+        .expr(Expr::col(article_tag::Column::TagId).count())
+        .filter(
+            Expr::col((ArticleTag, article_tag::Column::ArticleId))
+                .equals((Article, article::Column::Id)),
+        )
+        .filter(article_tag::Column::TagId.in_subquery(source_tags))
+        .into_query();
+
+    SimpleExpr::SubQuery(None, Box::new(shared_tags.into_sub_query_statement()))
+}
+
 /// Returns expression for determine whether the article is liked by provided user.
 /// Return `true` if the user name is not specified since used as a filter.
 fn article_liked_by_user(user_name: Option<&String>) -> SimpleExpr {
@@ -333,7 +1113,7 @@ fn article_liked_by_user(user_name: Option<&String>) -> SimpleExpr {
                     favorited_article::Relation::Article.def().rev(),
                 )
                 .join(JoinType::LeftJoin, favorited_article::Relation::User.def())
-                .filter(user::Column::Username.like(name))
+                .filter(user::Column::Username.eq(name))
                 .select_only()
                 .column(article::Column::Id)
                 .into_query(),
@@ -357,8 +1137,105 @@ fn article_liked_by_current_user(user_id: Option<Uuid>) -> SimpleExpr {
     }
 }
 
+/// Returns expression for determine whether the article is visible to the logged in user.
+/// Published articles are visible to everyone, drafts only to their author. Deleted articles
+/// are visible to nobody.
+fn article_visible_to_current_user(user_id: Option<Uuid>) -> SimpleExpr {
+    let published = Expr::col((article::Entity, article::Column::Published)).eq(true);
+    let visible = match user_id {
+        Some(id) => published.or(Expr::col((article::Entity, article::Column::AuthorId)).eq(id)),
+        None => published,
+    };
+    article_not_deleted().and(visible)
+}
+
+/// Returns expression for determine whether the article is not (soft) deleted.
+fn article_not_deleted() -> SimpleExpr {
+    Expr::col((article::Entity, article::Column::DeletedAt)).is_null()
+}
+
+/// Set `following` flag of articles authors, resolved with single query for whole list
+/// instead of subquery per row (see get_followed_user_ids for details).
+/// Returns `articles` on success, otherwise returns an `database error`.
+async fn resolve_following(
+    db: &DatabaseConnection,
+    mut articles: Vec<ModelExtended>,
+    current_user_id: Option<Uuid>,
+) -> Result<Vec<ModelExtended>, DbErr> {
+    let author_ids = articles.iter().map(|art| art.author_id).collect();
+    let followed = get_followed_user_ids(db, current_user_id, author_ids).await?;
+    articles
+        .iter_mut()
+        .for_each(|art| art.author.following = followed.contains(&art.author_id));
+    Ok(articles)
+}
+
+/// Returns expression for count joined `favorited articles`. Never NULL, so article without
+/// favorites has 0 count regardless of backend.
 fn article_favorites_count() -> SimpleExpr {
-    Expr::count(Expr::col(favorited_article::Column::ArticleId)).cast_as(Alias::new("Integer"))
+    SimpleExpr::from(Func::coalesce([
+        Expr::count(Expr::col((
+            favorited_article::Entity,
+            favorited_article::Column::ArticleId,
+        ))),
+        Expr::val(0).into(),
+    ]))
+    .cast_as(Alias::new("Integer"))
+}
+
+/// Returns expression for count `favorited articles` created since provided time.
+fn article_favorites_count_since(since: DateTime) -> SimpleExpr {
+    Func::sum(Expr::case(favorited_article::Column::CreatedAt.gte(since), 1).finally(0)).into()
+}
+
+/// Returns expression for time the joined `article tag` was created.
+fn article_tagged_at() -> SimpleExpr {
+    Func::max(Expr::col((
+        article_tag::Entity,
+        article_tag::Column::CreatedAt,
+    )))
+    .into()
+}
+
+/// Semantics of filtering `articles` by several tag names.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TagMatch {
+    /// Article should be tagged by all of the provided tags.
+    #[default]
+    All,
+    /// Article should be tagged by any of the provided tags.
+    Any,
+}
+
+/// Order of `articles` listing, most recent first by the selected timestamp.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ArticleSort {
+    /// Recently updated articles first.
+    #[default]
+    Updated,
+    /// Recently created articles first.
+    Created,
+}
+
+impl ArticleSort {
+    fn column(self) -> article::Column {
+        match self {
+            ArticleSort::Updated => article::Column::UpdatedAt,
+            ArticleSort::Created => article::Column::CreatedAt,
+        }
+    }
+}
+
+impl FromStr for ArticleSort {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "updated" => Ok(ArticleSort::Updated),
+            "created" => Ok(ArticleSort::Created),
+            _ => Err(()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -373,6 +1250,7 @@ pub struct ModelExtended {
     favorites_count: i32,
     created_at: Option<DateTime>,
     updated_at: Option<DateTime>,
+    published: bool,
     author_id: Uuid,
     author: Profile,
 }
@@ -389,12 +1267,58 @@ impl FromQueryResult for ModelExtended {
             favorites_count: res.try_get(pre, "favorites_count")?,
             created_at: res.try_get(pre, "created_at")?,
             updated_at: res.try_get(pre, "updated_at")?,
+            published: res.try_get(pre, "published")?,
             author_id: res.try_get(pre, "author_id")?,
             author: Profile::from_query_result(res, pre)?,
         })
     }
 }
 
+/// Row of paged query: article with total number of rows matching the query.
+struct ModelWithTotal {
+    model: ModelExtended,
+    total: i64,
+}
+
+impl FromQueryResult for ModelWithTotal {
+    fn from_query_result(res: &sea_orm::QueryResult, pre: &str) -> Result<Self, sea_orm::DbErr> {
+        Ok(Self {
+            model: ModelExtended::from_query_result(res, pre)?,
+            total: res.try_get(pre, "total")?,
+        })
+    }
+}
+
+/// Struct describing partial update of article. `None` leaves field unchanged, time of update
+/// is not derived from other fields and set only when provided.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ArticlePatch {
+    pub slug: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub body: Option<String>,
+    pub published: Option<bool>,
+    pub updated_at: Option<DateTime>,
+}
+
+/// Page of items together with total number of items, matching the query regardless of
+/// limit and offset, and limit and offset used for the page.
+#[derive(Debug, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u64,
+    pub limit: u64,
+    pub offset: u64,
+}
+
+/// Page of items located after cursor together with cursor of the next page, `None` when
+/// there is no next page.
+#[derive(Debug, PartialEq)]
+pub struct CursorPage<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<(DateTime, Uuid)>,
+}
+
 impl From<ModelExtended> for article::Model {
     fn from(mdl: ModelExtended) -> article::Model {
         article::Model {
@@ -406,6 +1330,8 @@ impl From<ModelExtended> for article::Model {
             author_id: mdl.author_id,
             created_at: mdl.created_at,
             updated_at: mdl.updated_at,
+            published: mdl.published,
+            deleted_at: None,
         }
     }
 }
@@ -417,10 +1343,15 @@ pub struct ArticleWithAuthor {
     pub title: String,
     pub description: String,
     pub body: String,
+    #[serde(skip)]
+    pub author_id: Uuid,
     pub favorited: bool,
     pub favorites_count: i32,
+    #[serde(serialize_with = "serialize_utc")]
     pub created_at: Option<DateTime>,
+    #[serde(serialize_with = "serialize_utc")]
     pub updated_at: Option<DateTime>,
+    pub published: bool,
     pub author: Profile,
     pub tag_list: Vec<String>,
 }
@@ -432,325 +1363,563 @@ impl FromQueryResult for ArticleWithAuthor {
             title: res.try_get(pre, "title")?,
             description: res.try_get(pre, "description")?,
             body: res.try_get(pre, "body")?,
+            author_id: res.try_get(pre, "author_id")?,
             favorited: res.try_get(pre, "favorited")?,
             favorites_count: res.try_get(pre, "favorites_count")?,
             created_at: res.try_get(pre, "created_at")?,
             updated_at: res.try_get(pre, "updated_at")?,
+            published: res.try_get(pre, "published")?,
             tag_list: vec![],
             author: Profile::from_query_result(res, pre)?,
         })
     }
 }
 
-impl From<(ModelExtended, Vec<tag::Model>)> for ArticleWithAuthor {
-    fn from((article, tags): (ModelExtended, Vec<tag::Model>)) -> Self {
+impl ArticleWithAuthor {
+    /// Assemble `article` with additional info from already fetched parts, thus
+    /// no database round trip required (e.g. after create or favorite article).
+    pub fn new(
+        article: article::Model,
+        author: Profile,
+        favorited: bool,
+        favorites_count: i32,
+        tag_list: Vec<String>,
+    ) -> Self {
         Self {
             slug: article.slug,
             title: article.title,
             description: article.description,
             body: article.body,
-            favorited: article.favorited,
-            favorites_count: article.favorites_count,
+            author_id: article.author_id,
+            favorited,
+            favorites_count,
             created_at: article.created_at,
             updated_at: article.updated_at,
-            author: article.author,
-            tag_list: tags.into_iter().map(|tg| tg.tag_name).collect(),
+            published: article.published,
+            author,
+            tag_list,
         }
     }
 }
 
+impl From<(ModelExtended, Vec<String>)> for ArticleWithAuthor {
+    fn from((article, tag_list): (ModelExtended, Vec<String>)) -> Self {
+        let author = article.author.clone();
+        let favorited = article.favorited;
+        let favorites_count = article.favorites_count;
+
+        Self::new(article.into(), author, favorited, favorites_count, tag_list)
+    }
+}
+
 #[cfg(test)]
-mod test_get_articles_with_filters {
-    use super::get_articles_with_filters;
-    use crate::repo::{article::ArticleWithAuthor, user::Profile};
-    use crate::tests::{
-        Operation::{Insert, Migration},
-        TestData, TestDataBuilder, TestErr,
-    };
+mod test_article_with_author_new {
+    use super::{get_article_by_id, ArticleWithAuthor};
+    use crate::repo::user::Profile;
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
     use std::vec;
 
     #[tokio::test]
-    async fn get_existing_articles() -> Result<(), TestErr> {
+    async fn same_shape_as_query_built() -> Result<(), TestErr> {
         let (
             connection,
             TestData {
                 users, articles, ..
             },
         ) = TestDataBuilder::new()
-            .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
-            .favorited_articles(Migration)
-            .tags(Migration)
-            .article_tags(Migration)
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Insert(vec![(1, 2)]))
+            .followers(Insert(vec![(1, 2)]))
+            .tags(Insert(1))
+            .article_tags(Insert(vec![(1, 1)]))
             .build()
             .await?;
 
-        let author = users.unwrap().into_iter().next().unwrap();
-        let expected: Vec<ArticleWithAuthor> = articles
-            .unwrap()
-            .into_iter()
-            .rev()
-            .map(|artcl| ArticleWithAuthor {
-                slug: artcl.slug,
-                title: artcl.title,
-                description: artcl.description,
-                body: artcl.body,
-                favorited: false,
-                favorites_count: 0,
-                author: Profile {
-                    username: author.username.clone(),
-                    bio: author.bio.clone(),
-                    image: author.image.clone(),
-                    following: false,
-                },
-                created_at: artcl.created_at,
-                updated_at: artcl.updated_at,
-                tag_list: vec![],
-            })
-            .collect();
+        let users = users.unwrap();
+        let author = users[0].clone();
+        let article = articles.unwrap().into_iter().next().unwrap();
+
+        let built = ArticleWithAuthor::new(
+            article.clone(),
+            Profile {
+                username: author.username,
+                bio: author.bio,
+                image: author.image,
+                following: true,
+            },
+            true,
+            1,
+            vec!["tag_name1".to_owned()],
+        );
+        let queried = get_article_by_id(&connection, article.id, Some(users[1].id))
+            .await?
+            .unwrap();
 
-        let result =
-            get_articles_with_filters(&connection, None, None, None, None, None, None).await?;
-        assert_eq!(result, expected);
+        assert_eq!(
+            serde_json::to_value(&built).unwrap(),
+            serde_json::to_value(&queried).unwrap()
+        );
 
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod test_get_drafts {
+    use super::{get_drafts, get_own_articles, get_trending_articles};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use chrono::{Duration, Utc};
+    use entity::entities::article;
+    use sea_orm::{ActiveModelTrait, Set};
+    use std::vec;
 
     #[tokio::test]
-    async fn get_empty_list() -> Result<(), TestErr> {
-        let (connection, _) = TestDataBuilder::new()
-            .users(Migration)
-            .articles(Migration)
-            .favorited_articles(Migration)
-            .tags(Migration)
-            .article_tags(Migration)
+    async fn only_own_drafts() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1, 2]))
+            .tags(Insert(1))
+            .article_tags(Insert(vec![(2, 1)]))
+            .favorited_articles(Insert(vec![(2, 2)]))
+            .followers(Insert(vec![(1, 2)]))
             .build()
             .await?;
+        for draft in articles.unwrap().into_iter().skip(1) {
+            let mut model: article::ActiveModel = draft.into();
+            model.published = Set(false);
+            model.update(&connection).await?;
+        }
+        let users = users.unwrap();
 
-        let result =
-            get_articles_with_filters(&connection, None, None, None, None, None, None).await?;
-        let expected = vec![];
-        assert_eq!(result, expected);
+        let result = get_drafts(&connection, users[0].id, 20, None).await?;
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].slug, "title2");
+        assert_eq!(result[0].tag_list, vec!["tag_name1"]);
+
+        // Favorited draft of other user not trending for others
+        let since = Utc::now().naive_utc() - Duration::days(1);
+        let result = get_trending_articles(&connection, since, 20, Some(users[1].id)).await?;
+        assert!(result.is_empty());
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn filter_article_tag_pos() -> Result<(), TestErr> {
+    async fn own_articles_with_drafts() -> Result<(), TestErr> {
         let (
             connection,
             TestData {
                 users, articles, ..
             },
         ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1, 2]))
+            .tags(Insert(1))
+            .article_tags(Insert(vec![(2, 1)]))
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let draft = articles.unwrap().remove(1);
+        let mut model: article::ActiveModel = draft.into();
+        model.published = Set(false);
+        model.update(&connection).await?;
+        let users = users.unwrap();
+
+        let mut result = get_own_articles(&connection, users[0].id, 20, None).await?;
+        result.sort_by(|a, b| a.slug.cmp(&b.slug));
+        assert_eq!(result.len(), 2);
+        assert_eq!(
+            (result[0].slug.as_str(), result[0].published),
+            ("title1", true)
+        );
+        assert_eq!(
+            (result[1].slug.as_str(), result[1].published),
+            ("title2", false)
+        );
+        assert_eq!(result[1].tag_list, vec!["tag_name1"]);
+
+        let result = get_own_articles(&connection, users[0].id, 1, Some(1)).await?;
+        assert_eq!(result.len(), 1);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_attach_tags {
+    use super::{get_articles_page, ArticleSort, TagMatch};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn tags_matched_by_article() -> Result<(), TestErr> {
+        // Links inserted in reverse order of articles, some articles without tags
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .articles(Insert(vec![1, 1, 1, 1]))
+            .tags(Insert(3))
+            .article_tags(Insert(vec![(4, 1), (4, 3), (3, 2), (1, 3), (1, 1)]))
             .favorited_articles(Migration)
-            .tags(Insert(5))
-            .article_tags(Insert(vec![(1, 1), (2, 2), (3, 3), (4, 4), (5, 5)]))
+            .followers(Migration)
             .build()
             .await?;
-
-        let author = users.unwrap().into_iter().next().unwrap();
-        let article = articles.unwrap().into_iter().nth(2).unwrap();
-        let expected: Vec<ArticleWithAuthor> = [article]
-            .into_iter()
-            .map(|artcl| ArticleWithAuthor {
-                slug: artcl.slug,
-                title: artcl.title,
-                description: artcl.description,
-                body: artcl.body,
-                favorited: false,
-                favorites_count: 0,
-                author: Profile {
-                    username: author.username.clone(),
-                    bio: author.bio.clone(),
-                    image: author.image.clone(),
-                    following: false,
-                },
-                created_at: artcl.created_at,
-                updated_at: artcl.updated_at,
-                tag_list: vec!["tag_name3".to_owned()],
-            })
-            .collect();
-
-        let result = get_articles_with_filters(
-            &connection,
-            Some(&"tag_name3".to_owned()),
-            None,
-            None,
-            None,
-            None,
-            None,
-        )
-        .await?;
-
-        assert_eq!(result, expected);
+        let slugs: Vec<String> = articles.unwrap().into_iter().map(|art| art.slug).collect();
+
+        for sort in [ArticleSort::Updated, ArticleSort::Created] {
+            let result = get_articles_page(
+                &connection,
+                &[],
+                TagMatch::Any,
+                None,
+                None,
+                None,
+                sort,
+                20,
+                None,
+                None,
+            )
+            .await?
+            .items;
+            let tags: HashMap<String, Vec<String>> = result
+                .into_iter()
+                .map(|art| (art.slug, art.tag_list))
+                .collect();
+
+            assert_eq!(tags[&slugs[0]], vec!["tag_name1", "tag_name3"]);
+            assert!(tags[&slugs[1]].is_empty());
+            assert_eq!(tags[&slugs[2]], vec!["tag_name2"]);
+            assert_eq!(tags[&slugs[3]], vec!["tag_name1", "tag_name3"]);
+        }
 
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod test_generate_unique_slug {
+    use super::generate_unique_slug;
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use entity::entities::article;
+    use sea_orm::{ActiveModelTrait, Set};
 
     #[tokio::test]
-    async fn filter_article_tag_neg() -> Result<(), TestErr> {
-        let (connection, _) = TestDataBuilder::new()
+    async fn suffix_for_taken_slugs() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
             .users(Insert(1))
             .articles(Insert(vec![1, 1]))
-            .favorited_articles(Migration)
-            .tags(Insert(2))
-            .article_tags(Insert(vec![(1, 1)]))
             .build()
             .await?;
+        let mut articles = articles.unwrap().into_iter();
 
-        let result = get_articles_with_filters(
-            &connection,
-            Some(&"tag_name2".to_owned()),
-            None,
-            None,
-            None,
-            None,
-            None,
-        )
-        .await?;
+        assert_eq!(
+            generate_unique_slug(&connection, "Title", None).await?,
+            "title"
+        );
 
-        let expected = vec![];
-        assert_eq!(result, expected);
+        let mut model: article::ActiveModel = articles.next().unwrap().into();
+        model.slug = Set("title".to_owned());
+        let first = model.update(&connection).await?;
+        assert_eq!(
+            generate_unique_slug(&connection, "Title", None).await?,
+            "title-2"
+        );
+
+        let mut model: article::ActiveModel = articles.next().unwrap().into();
+        model.slug = Set("title-2".to_owned());
+        model.update(&connection).await?;
+        assert_eq!(
+            generate_unique_slug(&connection, "Title", None).await?,
+            "title-3"
+        );
+
+        // Renamed article keeps own slug
+        let slug = generate_unique_slug(&connection, "Title", Some(first.id)).await?;
+        assert_eq!(slug, "title");
 
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod test_resolve_following {
+    use super::{get_article_by_slug, get_articles_page, ArticleSort, TagMatch};
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use std::vec;
 
     #[tokio::test]
-    async fn filter_article_tag_empty() -> Result<(), TestErr> {
-        let (connection, _) = TestDataBuilder::new()
-            .users(Insert(1))
-            .articles(Insert(vec![1, 1]))
-            .favorited_articles(Migration)
-            .tags(Insert(2))
+    async fn same_as_subquery() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(4))
+            .articles(Insert(vec![2, 3, 4, 2, 1]))
+            .tags(Insert(1))
             .article_tags(Insert(vec![(1, 1)]))
+            .favorited_articles(Insert(vec![(1, 1)]))
+            .followers(Insert(vec![(2, 1), (4, 1), (3, 2)]))
             .build()
             .await?;
+        let current_user_id = Some(users.unwrap()[0].id);
 
-        let result = get_articles_with_filters(
+        let result = get_articles_page(
             &connection,
-            Some(&"".to_owned()),
-            None,
+            &[],
+            TagMatch::Any,
             None,
             None,
             None,
+            ArticleSort::Updated,
+            20,
             None,
+            current_user_id,
         )
-        .await?;
+        .await?
+        .items;
+        assert_eq!(result.len(), 5);
+
+        for article in result {
+            let expected = get_article_by_slug(&connection, &article.slug, current_user_id)
+                .await?
+                .unwrap();
+            assert_eq!(article.author.following, expected.author.following);
+        }
 
-        let expected = vec![];
-        assert_eq!(result, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn query_count_independent_of_page_size() -> Result<(), TestErr> {
+        let (mut connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(4))
+            .articles(Insert(vec![2, 3, 4, 2, 3, 4, 2, 3, 4]))
+            .tags(Insert(1))
+            .article_tags(Insert(vec![(1, 1)]))
+            .favorited_articles(Insert(vec![(1, 1)]))
+            .followers(Insert(vec![(2, 1), (4, 1)]))
+            .build()
+            .await?;
+        let current_user_id = Some(users.unwrap()[0].id);
+
+        let queries = Arc::new(AtomicUsize::new(0));
+        let counter = queries.clone();
+        connection.set_metric_callback(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let mut counts = Vec::new();
+        for limit in [1, 9] {
+            queries.store(0, Ordering::SeqCst);
+            let result = get_articles_page(
+                &connection,
+                &[],
+                TagMatch::Any,
+                None,
+                None,
+                None,
+                ArticleSort::Updated,
+                limit,
+                None,
+                current_user_id,
+            )
+            .await?
+            .items;
+            assert_eq!(result.len(), limit as usize);
+            counts.push(queries.load(Ordering::SeqCst));
+        }
+
+        // Articles, followed authors, then tags of loaded articles
+        assert_eq!(counts, vec![3, 3]);
 
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod test_get_articles_page {
+    use super::{get_articles_count, get_articles_page, ArticleSort, Page, TagMatch};
+    use crate::repo::{article::ArticleWithAuthor, user::Profile};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use entity::entities::{
+        article,
+        prelude::{Article, User},
+        user,
+    };
+    use sea_orm::{prelude::Expr, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+    use std::vec;
 
     #[tokio::test]
-    async fn filter_article_author_pos() -> Result<(), TestErr> {
+    async fn get_existing_articles() -> Result<(), TestErr> {
         let (
             connection,
             TestData {
                 users, articles, ..
             },
         ) = TestDataBuilder::new()
-            .users(Insert(2))
-            .articles(Insert(vec![1, 1, 2, 1, 1]))
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
             .favorited_articles(Migration)
             .tags(Migration)
             .article_tags(Migration)
             .build()
             .await?;
 
-        let author = users.unwrap().into_iter().nth(1).unwrap();
-        let article = articles.unwrap().into_iter().nth(2).unwrap();
-        let expected: Vec<ArticleWithAuthor> = [article]
+        let author = users.unwrap().into_iter().next().unwrap();
+        let expected: Vec<ArticleWithAuthor> = articles
+            .unwrap()
             .into_iter()
+            .rev()
             .map(|artcl| ArticleWithAuthor {
                 slug: artcl.slug,
                 title: artcl.title,
                 description: artcl.description,
                 body: artcl.body,
+                author_id: artcl.author_id,
                 favorited: false,
                 favorites_count: 0,
                 author: Profile {
-                    username: "username2".to_owned(),
+                    username: author.username.clone(),
                     bio: author.bio.clone(),
                     image: author.image.clone(),
                     following: false,
                 },
                 created_at: artcl.created_at,
                 updated_at: artcl.updated_at,
+                published: artcl.published,
                 tag_list: vec![],
             })
             .collect();
 
-        let result = get_articles_with_filters(
+        let result = get_articles_page(
             &connection,
+            &[],
+            TagMatch::All,
             None,
-            Some(&"username2".to_owned()),
             None,
             None,
+            ArticleSort::Updated,
+            20,
             None,
             None,
         )
-        .await?;
-
+        .await?
+        .items;
         assert_eq!(result, expected);
 
         Ok(())
     }
 
+    async fn search_articles(
+        connection: &DatabaseConnection,
+        text: &str,
+    ) -> Result<Vec<String>, TestErr> {
+        let articles = get_articles_page(
+            connection,
+            &[],
+            TagMatch::All,
+            None,
+            None,
+            Some(&text.to_owned()),
+            ArticleSort::Updated,
+            20,
+            None,
+            None,
+        )
+        .await?
+        .items;
+        Ok(articles.into_iter().map(|art| art.slug).collect())
+    }
+
     #[tokio::test]
-    async fn filter_article_author_neg() -> Result<(), TestErr> {
-        let (connection, _) = TestDataBuilder::new()
-            .users(Insert(2))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
+    async fn search_matching_body() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1]))
             .favorited_articles(Migration)
             .tags(Migration)
             .article_tags(Migration)
             .build()
             .await?;
+        Article::update_many()
+            .col_expr(
+                article::Column::Body,
+                Expr::value("Borrowing and Ownership 100%"),
+            )
+            .filter(article::Column::Id.eq(articles.unwrap()[1].id))
+            .exec(&connection)
+            .await?;
 
-        let result = get_articles_with_filters(
-            &connection,
-            None,
-            Some(&"username2".to_owned()),
-            None,
-            None,
-            None,
-            None,
-        )
-        .await?;
+        assert_eq!(search_articles(&connection, "ownership").await?, ["title2"]);
+        assert_eq!(search_articles(&connection, "100%").await?, ["title2"]);
+        assert_eq!(search_articles(&connection, "TITLE3").await?, ["title3"]);
+        assert_eq!(
+            search_articles(&connection, "descr").await?,
+            ["title3", "title2", "title1"]
+        );
 
-        let expected = vec![];
-        assert_eq!(result, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn search_not_matching() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        assert!(search_articles(&connection, "ownership").await?.is_empty());
+        assert!(search_articles(&connection, "title_").await?.is_empty());
+        assert!(search_articles(&connection, "%").await?.is_empty());
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn filter_article_author_empty() -> Result<(), TestErr> {
+    async fn get_empty_list() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new()
-            .users(Insert(2))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .users(Migration)
+            .articles(Migration)
             .favorited_articles(Migration)
             .tags(Migration)
             .article_tags(Migration)
             .build()
             .await?;
 
-        let result = get_articles_with_filters(
+        let result = get_articles_page(
             &connection,
+            &[],
+            TagMatch::All,
             None,
-            Some(&"".to_owned()),
             None,
             None,
+            ArticleSort::Updated,
+            20,
             None,
             None,
         )
-        .await?;
-
+        .await?
+        .items;
         let expected = vec![];
         assert_eq!(result, expected);
 
@@ -758,18 +1927,18 @@ mod test_get_articles_with_filters {
     }
 
     #[tokio::test]
-    async fn filter_article_user_who_liked_it_pos() -> Result<(), TestErr> {
+    async fn filter_article_tag_pos() -> Result<(), TestErr> {
         let (
             connection,
             TestData {
                 users, articles, ..
             },
         ) = TestDataBuilder::new()
-            .users(Insert(2))
+            .users(Insert(1))
             .articles(Insert(vec![1, 1, 1, 1, 1]))
-            .favorited_articles(Insert(vec![(3, 2)]))
-            .tags(Migration)
-            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .tags(Insert(5))
+            .article_tags(Insert(vec![(1, 1), (2, 2), (3, 3), (4, 4), (5, 5)]))
             .build()
             .await?;
 
@@ -782,8 +1951,9 @@ mod test_get_articles_with_filters {
                 title: artcl.title,
                 description: artcl.description,
                 body: artcl.body,
+                author_id: artcl.author_id,
                 favorited: false,
-                favorites_count: 1,
+                favorites_count: 0,
                 author: Profile {
                     username: author.username.clone(),
                     bio: author.bio.clone(),
@@ -792,20 +1962,25 @@ mod test_get_articles_with_filters {
                 },
                 created_at: artcl.created_at,
                 updated_at: artcl.updated_at,
-                tag_list: vec![],
+                published: artcl.published,
+                tag_list: vec!["tag_name3".to_owned()],
             })
             .collect();
 
-        let result = get_articles_with_filters(
+        let result = get_articles_page(
             &connection,
+            &["tag_name3".to_owned()],
+            TagMatch::All,
             None,
             None,
-            Some(&"username2".to_owned()),
             None,
+            ArticleSort::Updated,
+            20,
             None,
             None,
         )
-        .await?;
+        .await?
+        .items;
 
         assert_eq!(result, expected);
 
@@ -813,26 +1988,30 @@ mod test_get_articles_with_filters {
     }
 
     #[tokio::test]
-    async fn filter_article_user_who_liked_it_neg() -> Result<(), TestErr> {
+    async fn filter_article_tag_neg() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new()
-            .users(Insert(2))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
-            .favorited_articles(Insert(vec![(3, 2)]))
-            .tags(Migration)
-            .article_tags(Migration)
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1]))
+            .favorited_articles(Migration)
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1)]))
             .build()
             .await?;
 
-        let result = get_articles_with_filters(
+        let result = get_articles_page(
             &connection,
+            &["tag_name2".to_owned()],
+            TagMatch::All,
             None,
             None,
-            Some(&"username1".to_owned()),
             None,
+            ArticleSort::Updated,
+            20,
             None,
             None,
         )
-        .await?;
+        .await?
+        .items;
 
         let expected = vec![];
         assert_eq!(result, expected);
@@ -841,26 +2020,30 @@ mod test_get_articles_with_filters {
     }
 
     #[tokio::test]
-    async fn filter_article_user_who_liked_it_empty() -> Result<(), TestErr> {
+    async fn filter_article_tag_empty() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new()
-            .users(Insert(2))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
-            .favorited_articles(Insert(vec![(3, 2)]))
-            .tags(Migration)
-            .article_tags(Migration)
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1]))
+            .favorited_articles(Migration)
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1)]))
             .build()
             .await?;
 
-        let result = get_articles_with_filters(
+        let result = get_articles_page(
             &connection,
+            &["".to_owned()],
+            TagMatch::All,
             None,
             None,
-            Some(&"".to_owned()),
             None,
+            ArticleSort::Updated,
+            20,
             None,
             None,
         )
-        .await?;
+        .await?
+        .items;
 
         let expected = vec![];
         assert_eq!(result, expected);
@@ -869,148 +2052,385 @@ mod test_get_articles_with_filters {
     }
 
     #[tokio::test]
-    async fn limit_articles_pos() -> Result<(), TestErr> {
-        let (
-            connection,
-            TestData {
-                users, articles, ..
-            },
-        ) = TestDataBuilder::new()
+    async fn filter_article_tags_all() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .articles(Insert(vec![1, 1, 1, 1]))
             .favorited_articles(Migration)
-            .tags(Migration)
-            .article_tags(Migration)
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (1, 2), (2, 1), (3, 2)]))
             .build()
             .await?;
 
-        let author = users.unwrap().into_iter().next().unwrap();
-        let expected: Vec<ArticleWithAuthor> = articles
-            .unwrap()
-            .into_iter()
-            .rev()
-            .take(2)
-            .map(|artcl| ArticleWithAuthor {
-                slug: artcl.slug,
-                title: artcl.title,
-                description: artcl.description,
-                body: artcl.body,
-                favorited: false,
-                favorites_count: 0,
-                author: Profile {
-                    username: author.username.clone(),
-                    bio: author.bio.clone(),
-                    image: author.image.clone(),
-                    following: false,
-                },
-                created_at: artcl.created_at,
-                updated_at: artcl.updated_at,
-                tag_list: vec![],
-            })
-            .collect();
+        let result = get_articles_page(
+            &connection,
+            &["tag_name1".to_owned(), "tag_name2".to_owned()],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            None,
+        )
+        .await?
+        .items;
+        let result: Vec<String> = result.into_iter().map(|artcl| artcl.title).collect();
 
-        let result =
-            get_articles_with_filters(&connection, None, None, None, Some(2), None, None).await?;
-        assert_eq!(result, expected);
+        assert_eq!(result, vec!["title1"]);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn limit_articles_zero_val() -> Result<(), TestErr> {
+    async fn filter_article_tags_all_no_match() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .articles(Insert(vec![1, 1, 1]))
+            .favorited_articles(Migration)
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(2, 1), (3, 2)]))
+            .build()
+            .await?;
+        let tag_names = ["tag_name1".to_owned(), "tag_name2".to_owned()];
+
+        let result = get_articles_page(
+            &connection,
+            &tag_names,
+            TagMatch::All,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            None,
+        )
+        .await?
+        .items;
+        let count = get_articles_count(
+            &connection,
+            &tag_names,
+            TagMatch::All,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await?;
+
+        assert!(result.is_empty());
+        assert_eq!(count, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn filter_article_tags_any() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1, 1]))
+            .favorited_articles(Migration)
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (1, 2), (2, 1), (3, 2)]))
+            .build()
+            .await?;
+
+        let result = get_articles_page(
+            &connection,
+            &["tag_name1".to_owned(), "tag_name2".to_owned()],
+            TagMatch::Any,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            None,
+        )
+        .await?
+        .items;
+        let result: Vec<String> = result.into_iter().map(|artcl| artcl.title).collect();
+
+        assert_eq!(result, vec!["title3", "title2", "title1"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn filter_article_tags_ignore_case() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1, 1]))
             .favorited_articles(Migration)
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (1, 2), (2, 1), (3, 2)]))
+            .build()
+            .await?;
+
+        for (tag_names, tag_match, expected) in [
+            (
+                vec!["TAG_Name1", "Tag_name2"],
+                TagMatch::All,
+                vec!["title1"],
+            ),
+            (
+                vec!["tag_name1", "TAG_NAME1"],
+                TagMatch::All,
+                vec!["title2", "title1"],
+            ),
+            (vec!["Tag_Name2"], TagMatch::Any, vec!["title3", "title1"]),
+        ] {
+            let tag_names: Vec<String> = tag_names.into_iter().map(str::to_owned).collect();
+            let result = get_articles_page(
+                &connection,
+                &tag_names,
+                tag_match,
+                None,
+                None,
+                None,
+                ArticleSort::Updated,
+                20,
+                None,
+                None,
+            )
+            .await?
+            .items;
+            let result: Vec<String> = result.into_iter().map(|artcl| artcl.title).collect();
+            assert_eq!(result, expected);
+
+            let count =
+                get_articles_count(&connection, &tag_names, tag_match, None, None, None, None)
+                    .await?;
+            assert_eq!(count, expected.len() as u64);
+        }
+
+        Ok(())
+    }
+
+    /// Titles of articles filtered by author name or name of user who liked article.
+    async fn filtered_titles(
+        connection: &DatabaseConnection,
+        author_name: Option<&str>,
+        user_who_liked_it: Option<&str>,
+    ) -> Result<Vec<String>, TestErr> {
+        let result = get_articles_page(
+            connection,
+            &[],
+            TagMatch::All,
+            author_name.map(|name| name.to_owned()).as_ref(),
+            user_who_liked_it.map(|name| name.to_owned()).as_ref(),
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            None,
+        )
+        .await?
+        .items;
+        Ok(result.into_iter().map(|artcl| artcl.title).collect())
+    }
+
+    #[tokio::test]
+    async fn filter_exact_user_name() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(12))
+            .articles(Insert(vec![1, 12]))
+            .favorited_articles(Insert(vec![(1, 1), (2, 12)]))
             .tags(Migration)
             .article_tags(Migration)
             .build()
             .await?;
 
-        let expected = vec![];
-        let result =
-            get_articles_with_filters(&connection, None, None, None, Some(0), None, None).await?;
-        assert_eq!(result, expected);
+        let result = filtered_titles(&connection, Some("username1"), None).await?;
+        assert_eq!(result, vec!["title1"]);
+        let result = filtered_titles(&connection, None, Some("username1")).await?;
+        assert_eq!(result, vec!["title1"]);
+        let result = filtered_titles(&connection, Some("username1%"), Some("username1_")).await?;
+        assert!(result.is_empty());
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn offset_articles_pos() -> Result<(), TestErr> {
+    async fn filter_literal_percent_in_user_name() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2]))
+            .favorited_articles(Insert(vec![(1, 1), (2, 2)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+        let user = users.unwrap().into_iter().nth(1).unwrap();
+        let mut user_model: user::ActiveModel = user.into();
+        user_model.username = Set("user%name".to_owned());
+        User::update(user_model).exec(&connection).await?;
+
+        let result = filtered_titles(&connection, Some("user%name"), None).await?;
+        assert_eq!(result, vec!["title2"]);
+        let result = filtered_titles(&connection, None, Some("user%name")).await?;
+        assert_eq!(result, vec!["title2"]);
+        let result = filtered_titles(&connection, Some("user%"), None).await?;
+        assert!(result.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn filter_article_author_pos() -> Result<(), TestErr> {
         let (
             connection,
             TestData {
                 users, articles, ..
             },
         ) = TestDataBuilder::new()
-            .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1, 2, 1, 1]))
             .favorited_articles(Migration)
             .tags(Migration)
             .article_tags(Migration)
             .build()
             .await?;
 
-        let author = users.unwrap().into_iter().next().unwrap();
-        let expected: Vec<ArticleWithAuthor> = articles
-            .unwrap()
+        let author = users.unwrap().into_iter().nth(1).unwrap();
+        let article = articles.unwrap().into_iter().nth(2).unwrap();
+        let expected: Vec<ArticleWithAuthor> = [article]
             .into_iter()
-            .take(3)
-            .rev()
             .map(|artcl| ArticleWithAuthor {
                 slug: artcl.slug,
                 title: artcl.title,
                 description: artcl.description,
                 body: artcl.body,
+                author_id: artcl.author_id,
                 favorited: false,
                 favorites_count: 0,
                 author: Profile {
-                    username: author.username.clone(),
+                    username: "username2".to_owned(),
                     bio: author.bio.clone(),
                     image: author.image.clone(),
                     following: false,
                 },
                 created_at: artcl.created_at,
                 updated_at: artcl.updated_at,
+                published: artcl.published,
                 tag_list: vec![],
             })
             .collect();
 
-        let result =
-            get_articles_with_filters(&connection, None, None, None, None, Some(2), None).await?;
+        let result = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            Some(&"username2".to_owned()),
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            None,
+        )
+        .await?
+        .items;
+
         assert_eq!(result, expected);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn offset_articles_zero_val() -> Result<(), TestErr> {
+    async fn filter_article_author_neg() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let result = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            Some(&"username2".to_owned()),
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            None,
+        )
+        .await?
+        .items;
+
+        let expected = vec![];
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn filter_article_author_empty() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let result = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            Some(&"".to_owned()),
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            None,
+        )
+        .await?
+        .items;
+
+        let expected = vec![];
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn filter_article_user_who_liked_it_pos() -> Result<(), TestErr> {
         let (
             connection,
             TestData {
                 users, articles, ..
             },
         ) = TestDataBuilder::new()
-            .users(Insert(1))
+            .users(Insert(2))
             .articles(Insert(vec![1, 1, 1, 1, 1]))
-            .favorited_articles(Migration)
+            .favorited_articles(Insert(vec![(3, 2)]))
             .tags(Migration)
             .article_tags(Migration)
             .build()
             .await?;
 
         let author = users.unwrap().into_iter().next().unwrap();
-        let expected: Vec<ArticleWithAuthor> = articles
-            .unwrap()
+        let article = articles.unwrap().into_iter().nth(2).unwrap();
+        let expected: Vec<ArticleWithAuthor> = [article]
             .into_iter()
-            .rev()
             .map(|artcl| ArticleWithAuthor {
                 slug: artcl.slug,
                 title: artcl.title,
                 description: artcl.description,
                 body: artcl.body,
+                author_id: artcl.author_id,
                 favorited: false,
-                favorites_count: 0,
+                favorites_count: 1,
                 author: Profile {
                     username: author.username.clone(),
                     bio: author.bio.clone(),
@@ -1019,509 +2439,2165 @@ mod test_get_articles_with_filters {
                 },
                 created_at: artcl.created_at,
                 updated_at: artcl.updated_at,
+                published: artcl.published,
                 tag_list: vec![],
             })
             .collect();
 
-        let result =
-            get_articles_with_filters(&connection, None, None, None, None, Some(0), None).await?;
-        assert_eq!(result, expected);
-
-        Ok(())
-    }
-
-    #[tokio::test]
-    async fn articles_author_followed_by_current_user() -> Result<(), TestErr> {
-        let (connection, TestData { users, .. }) = TestDataBuilder::new()
-            .users(Insert(3))
-            .articles(Insert(vec![1, 2]))
-            .favorited_articles(Migration)
-            .followers(Insert(vec![(1, 3)]))
-            .tags(Migration)
-            .article_tags(Migration)
-            .build()
-            .await?;
-
-        let current_user = users.unwrap().into_iter().last().unwrap();
-
-        let mut result = get_articles_with_filters(
+        let result = get_articles_page(
             &connection,
+            &[],
+            TagMatch::All,
             None,
+            Some(&"username2".to_owned()),
             None,
+            ArticleSort::Updated,
+            20,
             None,
             None,
-            None,
-            Some(current_user.id),
         )
-        .await?;
-        result.reverse();
+        .await?
+        .items;
 
-        assert!(result[0].author.following);
-        assert!(!result[1].author.following);
+        assert_eq!(result, expected);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn articles_favorited_by_current_user() -> Result<(), TestErr> {
-        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+    async fn filter_article_user_who_liked_it_neg() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
             .users(Insert(2))
-            .articles(Insert(vec![1, 1]))
-            .favorited_articles(Insert(vec![(2, 2)]))
-            .followers(Migration)
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .favorited_articles(Insert(vec![(3, 2)]))
             .tags(Migration)
             .article_tags(Migration)
             .build()
             .await?;
 
-        let current_user = users.unwrap().into_iter().last().unwrap();
-
-        let mut result = get_articles_with_filters(
+        let result = get_articles_page(
             &connection,
+            &[],
+            TagMatch::All,
             None,
+            Some(&"username1".to_owned()),
             None,
+            ArticleSort::Updated,
+            20,
             None,
             None,
-            None,
-            Some(current_user.id),
         )
-        .await?;
-        result.reverse();
+        .await?
+        .items;
 
-        assert!(!result[0].favorited);
-        assert!(result[1].favorited);
+        let expected = vec![];
+        assert_eq!(result, expected);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn articles_favorited_count() -> Result<(), TestErr> {
+    async fn filter_article_user_who_liked_it_empty() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new()
-            .users(Insert(5))
-            .articles(Insert(vec![1, 1]))
-            .favorited_articles(Insert(vec![(1, 1), (1, 2), (1, 3), (1, 4), (1, 5)]))
-            .followers(Migration)
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .favorited_articles(Insert(vec![(3, 2)]))
             .tags(Migration)
             .article_tags(Migration)
             .build()
             .await?;
 
-        let mut result =
-            get_articles_with_filters(&connection, None, None, None, None, None, None).await?;
-        result.reverse();
+        let result = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            Some(&"".to_owned()),
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            None,
+        )
+        .await?
+        .items;
 
-        assert_eq!(result[0].favorites_count, 5);
-        assert_eq!(result[1].favorites_count, 0);
+        let expected = vec![];
+        assert_eq!(result, expected);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn articles_tag_list() -> Result<(), TestErr> {
-        let (connection, _) = TestDataBuilder::new()
+    async fn limit_articles_pos() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Insert(vec![1, 1]))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
             .favorited_articles(Migration)
-            .followers(Migration)
-            .tags(Insert(2))
-            .article_tags(Insert(vec![(1, 1), (1, 2)]))
+            .tags(Migration)
+            .article_tags(Migration)
             .build()
             .await?;
 
-        let mut result =
-            get_articles_with_filters(&connection, None, None, None, None, None, None).await?;
-        result.reverse();
-
-        let tags = &mut result[0].tag_list;
-        tags.sort();
+        let author = users.unwrap().into_iter().next().unwrap();
+        let expected: Vec<ArticleWithAuthor> = articles
+            .unwrap()
+            .into_iter()
+            .rev()
+            .take(2)
+            .map(|artcl| ArticleWithAuthor {
+                slug: artcl.slug,
+                title: artcl.title,
+                description: artcl.description,
+                body: artcl.body,
+                author_id: artcl.author_id,
+                favorited: false,
+                favorites_count: 0,
+                author: Profile {
+                    username: author.username.clone(),
+                    bio: author.bio.clone(),
+                    image: author.image.clone(),
+                    following: false,
+                },
+                created_at: artcl.created_at,
+                updated_at: artcl.updated_at,
+                published: artcl.published,
+                tag_list: vec![],
+            })
+            .collect();
 
-        assert_eq!(result[0].tag_list, vec!["tag_name1", "tag_name2"]);
-        assert_eq!(result[1].favorites_count, 0);
+        let result = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            2,
+            None,
+            None,
+        )
+        .await?
+        .items;
+        assert_eq!(result, expected);
 
         Ok(())
     }
-}
 
-#[cfg(test)]
-mod test_get_articles_feed {
-    use super::get_articles_feed;
-    use crate::repo::article::ArticleWithAuthor;
-    use crate::tests::{
-        Operation::{Insert, Migration},
-        TestData, TestDataBuilder, TestErr,
-    };
-    use std::vec;
+    #[tokio::test]
+    async fn limit_articles_zero_val() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let expected = vec![];
+        let result = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            0,
+            None,
+            None,
+        )
+        .await?
+        .items;
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
 
     #[tokio::test]
-    async fn get_followed_authors_articles() -> Result<(), TestErr> {
+    async fn offset_articles_pos() -> Result<(), TestErr> {
         let (
             connection,
             TestData {
                 users, articles, ..
             },
         ) = TestDataBuilder::new()
-            .users(Insert(5))
-            .articles(Insert(vec![1, 2, 2, 3, 4]))
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
             .favorited_articles(Migration)
-            .followers(Insert(vec![(1, 5), (2, 5), (3, 5)]))
             .tags(Migration)
             .article_tags(Migration)
             .build()
             .await?;
 
-        let current_user = users.unwrap().into_iter().last().unwrap();
-        let expected: Vec<String> = articles
+        let author = users.unwrap().into_iter().next().unwrap();
+        let expected: Vec<ArticleWithAuthor> = articles
             .unwrap()
             .into_iter()
-            .take(4)
-            .map(|mdl| mdl.title)
+            .take(3)
+            .rev()
+            .map(|artcl| ArticleWithAuthor {
+                slug: artcl.slug,
+                title: artcl.title,
+                description: artcl.description,
+                body: artcl.body,
+                author_id: artcl.author_id,
+                favorited: false,
+                favorites_count: 0,
+                author: Profile {
+                    username: author.username.clone(),
+                    bio: author.bio.clone(),
+                    image: author.image.clone(),
+                    following: false,
+                },
+                created_at: artcl.created_at,
+                updated_at: artcl.updated_at,
+                published: artcl.published,
+                tag_list: vec![],
+            })
             .collect();
 
-        let result = get_articles_feed(&connection, None, None, current_user.id).await?;
-        let result: Vec<String> = result.into_iter().rev().map(|mdl| mdl.title).collect();
+        let result = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            Some(2),
+            None,
+        )
+        .await?
+        .items;
+        assert_eq!(result, expected);
 
-        assert_eq!(expected, result);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn offset_articles_zero_val() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let author = users.unwrap().into_iter().next().unwrap();
+        let expected: Vec<ArticleWithAuthor> = articles
+            .unwrap()
+            .into_iter()
+            .rev()
+            .map(|artcl| ArticleWithAuthor {
+                slug: artcl.slug,
+                title: artcl.title,
+                description: artcl.description,
+                body: artcl.body,
+                author_id: artcl.author_id,
+                favorited: false,
+                favorites_count: 0,
+                author: Profile {
+                    username: author.username.clone(),
+                    bio: author.bio.clone(),
+                    image: author.image.clone(),
+                    following: false,
+                },
+                created_at: artcl.created_at,
+                updated_at: artcl.updated_at,
+                published: artcl.published,
+                tag_list: vec![],
+            })
+            .collect();
+
+        let result = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            Some(0),
+            None,
+        )
+        .await?
+        .items;
+        assert_eq!(result, expected);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn user_not_follows_any_other() -> Result<(), TestErr> {
+    async fn articles_author_followed_by_current_user() -> Result<(), TestErr> {
         let (connection, TestData { users, .. }) = TestDataBuilder::new()
             .users(Insert(3))
-            .articles(Insert(vec![1, 2, 2]))
+            .articles(Insert(vec![1, 2]))
             .favorited_articles(Migration)
-            .followers(Insert(vec![(1, 2), (2, 1)]))
+            .followers(Insert(vec![(1, 3)]))
             .tags(Migration)
             .article_tags(Migration)
             .build()
             .await?;
 
         let current_user = users.unwrap().into_iter().last().unwrap();
-        let expected: Vec<ArticleWithAuthor> = vec![];
 
-        let result = get_articles_feed(&connection, None, None, current_user.id).await?;
+        let mut result = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            Some(current_user.id),
+        )
+        .await?
+        .items;
+        result.reverse();
 
-        assert_eq!(expected, result);
+        assert!(result[0].author.following);
+        assert!(!result[1].author.following);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn limit_articles_pos() -> Result<(), TestErr> {
-        let (
-            connection,
-            TestData {
-                users, articles, ..
-            },
-        ) = TestDataBuilder::new()
-            .users(Insert(5))
-            .articles(Insert(vec![1, 2, 2, 3, 4]))
-            .favorited_articles(Migration)
-            .followers(Insert(vec![(1, 5), (2, 5), (3, 5)]))
+    async fn favorites_count_of_not_favorited() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(3))
+            .articles(Insert(vec![1, 1, 1, 1]))
+            .favorited_articles(Insert(vec![(1, 1), (1, 2), (1, 3), (3, 2)]))
+            .followers(Migration)
             .tags(Migration)
             .article_tags(Migration)
             .build()
             .await?;
 
-        let current_user = users.unwrap().into_iter().last().unwrap();
-        let expected: Vec<String> = articles.unwrap()[2..4]
-            .iter()
-            .rev()
-            .map(|mdl| &mdl.title)
-            .cloned()
+        let result = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            None,
+        )
+        .await?
+        .items;
+        let mut counts: Vec<(String, i32)> = result
+            .into_iter()
+            .map(|art| (art.slug, art.favorites_count))
             .collect();
-
-        let result = get_articles_feed(&connection, Some(2), None, current_user.id).await?;
-        let result: Vec<String> = result.iter().map(|mdl| &mdl.title).cloned().collect();
-
-        assert_eq!(expected, result);
+        counts.sort();
+
+        assert_eq!(
+            counts,
+            vec![
+                ("title1".to_owned(), 3),
+                ("title2".to_owned(), 0),
+                ("title3".to_owned(), 1),
+                ("title4".to_owned(), 0),
+            ]
+        );
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn limit_articles_zero_val() -> Result<(), TestErr> {
+    async fn own_articles_not_followed() -> Result<(), TestErr> {
         let (connection, TestData { users, .. }) = TestDataBuilder::new()
-            .users(Insert(5))
-            .articles(Insert(vec![1, 2, 2, 3, 4]))
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2]))
             .favorited_articles(Migration)
-            .followers(Insert(vec![(1, 5), (2, 5), (3, 5)]))
+            .followers(Insert(vec![(2, 1)]))
             .tags(Migration)
             .article_tags(Migration)
             .build()
             .await?;
 
-        let expected = vec![];
-        let current_user = users.unwrap().into_iter().last().unwrap();
-        let result = get_articles_feed(&connection, Some(0), None, current_user.id).await?;
-        assert_eq!(result, expected);
+        let current_user = users.unwrap().into_iter().next().unwrap();
+
+        let mut result = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            Some(current_user.id),
+        )
+        .await?
+        .items;
+        result.reverse();
+
+        assert!(!result[0].author.following);
+        assert!(result[1].author.following);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn offset_articles_pos() -> Result<(), TestErr> {
-        let (
-            connection,
-            TestData {
-                users, articles, ..
-            },
-        ) = TestDataBuilder::new()
-            .users(Insert(5))
-            .articles(Insert(vec![1, 2, 2, 3, 4]))
-            .favorited_articles(Migration)
-            .followers(Insert(vec![(1, 5), (2, 5), (3, 5)]))
+    async fn articles_favorited_by_current_user() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1]))
+            .favorited_articles(Insert(vec![(2, 2)]))
+            .followers(Migration)
             .tags(Migration)
             .article_tags(Migration)
             .build()
             .await?;
 
         let current_user = users.unwrap().into_iter().last().unwrap();
-        let expected: Vec<String> = articles
-            .unwrap()
-            .iter()
-            .take(2)
-            .rev()
-            .map(|mdl| &mdl.title)
-            .cloned()
-            .collect();
 
-        let result = get_articles_feed(&connection, None, Some(2), current_user.id).await?;
-        let result: Vec<String> = result.iter().map(|mdl| &mdl.title).cloned().collect();
+        let mut result = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            Some(current_user.id),
+        )
+        .await?
+        .items;
+        result.reverse();
+
+        assert!(!result[0].favorited);
+        assert!(result[1].favorited);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn articles_favorited_count() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(5))
+            .articles(Insert(vec![1, 1]))
+            .favorited_articles(Insert(vec![(1, 1), (1, 2), (1, 3), (1, 4), (1, 5)]))
+            .followers(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let mut result = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            None,
+        )
+        .await?
+        .items;
+        result.reverse();
+
+        assert_eq!(result[0].favorites_count, 5);
+        assert_eq!(result[1].favorites_count, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn articles_tag_list() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1]))
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (1, 2)]))
+            .build()
+            .await?;
+
+        let mut result = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            None,
+        )
+        .await?
+        .items;
+        result.reverse();
+
+        let tags = &mut result[0].tag_list;
+        tags.sort();
+
+        assert_eq!(result[0].tag_list, vec!["tag_name1", "tag_name2"]);
+        assert_eq!(result[1].favorites_count, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn filtered_page() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2, 1, 1, 2]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Insert(vec![(1, 1), (2, 1)]))
+            .followers(Migration)
+            .build()
+            .await?;
+        let author = "username1".to_owned();
+
+        let page = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            Some(&author),
+            None,
+            None,
+            ArticleSort::Created,
+            2,
+            Some(1),
+            None,
+        )
+        .await?;
+        assert_eq!((page.total, page.limit, page.offset), (3, 2, 1));
+        assert_eq!(page.items.len(), 2);
+        assert!(page.items.iter().all(|art| art.author.username == author));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn page_past_the_end() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let page = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            ArticleSort::Created,
+            20,
+            Some(5),
+            None,
+        )
+        .await?;
+        let expected = Page {
+            items: vec![],
+            total: 2,
+            limit: 20,
+            offset: 5,
+        };
+        assert_eq!(page, expected);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_article_sort {
+    use super::{get_articles_page, ArticleSort, TagMatch};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use chrono::Duration;
+    use entity::entities::article;
+    use sea_orm::{ActiveModelTrait, Set};
+    use std::vec;
+
+    #[tokio::test]
+    async fn updated_and_created_order() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        // Oldest article was updated most recently
+        let oldest = articles.unwrap().into_iter().next().unwrap();
+        let updated_at = oldest.updated_at.unwrap() + Duration::hours(1);
+        let mut model: article::ActiveModel = oldest.into();
+        model.updated_at = Set(Some(updated_at));
+        model.update(&connection).await?;
+
+        for (sort, expected) in [
+            (ArticleSort::Updated, ["title1", "title3", "title2"]),
+            (ArticleSort::Created, ["title3", "title2", "title1"]),
+        ] {
+            let result = get_articles_page(
+                &connection,
+                &[],
+                TagMatch::All,
+                None,
+                None,
+                None,
+                sort,
+                20,
+                None,
+                None,
+            )
+            .await?
+            .items;
+            let slugs: Vec<&str> = result.iter().map(|art| art.slug.as_str()).collect();
+            assert_eq!(slugs, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_sort() {
+        assert_eq!("updated".parse(), Ok(ArticleSort::Updated));
+        assert_eq!("created".parse(), Ok(ArticleSort::Created));
+        assert_eq!("title".parse::<ArticleSort>(), Err(()));
+    }
+}
+
+#[cfg(test)]
+mod test_get_articles_feed {
+    use super::{get_articles_feed, get_feed_count, TagMatch};
+    use crate::repo::article::ArticleWithAuthor;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use std::vec;
+
+    #[tokio::test]
+    async fn get_followed_authors_articles() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(5))
+            .articles(Insert(vec![1, 2, 2, 3, 4]))
+            .favorited_articles(Migration)
+            .followers(Insert(vec![(1, 5), (2, 5), (3, 5)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let current_user = users.unwrap().into_iter().last().unwrap();
+        let expected: Vec<String> = articles
+            .unwrap()
+            .into_iter()
+            .take(4)
+            .map(|mdl| mdl.title)
+            .collect();
+
+        let result =
+            get_articles_feed(&connection, &[], TagMatch::All, 20, None, current_user.id).await?;
+        let result: Vec<String> = result.into_iter().rev().map(|mdl| mdl.title).collect();
+
+        assert_eq!(expected, result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn user_not_follows_any_other() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(3))
+            .articles(Insert(vec![1, 2, 2]))
+            .favorited_articles(Migration)
+            .followers(Insert(vec![(1, 2), (2, 1)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let current_user = users.unwrap().into_iter().last().unwrap();
+        let expected: Vec<ArticleWithAuthor> = vec![];
+
+        let result =
+            get_articles_feed(&connection, &[], TagMatch::All, 20, None, current_user.id).await?;
+
+        assert_eq!(expected, result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn limit_articles_pos() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(5))
+            .articles(Insert(vec![1, 2, 2, 3, 4]))
+            .favorited_articles(Migration)
+            .followers(Insert(vec![(1, 5), (2, 5), (3, 5)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let current_user = users.unwrap().into_iter().last().unwrap();
+        let expected: Vec<String> = articles.unwrap()[2..4]
+            .iter()
+            .rev()
+            .map(|mdl| &mdl.title)
+            .cloned()
+            .collect();
+
+        let result =
+            get_articles_feed(&connection, &[], TagMatch::All, 2, None, current_user.id).await?;
+        let result: Vec<String> = result.iter().map(|mdl| &mdl.title).cloned().collect();
+
         assert_eq!(expected, result);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn offset_articles_zero_val() -> Result<(), TestErr> {
-        let (
-            connection,
-            TestData {
-                users, articles, ..
-            },
-        ) = TestDataBuilder::new()
-            .users(Insert(5))
-            .articles(Insert(vec![1, 2, 2, 3, 4]))
-            .favorited_articles(Migration)
-            .followers(Insert(vec![(1, 5), (2, 5), (3, 5)]))
-            .tags(Migration)
-            .article_tags(Migration)
+    async fn limit_articles_zero_val() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(5))
+            .articles(Insert(vec![1, 2, 2, 3, 4]))
+            .favorited_articles(Migration)
+            .followers(Insert(vec![(1, 5), (2, 5), (3, 5)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let expected = vec![];
+        let current_user = users.unwrap().into_iter().last().unwrap();
+        let result =
+            get_articles_feed(&connection, &[], TagMatch::All, 0, None, current_user.id).await?;
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn offset_articles_pos() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(5))
+            .articles(Insert(vec![1, 2, 2, 3, 4]))
+            .favorited_articles(Migration)
+            .followers(Insert(vec![(1, 5), (2, 5), (3, 5)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let current_user = users.unwrap().into_iter().last().unwrap();
+        let expected: Vec<String> = articles
+            .unwrap()
+            .iter()
+            .take(2)
+            .rev()
+            .map(|mdl| &mdl.title)
+            .cloned()
+            .collect();
+
+        let result = get_articles_feed(
+            &connection,
+            &[],
+            TagMatch::All,
+            20,
+            Some(2),
+            current_user.id,
+        )
+        .await?;
+        let result: Vec<String> = result.iter().map(|mdl| &mdl.title).cloned().collect();
+        assert_eq!(expected, result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn offset_articles_zero_val() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(5))
+            .articles(Insert(vec![1, 2, 2, 3, 4]))
+            .favorited_articles(Migration)
+            .followers(Insert(vec![(1, 5), (2, 5), (3, 5)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let expected: Vec<String> = articles
+            .unwrap()
+            .iter()
+            .take(4)
+            .rev()
+            .map(|mdl| &mdl.title)
+            .cloned()
+            .collect();
+        let current_user = users.unwrap().into_iter().last().unwrap();
+        let result = get_articles_feed(
+            &connection,
+            &[],
+            TagMatch::All,
+            20,
+            Some(0),
+            current_user.id,
+        )
+        .await?;
+        let result: Vec<String> = result.iter().map(|mdl| &mdl.title).cloned().collect();
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn filter_by_tag_with_count() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(3))
+            .articles(Insert(vec![1, 1, 2, 2, 3]))
+            .favorited_articles(Migration)
+            .followers(Insert(vec![(1, 3), (2, 3)]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (2, 2), (3, 1), (5, 1)]))
+            .build()
+            .await?;
+
+        let current_user = users.unwrap().into_iter().last().unwrap();
+        let tags = vec!["tag_name1".to_owned()];
+
+        let result =
+            get_articles_feed(&connection, &tags, TagMatch::All, 20, None, current_user.id).await?;
+        let titles: Vec<String> = result.into_iter().map(|mdl| mdl.title).collect();
+        assert_eq!(titles, ["title3", "title1"]);
+
+        let count = get_feed_count(&connection, &tags, TagMatch::All, current_user.id).await?;
+        assert_eq!(count, 2);
+        let count = get_feed_count(&connection, &[], TagMatch::All, current_user.id).await?;
+        assert_eq!(count, 4);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_trending_articles {
+    use super::get_trending_articles;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use chrono::{Duration, Utc};
+    use entity::entities::{favorited_article, prelude::FavoritedArticle};
+    use migration::Expr;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+    use std::vec;
+
+    #[tokio::test]
+    async fn recent_favorites_outrank_older() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(3))
+            .articles(Insert(vec![1, 1, 1]))
+            .favorited_articles(Insert(vec![(1, 1), (1, 2), (1, 3), (2, 1), (3, 1), (3, 2)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let now = Utc::now().naive_utc();
+
+        // All favorites of first article made a month ago, one of third article too
+        let first_id = articles.as_ref().unwrap()[0].id;
+        let third_id = articles.as_ref().unwrap()[2].id;
+        let month_ago = now - Duration::days(30);
+        FavoritedArticle::update_many()
+            .col_expr(favorited_article::Column::CreatedAt, Expr::value(month_ago))
+            .filter(
+                favorited_article::Column::ArticleId.eq(first_id).or(
+                    favorited_article::Column::ArticleId
+                        .eq(third_id)
+                        .and(favorited_article::Column::UserId.eq(users.unwrap()[0].id)),
+                ),
+            )
+            .exec(&connection)
+            .await?;
+
+        let since = now - Duration::days(7);
+        let result = get_trending_articles(&connection, since, 20, None).await?;
+        let slugs: Vec<&str> = result.iter().map(|art| art.slug.as_str()).collect();
+
+        // Third article has one recent favorite, like second one, but updated more recently
+        assert_eq!(slugs, ["title3", "title2"]);
+        assert_eq!(result[0].favorites_count, 2);
+
+        let result = get_trending_articles(&connection, month_ago, 1, None).await?;
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].slug, "title1");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_articles_after {
+    use super::{create_article, get_articles_after, TagMatch};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use chrono::Duration;
+    use std::collections::HashSet;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn walk_pages_without_overlap() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2, 1, 2, 1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let mut cursor = None;
+        let mut titles: Vec<String> = vec![];
+        let mut pages = 0;
+        loop {
+            let page = get_articles_after(
+                &connection,
+                &[],
+                TagMatch::All,
+                None,
+                None,
+                None,
+                cursor,
+                2,
+                None,
+            )
+            .await?;
+            assert!(page.items.len() <= 2);
+            titles.extend(page.items.into_iter().map(|artcl| artcl.title));
+            pages += 1;
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        assert_eq!(pages, 3);
+        assert_eq!(titles.len(), 5);
+        let unique: HashSet<String> = titles.into_iter().collect();
+        let expected: HashSet<String> = articles
+            .unwrap()
+            .into_iter()
+            .map(|artcl| artcl.title)
+            .collect();
+        assert_eq!(unique, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_between_pages() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let first_page = get_articles_after(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            None,
+            2,
+            None,
+        )
+        .await?;
+
+        // Newer article does not shift next page
+        let mut newest = articles.unwrap().into_iter().next().unwrap();
+        newest.id = Uuid::new_v4();
+        newest.slug = "newest".to_owned();
+        newest.title = "newest".to_owned();
+        newest.updated_at = newest.updated_at.map(|time| time + Duration::hours(1));
+        create_article(&connection, newest.into()).await?;
+
+        let second_page = get_articles_after(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            first_page.next_cursor,
+            2,
+            None,
+        )
+        .await?;
+
+        assert_eq!(first_page.items.len(), 2);
+        assert_eq!(second_page.items.len(), 1);
+        assert!(first_page
+            .items
+            .iter()
+            .all(|first| first.slug != second_page.items[0].slug));
+        assert_eq!(second_page.next_cursor, None);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_articles_count {
+    use super::{get_articles_count, get_articles_page, get_feed_count, ArticleSort, TagMatch};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use std::vec;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn count_articles() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(5))
+            .articles(Insert(vec![1, 2, 2, 3, 4]))
+            .favorited_articles(Insert(vec![(1, 1), (2, 2), (3, 2)]))
+            .followers(Insert(vec![(1, 5), (2, 5), (3, 5)]))
+            .tags(Insert(3))
+            .article_tags(Insert(vec![(1, 1), (1, 2), (2, 2)]))
+            .build()
+            .await?;
+        let current_user = users.unwrap().into_iter().last().unwrap();
+
+        let result =
+            get_articles_count(&connection, &[], TagMatch::All, None, None, None, None).await?;
+        assert_eq!(result, 5);
+        let result = get_articles_count(
+            &connection,
+            &["tag_name2".to_owned()],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await?;
+        assert_eq!(result, 2);
+        let result = get_articles_count(
+            &connection,
+            &["not_exist".to_owned()],
+            TagMatch::All,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await?;
+        assert_eq!(result, 0);
+        let tags = ["tag_name1".to_owned(), "tag_name2".to_owned()];
+        let result =
+            get_articles_count(&connection, &tags, TagMatch::All, None, None, None, None).await?;
+        assert_eq!(result, 1);
+        let result =
+            get_articles_count(&connection, &tags, TagMatch::Any, None, None, None, None).await?;
+        assert_eq!(result, 2);
+        let result = get_articles_count(
+            &connection,
+            &[],
+            TagMatch::All,
+            Some(&"username2".to_owned()),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        assert_eq!(result, 2);
+        let result = get_articles_count(
+            &connection,
+            &[],
+            TagMatch::All,
+            Some(&"not_exist".to_owned()),
+            None,
+            None,
+            None,
+        )
+        .await?;
+        assert_eq!(result, 0);
+        let result = get_articles_count(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            Some(&"username2".to_owned()),
+            None,
+            None,
+        )
+        .await?;
+        assert_eq!(result, 2);
+        let result = get_articles_count(
+            &connection,
+            &[],
+            TagMatch::All,
+            None,
+            Some(&"not_exist".to_owned()),
+            None,
+            None,
+        )
+        .await?;
+        assert_eq!(result, 0);
+        let result = get_feed_count(&connection, &[], TagMatch::All, current_user.id).await?;
+        assert_eq!(result, 4);
+        let result = get_feed_count(&connection, &[], TagMatch::All, Uuid::new_v4()).await?;
+        assert_eq!(result, 0);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn page_never_exceeds_count() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1, 2, 1, 2, 1]))
+            .favorited_articles(Migration)
+            .followers(Insert(vec![(1, 2)]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (2, 1), (3, 1), (4, 2), (6, 1), (6, 2)]))
+            .build()
+            .await?;
+        let users = users.unwrap();
+        let tag_names = ["tag_name1".to_owned()];
+        let author_name = "username1".to_owned();
+
+        for viewer in [None, Some(users[0].id), Some(users[1].id)] {
+            let count = get_articles_count(
+                &connection,
+                &tag_names,
+                TagMatch::All,
+                Some(&author_name),
+                None,
+                None,
+                viewer,
+            )
+            .await?;
+            assert_eq!(count, 3);
+
+            for limit in [1, 2, 3, 10] {
+                let page = get_articles_page(
+                    &connection,
+                    &tag_names,
+                    TagMatch::All,
+                    Some(&author_name),
+                    None,
+                    None,
+                    ArticleSort::Updated,
+                    limit,
+                    None,
+                    viewer,
+                )
+                .await?;
+                assert!(page.items.len() as u64 <= count);
+                assert_eq!(page.total, count);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_article_by_slug {
+    use super::get_article_by_slug;
+    use crate::repo::{article::ArticleWithAuthor, user::Profile};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use std::vec;
+
+    #[tokio::test]
+    async fn get_existing_article() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .favorited_articles(Migration)
+            .tags(Insert(1))
+            .article_tags(Insert(vec![(3, 1)]))
+            .build()
+            .await?;
+
+        let author = users.unwrap().into_iter().next().unwrap();
+        let article = articles.unwrap().into_iter().nth(2).unwrap();
+        let expected = ArticleWithAuthor {
+            slug: article.slug,
+            title: article.title,
+            description: article.description,
+            body: article.body,
+            author_id: article.author_id,
+            favorited: false,
+            favorites_count: 0,
+            author: Profile {
+                username: author.username.clone(),
+                bio: author.bio.clone(),
+                image: author.image.clone(),
+                following: false,
+            },
+            created_at: article.created_at,
+            updated_at: article.updated_at,
+            published: article.published,
+            tag_list: vec!["tag_name1".to_owned()],
+        };
+
+        let result = get_article_by_slug(&connection, "title3", None).await?;
+        assert_eq!(result, Some(expected));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn none_existing_slug() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let result = get_article_by_slug(&connection, "not_exist", None).await?;
+        let expected = None;
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn own_article_not_followed() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+        let author = users.unwrap().into_iter().next().unwrap();
+
+        let result = get_article_by_slug(&connection, "title1", Some(author.id)).await?;
+        assert!(!result.unwrap().author.following);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_article_by_id {
+    use super::get_article_by_id;
+    use crate::repo::{article::ArticleWithAuthor, user::Profile};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use std::vec;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn get_existing_article() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .favorited_articles(Migration)
+            .tags(Insert(1))
+            .article_tags(Insert(vec![(3, 1)]))
+            .build()
+            .await?;
+
+        let author = users.unwrap().into_iter().next().unwrap();
+        let article = articles.unwrap().into_iter().nth(2).unwrap();
+        let expected = ArticleWithAuthor {
+            slug: article.slug,
+            title: article.title,
+            description: article.description,
+            body: article.body,
+            author_id: article.author_id,
+            favorited: false,
+            favorites_count: 0,
+            author: Profile {
+                username: author.username.clone(),
+                bio: author.bio.clone(),
+                image: author.image.clone(),
+                following: false,
+            },
+            created_at: article.created_at,
+            updated_at: article.updated_at,
+            published: article.published,
+            tag_list: vec!["tag_name1".to_owned()],
+        };
+
+        let result = get_article_by_id(&connection, article.id, None).await?;
+        assert_eq!(result, Some(expected));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn none_existing_id() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let result = get_article_by_id(&connection, Uuid::new_v4(), None).await?;
+        let expected = None;
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_article_model_by_slug {
+    use super::get_article_model_by_slug;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use entity::entities::article;
+    use sea_orm::{ActiveModelTrait, Set};
+    use std::vec;
+
+    #[tokio::test]
+    async fn get_existing_article() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let expected = articles.unwrap().into_iter().nth(2).unwrap();
+        let result = get_article_model_by_slug(&connection, "title3", None).await?;
+        assert_eq!(result, Some(expected));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn none_existing_slug() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        let result = get_article_model_by_slug(&connection, "not_exist", None).await?;
+        let expected = None;
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn draft_found_only_for_author() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+        let draft = articles.unwrap().remove(0);
+        let mut model: article::ActiveModel = draft.into();
+        model.published = Set(false);
+        model.update(&connection).await?;
+        let users = users.unwrap();
+
+        let result = get_article_model_by_slug(&connection, "title1", Some(users[0].id)).await?;
+        assert!(result.is_some());
+        let result = get_article_model_by_slug(&connection, "title1", Some(users[1].id)).await?;
+        assert!(result.is_none());
+        let result = get_article_model_by_slug(&connection, "title1", None).await?;
+        assert!(result.is_none());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_all_article_slugs {
+    use super::get_all_article_slugs;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+
+    #[tokio::test]
+    async fn get_existing_slugs() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1]))
+            .build()
+            .await?;
+        let mut expected: Vec<_> = articles
+            .unwrap()
+            .into_iter()
+            .map(|art| (art.slug, art.updated_at))
+            .collect();
+        expected.sort();
+
+        let result = get_all_article_slugs(&connection, 20, None).await?;
+
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn limit_and_offset() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1]))
+            .build()
+            .await?;
+
+        let result = get_all_article_slugs(&connection, 1, Some(1)).await?;
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].0, "title2");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn no_articles() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Migration)
+            .articles(Migration)
+            .build()
+            .await?;
+
+        let result = get_all_article_slugs(&connection, 20, None).await?;
+
+        assert!(result.is_empty());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_create_article {
+    use super::create_article;
+    use crate::tests::{
+        Operation::{Create, Insert},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use entity::entities::{article, prelude::Article};
+    use sea_orm::Set;
+
+    #[tokio::test]
+    async fn insert_not_exist_data() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Create(vec![1]))
+            .build()
+            .await?;
+        let id = articles.as_ref().unwrap().iter().next().unwrap().id;
+        let actives = TestDataBuilder::activate_models::<Article, article::ActiveModel>(&articles);
+        let model = actives.into_iter().next().unwrap();
+
+        let insert_result = create_article(&connection, model).await?;
+        assert_eq!(insert_result.last_insert_id, id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_existing_id() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                articles: inserted, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .build()
+            .await?;
+        let (_, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Create(2))
+            .articles(Create(vec![2, 2]))
+            .build()
+            .await?;
+
+        let inserted_id = inserted.unwrap().into_iter().next().unwrap().id;
+        let second_article = articles.unwrap().into_iter().nth(1).unwrap();
+        let model2 = article::ActiveModel {
+            id: Set(inserted_id),
+            ..second_article.into()
+        };
+
+        let insert_result = create_article(&connection, model2).await;
+
+        assert!(insert_result.is_err_and(|err| err
+            .to_string()
+            .ends_with("UNIQUE constraint failed: article.id")));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_existing_slug() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                articles: inserted, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .build()
+            .await?;
+        let (_, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Create(2))
+            .articles(Create(vec![2, 2]))
+            .build()
+            .await?;
+
+        let inserted_slug = inserted.unwrap().into_iter().next().unwrap().slug;
+        let second_article = articles.unwrap().into_iter().nth(1).unwrap();
+        let model2 = article::ActiveModel {
+            slug: Set(inserted_slug),
+            ..second_article.into()
+        };
+
+        let insert_result = create_article(&connection, model2).await;
+
+        assert!(insert_result.is_err_and(|err| err
+            .to_string()
+            .ends_with("UNIQUE constraint failed: article.slug")));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_empty_slug() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Create(vec![1]))
+            .build()
+            .await?;
+        let created = articles.unwrap().into_iter().next().unwrap();
+
+        let model = article::ActiveModel {
+            slug: Set("".to_owned()),
+            ..created.into()
+        };
+
+        let insert_result = create_article(&connection, model).await;
+
+        assert!(insert_result
+            .is_err_and(|err| err.to_string().ends_with("CHECK constraint failed: slug")));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_empty_title() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Create(vec![1]))
+            .build()
+            .await?;
+        let created = articles.unwrap().into_iter().next().unwrap();
+
+        let model = article::ActiveModel {
+            title: Set("".to_owned()),
+            ..created.into()
+        };
+
+        let insert_result = create_article(&connection, model).await;
+
+        assert!(insert_result
+            .is_err_and(|err| err.to_string().ends_with("CHECK constraint failed: title")));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_empty_description() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Create(vec![1]))
+            .build()
+            .await?;
+        let created = articles.unwrap().into_iter().next().unwrap();
+
+        let model = article::ActiveModel {
+            description: Set("".to_owned()),
+            ..created.into()
+        };
+
+        let insert_result = create_article(&connection, model).await;
+
+        assert!(insert_result.is_err_and(|err| err
+            .to_string()
+            .ends_with("CHECK constraint failed: description")));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_empty_body() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Create(vec![1]))
+            .build()
+            .await?;
+        let created = articles.unwrap().into_iter().next().unwrap();
+
+        let model = article::ActiveModel {
+            body: Set("".to_owned()),
+            ..created.into()
+        };
+
+        let insert_result = create_article(&connection, model).await;
+
+        assert!(insert_result
+            .is_err_and(|err| err.to_string().ends_with("CHECK constraint failed: body")));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_patch_article {
+    use super::{patch_article, ArticlePatch};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use chrono::Utc;
+    use entity::entities::{article, prelude::Article};
+    use sea_orm::EntityTrait;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn patch_only_body() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1]))
+            .build()
+            .await?;
+        let model = articles.unwrap().into_iter().nth(1).unwrap();
+
+        let patch = ArticlePatch {
+            body: Some("new body".to_owned()),
+            ..Default::default()
+        };
+        let result = patch_article(&connection, model.id, patch).await?;
+
+        let expected = article::Model {
+            body: "new body".to_owned(),
+            ..model
+        };
+        assert_eq!(result, expected);
+        let stored = Article::find_by_id(expected.id).one(&connection).await?;
+        assert_eq!(stored, Some(expected));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn patch_body_and_description() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .build()
+            .await?;
+        let model = articles.unwrap().into_iter().nth(3).unwrap();
+
+        let patch = ArticlePatch {
+            body: Some("body".to_owned()),
+            description: Some("description".to_owned()),
+            ..Default::default()
+        };
+        let result = patch_article(&connection, model.id, patch).await?;
+
+        let expected = article::Model {
+            body: "body".to_owned(),
+            description: "description".to_owned(),
+            ..model
+        };
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn patch_title_slug_and_time() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .build()
+            .await?;
+        let model = articles.unwrap().into_iter().next().unwrap();
+        let time = Utc::now().naive_utc();
+
+        let patch = ArticlePatch {
+            title: Some("new title".to_owned()),
+            slug: Some("new-title".to_owned()),
+            updated_at: Some(time),
+            ..Default::default()
+        };
+        let result = patch_article(&connection, model.id, patch).await?;
+
+        let expected = article::Model {
+            title: "new title".to_owned(),
+            slug: "new-title".to_owned(),
+            updated_at: Some(time),
+            ..model
+        };
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn empty_patch_keeps_article() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .build()
+            .await?;
+        let model = articles.unwrap().into_iter().next().unwrap();
+
+        let result = patch_article(&connection, model.id, ArticlePatch::default()).await?;
+        assert_eq!(result, model);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn patch_not_existing_article() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Migration)
+            .articles(Migration)
+            .build()
+            .await?;
+
+        let patch = ArticlePatch {
+            body: Some("body".to_owned()),
+            ..Default::default()
+        };
+        let result = patch_article(&connection, Uuid::new_v4(), patch).await;
+        assert!(
+            result.is_err_and(|err| err.to_string().ends_with("None of the records are updated"))
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_transfer_article {
+    use super::{get_articles_page, transfer_article, ArticleSort, TagMatch};
+    use crate::repo::user_cache::UserCache;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use sea_orm::DbErr;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn transfer_to_existing_author() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1]))
+            .favorited_articles(Migration)
+            .followers(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+        let new_author = users.unwrap().into_iter().nth(1).unwrap();
+        let article = articles.unwrap().into_iter().next().unwrap();
+
+        let result = transfer_article(
+            &connection,
+            &UserCache::default(),
+            article.id,
+            new_author.id,
+        )
+        .await?;
+        assert_eq!(result.author_id, new_author.id);
+        assert_eq!(result.slug, article.slug);
+        assert_ne!(result.updated_at, article.updated_at);
+
+        let listed = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::All,
+            Some(&new_author.username),
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            None,
+        )
+        .await?
+        .items;
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].slug, article.slug);
+        assert_eq!(listed[0].author.username, new_author.username);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn transfer_to_not_existing_author() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
             .build()
             .await?;
+        let article = articles.unwrap().into_iter().next().unwrap();
 
-        let expected: Vec<String> = articles
-            .unwrap()
-            .iter()
-            .take(4)
-            .rev()
-            .map(|mdl| &mdl.title)
-            .cloned()
-            .collect();
-        let current_user = users.unwrap().into_iter().last().unwrap();
-        let result = get_articles_feed(&connection, None, Some(0), current_user.id).await?;
-        let result: Vec<String> = result.iter().map(|mdl| &mdl.title).cloned().collect();
-        assert_eq!(result, expected);
+        let result = transfer_article(
+            &connection,
+            &UserCache::default(),
+            article.id,
+            Uuid::new_v4(),
+        )
+        .await;
+        assert!(matches!(result, Err(DbErr::RecordNotFound(_))));
 
         Ok(())
     }
-}
-
-#[cfg(test)]
-mod test_get_articles_count {
-    use super::get_articles_count;
-    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
-    use std::vec;
-    use uuid::Uuid;
 
     #[tokio::test]
-    async fn count_articles() -> Result<(), TestErr> {
+    async fn transfer_not_existing_article() -> Result<(), TestErr> {
         let (connection, TestData { users, .. }) = TestDataBuilder::new()
-            .users(Insert(5))
-            .articles(Insert(vec![1, 2, 2, 3, 4]))
-            .favorited_articles(Insert(vec![(1, 1), (2, 2), (3, 2)]))
-            .followers(Insert(vec![(1, 5), (2, 5), (3, 5)]))
-            .tags(Insert(3))
-            .article_tags(Insert(vec![(1, 1), (1, 2), (2, 2)]))
+            .users(Insert(1))
+            .articles(Migration)
             .build()
             .await?;
-        let current_user = users.unwrap().into_iter().last().unwrap();
+        let user = users.unwrap().into_iter().next().unwrap();
 
-        let result = get_articles_count(&connection, None, None, None, None).await?;
-        assert_eq!(result, 5);
-        let result =
-            get_articles_count(&connection, Some(&"tag_name2".to_owned()), None, None, None)
-                .await?;
-        assert_eq!(result, 2);
-        let result =
-            get_articles_count(&connection, Some(&"not_exist".to_owned()), None, None, None)
-                .await?;
-        assert_eq!(result, 0);
-        let result =
-            get_articles_count(&connection, None, Some(&"username2".to_owned()), None, None)
-                .await?;
-        assert_eq!(result, 2);
-        let result =
-            get_articles_count(&connection, None, Some(&"not_exist".to_owned()), None, None)
-                .await?;
-        assert_eq!(result, 0);
         let result =
-            get_articles_count(&connection, None, None, Some(&"username2".to_owned()), None)
-                .await?;
-        assert_eq!(result, 2);
-        let result =
-            get_articles_count(&connection, None, None, Some(&"not_exist".to_owned()), None)
-                .await?;
-        assert_eq!(result, 0);
-        let result =
-            get_articles_count(&connection, None, None, None, Some(current_user.id)).await?;
-        assert_eq!(result, 4);
-        let result =
-            get_articles_count(&connection, None, None, None, Some(Uuid::new_v4())).await?;
-        assert_eq!(result, 0);
+            transfer_article(&connection, &UserCache::default(), Uuid::new_v4(), user.id).await;
+        assert_eq!(result, Err(DbErr::RecordNotUpdated));
 
         Ok(())
     }
 }
 
 #[cfg(test)]
-mod test_get_article_by_slug {
-    use super::get_article_by_slug;
-    use crate::repo::{article::ArticleWithAuthor, user::Profile};
+mod test_delete_article {
+    use super::{
+        delete_article, get_article_by_id, get_article_by_slug, get_article_model_by_slug,
+        get_articles_feed, get_articles_page, get_own_articles, ArticleSort, TagMatch,
+    };
     use crate::tests::{
+        execute_migration,
         Operation::{Insert, Migration},
         TestData, TestDataBuilder, TestErr,
     };
-    use std::vec;
+    use entity::entities::{
+        article,
+        prelude::{Article, ArticleTag, Comment, FavoritedArticle, Reaction},
+        reaction,
+    };
+    use sea_orm::{EntityTrait, Set};
 
     #[tokio::test]
-    async fn get_existing_article() -> Result<(), TestErr> {
+    async fn delete_existing_data() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2]))
+            .comments(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
+            .build()
+            .await?;
+        execute_migration(&connection, "m20231201_000009_create_reaction_table").await?;
+        let actives = TestDataBuilder::activate_models::<Article, article::ActiveModel>(&articles);
+        let model = actives.into_iter().next().unwrap();
+
+        let delete_result = delete_article(&connection, model).await?;
+        assert_eq!(delete_result.rows_affected, 1_u64);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn delete_already_deleted() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .build()
+            .await?;
+        let actives = TestDataBuilder::activate_models::<Article, article::ActiveModel>(&articles);
+        let model = actives.into_iter().next().unwrap();
+
+        delete_article(&connection, model.clone()).await?;
+        let delete_result = delete_article(&connection, model).await?;
+        assert_eq!(delete_result.rows_affected, 0_u64);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn row_and_dependents_persist() -> Result<(), TestErr> {
         let (
             connection,
             TestData {
                 users, articles, ..
             },
         ) = TestDataBuilder::new()
-            .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
-            .favorited_articles(Migration)
-            .tags(Insert(1))
-            .article_tags(Insert(vec![(3, 1)]))
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2]))
+            .comments(Insert(vec![(1, 1), (2, 1), (2, 2)]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (1, 2), (2, 1)]))
+            .favorited_articles(Insert(vec![(1, 1), (1, 2), (2, 1)]))
             .build()
             .await?;
+        execute_migration(&connection, "m20231201_000009_create_reaction_table").await?;
+        let deleted_id = articles.as_ref().unwrap()[0].id;
+        for article in articles.as_ref().unwrap() {
+            let reaction_model = reaction::ActiveModel {
+                article_id: Set(article.id),
+                user_id: Set(users.as_ref().unwrap()[0].id),
+                kind: Set("clap".to_owned()),
+            };
+            Reaction::insert(reaction_model).exec(&connection).await?;
+        }
+        let actives = TestDataBuilder::activate_models::<Article, article::ActiveModel>(&articles);
+        let model = actives.into_iter().next().unwrap();
 
-        let author = users.unwrap().into_iter().next().unwrap();
-        let article = articles.unwrap().into_iter().nth(2).unwrap();
-        let expected = ArticleWithAuthor {
-            slug: article.slug,
-            title: article.title,
-            description: article.description,
-            body: article.body,
-            favorited: false,
-            favorites_count: 0,
-            author: Profile {
-                username: author.username.clone(),
-                bio: author.bio.clone(),
-                image: author.image.clone(),
-                following: false,
-            },
-            created_at: article.created_at,
-            updated_at: article.updated_at,
-            tag_list: vec!["tag_name1".to_owned()],
-        };
+        let delete_result = delete_article(&connection, model).await?;
+        assert_eq!(delete_result.rows_affected, 1_u64);
 
-        let result = get_article_by_slug(&connection, "title3", None).await?;
-        assert_eq!(result, Some(expected));
+        let stored = Article::find_by_id(deleted_id).one(&connection).await?;
+        assert!(stored.unwrap().deleted_at.is_some());
+        assert_eq!(ArticleTag::find().all(&connection).await?.len(), 3);
+        assert_eq!(FavoritedArticle::find().all(&connection).await?.len(), 3);
+        assert_eq!(Comment::find().all(&connection).await?.len(), 3);
+        assert_eq!(Reaction::find().all(&connection).await?.len(), 2);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn none_existing_slug() -> Result<(), TestErr> {
-        let (connection, _) = TestDataBuilder::new()
-            .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
+    async fn deleted_hidden_from_reads() -> Result<(), TestErr> {
+        // user2 follows user1, so article 1 of user1 is in the feed of user2
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .followers(Insert(vec![(1, 2)]))
+            .articles(Insert(vec![1, 2]))
             .favorited_articles(Migration)
             .tags(Migration)
             .article_tags(Migration)
             .build()
             .await?;
+        let article = articles.as_ref().unwrap()[0].clone();
+        let author_id = users.as_ref().unwrap()[0].id;
+        let reader_id = users.as_ref().unwrap()[1].id;
+        let actives = TestDataBuilder::activate_models::<Article, article::ActiveModel>(&articles);
+        let model = actives.into_iter().next().unwrap();
 
-        let result = get_article_by_slug(&connection, "not_exist", None).await?;
-        let expected = None;
-        assert_eq!(result, expected);
+        delete_article(&connection, model).await?;
+
+        let listed = get_articles_page(
+            &connection,
+            &[],
+            TagMatch::Any,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            Some(author_id),
+        )
+        .await?
+        .items;
+        assert_eq!(listed.len(), 1);
+        assert!(listed.iter().all(|art| art.slug != article.slug));
+        let feed = get_articles_feed(&connection, &[], TagMatch::Any, 20, None, reader_id).await?;
+        assert!(feed.is_empty());
+        let own = get_own_articles(&connection, author_id, 20, None).await?;
+        assert!(own.is_empty());
+        assert!(
+            get_article_by_slug(&connection, &article.slug, Some(author_id))
+                .await?
+                .is_none()
+        );
+        assert!(get_article_by_id(&connection, article.id, Some(author_id))
+            .await?
+            .is_none());
+        assert!(
+            get_article_model_by_slug(&connection, &article.slug, Some(author_id))
+                .await?
+                .is_none()
+        );
 
         Ok(())
     }
 }
 
 #[cfg(test)]
-mod test_get_article_by_id {
-    use super::get_article_by_id;
-    use crate::repo::{article::ArticleWithAuthor, user::Profile};
+#[cfg(feature = "seed")]
+mod test_empty_article_table {
+    use super::empty_article_table;
     use crate::tests::{
         Operation::{Insert, Migration},
-        TestData, TestDataBuilder, TestErr,
+        TestDataBuilder, TestErr,
     };
-    use std::vec;
-    use uuid::Uuid;
+    use entity::entities::{article, prelude::Article};
+    use sea_orm::EntityTrait;
 
     #[tokio::test]
-    async fn get_existing_article() -> Result<(), TestErr> {
-        let (
-            connection,
-            TestData {
-                users, articles, ..
-            },
-        ) = TestDataBuilder::new()
+    async fn delete_existing_articles() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
             .users(Insert(1))
             .articles(Insert(vec![1, 1, 1, 1, 1]))
-            .favorited_articles(Migration)
-            .tags(Insert(1))
-            .article_tags(Insert(vec![(3, 1)]))
             .build()
             .await?;
 
-        let author = users.unwrap().into_iter().next().unwrap();
-        let article = articles.unwrap().into_iter().nth(2).unwrap();
-        let expected = ArticleWithAuthor {
-            slug: article.slug,
-            title: article.title,
-            description: article.description,
-            body: article.body,
-            favorited: false,
-            favorites_count: 0,
-            author: Profile {
-                username: author.username.clone(),
-                bio: author.bio.clone(),
-                image: author.image.clone(),
-                following: false,
-            },
-            created_at: article.created_at,
-            updated_at: article.updated_at,
-            tag_list: vec!["tag_name1".to_owned()],
-        };
+        let delete_result = empty_article_table(&connection).await?;
+        assert_eq!(delete_result.rows_affected, 5_u64);
 
-        let result = get_article_by_id(&connection, article.id, None).await?;
-        assert_eq!(result, Some(expected));
+        let expected: Vec<article::Model> = Vec::new();
+        let result = Article::find().all(&connection).await?;
+        assert_eq!(result, expected);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn none_existing_id() -> Result<(), TestErr> {
+    async fn delete_empty_table() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new()
-            .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
-            .favorited_articles(Migration)
-            .tags(Migration)
-            .article_tags(Migration)
+            .users(Migration)
+            .articles(Migration)
             .build()
             .await?;
 
-        let result = get_article_by_id(&connection, Uuid::new_v4(), None).await?;
-        let expected = None;
+        let delete_result = empty_article_table(&connection).await?;
+        assert_eq!(delete_result.rows_affected, 0_u64);
+
+        let expected: Vec<article::Model> = Vec::new();
+        let result = Article::find().all(&connection).await?;
         assert_eq!(result, expected);
 
         Ok(())
@@ -1529,365 +4605,421 @@ mod test_get_article_by_id {
 }
 
 #[cfg(test)]
-mod test_get_article_model_by_slug {
-    use super::get_article_model_by_slug;
+mod test_get_recently_tagged_articles {
+    use super::get_recently_tagged_articles;
+    use crate::repo::article_tag::insert_article_tag;
     use crate::tests::{
         Operation::{Insert, Migration},
         TestData, TestDataBuilder, TestErr,
     };
+    use chrono::{Duration, Utc};
+    use entity::entities::{article_tag, prelude::ArticleTag};
+    use migration::Expr;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, Set};
     use std::vec;
 
     #[tokio::test]
-    async fn get_existing_article() -> Result<(), TestErr> {
-        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
-            .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
-            .favorited_articles(Migration)
-            .tags(Migration)
-            .article_tags(Migration)
+    async fn newer_taggings_first() -> Result<(), TestErr> {
+        let (connection, TestData { articles, tags, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1, 2, 2]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (2, 1), (3, 1), (1, 2)]))
+            .favorited_articles(Insert(vec![(2, 1), (2, 2)]))
+            .followers(Migration)
             .build()
             .await?;
+        let articles = articles.unwrap();
+        let tag_id = tags.unwrap()[0].id;
+        let now = Utc::now().naive_utc();
+
+        // Tag added to first article three days ago, to second one day ago, to third two days ago
+        for (idx, days) in [(0, 3), (1, 1), (2, 2)] {
+            ArticleTag::update_many()
+                .col_expr(
+                    article_tag::Column::CreatedAt,
+                    Expr::value(now - Duration::days(days)),
+                )
+                .filter(article_tag::Column::ArticleId.eq(articles[idx].id))
+                .filter(article_tag::Column::TagId.eq(tag_id))
+                .exec(&connection)
+                .await?;
+        }
 
-        let expected = articles.unwrap().into_iter().nth(2).unwrap();
-        let result = get_article_model_by_slug(&connection, "title3").await?;
-        assert_eq!(result, Some(expected));
+        let result = get_recently_tagged_articles(&connection, "tag_name1", 20, None).await?;
+        let slugs: Vec<&str> = result.iter().map(|art| art.slug.as_str()).collect();
+        assert_eq!(slugs, ["title2", "title3", "title1"]);
+        assert_eq!(result[0].favorites_count, 2);
+
+        // Tagging made just now goes ahead of all others
+        let model = article_tag::ActiveModel {
+            article_id: Set(articles[3].id),
+            tag_id: Set(tag_id),
+            ..Default::default()
+        };
+        insert_article_tag(&connection, model).await?;
+
+        let result = get_recently_tagged_articles(&connection, "tag_name1", 2, None).await?;
+        let slugs: Vec<&str> = result.iter().map(|art| art.slug.as_str()).collect();
+        assert_eq!(slugs, ["title4", "title2"]);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn none_existing_slug() -> Result<(), TestErr> {
+    async fn only_articles_with_tag() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .articles(Insert(vec![1, 1, 1]))
+            .tags(Insert(3))
+            .article_tags(Insert(vec![(1, 1), (2, 1), (1, 2)]))
             .favorited_articles(Migration)
-            .tags(Migration)
-            .article_tags(Migration)
+            .followers(Migration)
             .build()
             .await?;
 
-        let result = get_article_model_by_slug(&connection, "not_exist").await?;
-        let expected = None;
-        assert_eq!(result, expected);
+        let result = get_recently_tagged_articles(&connection, "tag_name2", 20, None).await?;
+        let slugs: Vec<&str> = result.iter().map(|art| art.slug.as_str()).collect();
+        assert_eq!(slugs, ["title1"]);
+        let mut tag_list = result[0].tag_list.clone();
+        tag_list.sort();
+        assert_eq!(tag_list, ["tag_name1", "tag_name2"]);
+
+        let result = get_recently_tagged_articles(&connection, "tag_name3", 20, None).await?;
+        assert!(result.is_empty());
+
+        // Tag name compared case insensitively
+        let result = get_recently_tagged_articles(&connection, "Tag_Name2", 20, None).await?;
+        let slugs: Vec<&str> = result.iter().map(|art| art.slug.as_str()).collect();
+        assert_eq!(slugs, ["title1"]);
 
         Ok(())
     }
 }
 
 #[cfg(test)]
-mod test_create_article {
-    use super::create_article;
+mod test_get_articles_by_month {
+    use super::get_articles_by_month;
     use crate::tests::{
-        Operation::{Create, Insert},
+        Operation::{Insert, Migration},
         TestData, TestDataBuilder, TestErr,
     };
+    use chrono::NaiveDate;
     use entity::entities::{article, prelude::Article};
-    use sea_orm::Set;
+    use sea_orm::{prelude::Expr, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+    use uuid::Uuid;
+
+    async fn set_created_at(
+        connection: &DatabaseConnection,
+        id: Uuid,
+        (year, month, day): (i32, u32, u32),
+        (hour, min, sec): (u32, u32, u32),
+    ) -> Result<(), TestErr> {
+        let created_at = NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|date| date.and_hms_opt(hour, min, sec))
+            .unwrap();
+        Article::update_many()
+            .col_expr(article::Column::CreatedAt, Expr::value(created_at))
+            .filter(article::Column::Id.eq(id))
+            .exec(connection)
+            .await?;
+        Ok(())
+    }
 
     #[tokio::test]
-    async fn insert_not_exist_data() -> Result<(), TestErr> {
+    async fn only_requested_month() -> Result<(), TestErr> {
         let (connection, TestData { articles, .. }) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Create(vec![1]))
+            .articles(Insert(vec![1, 1, 1, 1]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
             .build()
             .await?;
-        let id = articles.as_ref().unwrap().iter().next().unwrap().id;
-        let actives = TestDataBuilder::activate_models::<Article, article::ActiveModel>(&articles);
-        let model = actives.into_iter().next().unwrap();
+        let articles = articles.unwrap();
+        set_created_at(&connection, articles[0].id, (2023, 4, 30), (23, 59, 59)).await?;
+        set_created_at(&connection, articles[1].id, (2023, 5, 1), (0, 0, 0)).await?;
+        set_created_at(&connection, articles[2].id, (2023, 5, 31), (23, 59, 59)).await?;
+        set_created_at(&connection, articles[3].id, (2023, 6, 1), (0, 0, 0)).await?;
 
-        let insert_result = create_article(&connection, model).await?;
-        assert_eq!(insert_result.last_insert_id, id);
+        let result = get_articles_by_month(&connection, 2023, 5, 20, None, None).await?;
+        let slugs: Vec<_> = result.iter().map(|art| art.slug.as_str()).collect();
+        assert_eq!(slugs, ["title3", "title2"]);
+
+        let result = get_articles_by_month(&connection, 2023, 5, 1, Some(1), None).await?;
+        assert_eq!(result[0].slug, "title2");
+
+        let result = get_articles_by_month(&connection, 2022, 5, 20, None, None).await?;
+        assert!(result.is_empty());
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn insert_existing_id() -> Result<(), TestErr> {
-        let (
-            connection,
-            TestData {
-                articles: inserted, ..
-            },
-        ) = TestDataBuilder::new()
+    async fn not_existing_month() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
             .users(Insert(1))
             .articles(Insert(vec![1]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .favorited_articles(Migration)
             .build()
             .await?;
-        let (_, TestData { articles, .. }) = TestDataBuilder::new()
-            .users(Create(2))
-            .articles(Create(vec![2, 2]))
-            .build()
-            .await?;
-
-        let inserted_id = inserted.unwrap().into_iter().next().unwrap().id;
-        let second_article = articles.unwrap().into_iter().nth(1).unwrap();
-        let model2 = article::ActiveModel {
-            id: Set(inserted_id),
-            ..second_article.into()
-        };
-
-        let insert_result = create_article(&connection, model2).await;
 
-        assert!(insert_result.is_err_and(|err| err
-            .to_string()
-            .ends_with("UNIQUE constraint failed: article.id")));
+        let result = get_articles_by_month(&connection, 2023, 13, 20, None, None).await?;
+        assert!(result.is_empty());
 
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod test_is_article_author {
+    use super::is_article_author;
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use std::vec;
+    use uuid::Uuid;
 
     #[tokio::test]
-    async fn insert_existing_slug() -> Result<(), TestErr> {
+    async fn author_by_slug_and_id() -> Result<(), TestErr> {
         let (
             connection,
             TestData {
-                articles: inserted, ..
+                users, articles, ..
             },
         ) = TestDataBuilder::new()
-            .users(Insert(1))
-            .articles(Insert(vec![1]))
-            .build()
-            .await?;
-        let (_, TestData { articles, .. }) = TestDataBuilder::new()
-            .users(Create(2))
-            .articles(Create(vec![2, 2]))
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2]))
             .build()
             .await?;
+        let author_id = users.unwrap()[0].id;
+        let article_id = articles.unwrap()[0].id.to_string();
 
-        let inserted_slug = inserted.unwrap().into_iter().next().unwrap().slug;
-        let second_article = articles.unwrap().into_iter().nth(1).unwrap();
-        let model2 = article::ActiveModel {
-            slug: Set(inserted_slug),
-            ..second_article.into()
-        };
-
-        let insert_result = create_article(&connection, model2).await;
+        let result = is_article_author(&connection, "title1", author_id).await?;
+        assert_eq!(result, Some(true));
 
-        assert!(insert_result.is_err_and(|err| err
-            .to_string()
-            .ends_with("UNIQUE constraint failed: article.slug")));
+        let result = is_article_author(&connection, &article_id, author_id).await?;
+        assert_eq!(result, Some(true));
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn insert_empty_slug() -> Result<(), TestErr> {
-        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
-            .users(Insert(1))
-            .articles(Create(vec![1]))
+    async fn not_author() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2]))
             .build()
             .await?;
-        let created = articles.unwrap().into_iter().next().unwrap();
+        let other_id = users.unwrap()[1].id;
 
-        let model = article::ActiveModel {
-            slug: Set("".to_owned()),
-            ..created.into()
-        };
-
-        let insert_result = create_article(&connection, model).await;
-
-        assert!(insert_result
-            .is_err_and(|err| err.to_string().ends_with("CHECK constraint failed: slug")));
+        let result = is_article_author(&connection, "title1", other_id).await?;
+        assert_eq!(result, Some(false));
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn insert_empty_title() -> Result<(), TestErr> {
-        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+    async fn missing_article() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Create(vec![1]))
+            .articles(Insert(vec![1]))
             .build()
             .await?;
-        let created = articles.unwrap().into_iter().next().unwrap();
-
-        let model = article::ActiveModel {
-            title: Set("".to_owned()),
-            ..created.into()
-        };
+        let author_id = users.unwrap()[0].id;
 
-        let insert_result = create_article(&connection, model).await;
+        let result = is_article_author(&connection, "not_exist", author_id).await?;
+        assert_eq!(result, None);
 
-        assert!(insert_result
-            .is_err_and(|err| err.to_string().ends_with("CHECK constraint failed: title")));
+        let result = is_article_author(&connection, &Uuid::new_v4().to_string(), author_id).await?;
+        assert_eq!(result, None);
 
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod test_get_recent_duplicate_article_id {
+    use super::get_recent_duplicate_article_id;
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use chrono::{Duration, Utc};
+    use std::vec;
 
     #[tokio::test]
-    async fn insert_empty_description() -> Result<(), TestErr> {
-        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
-            .users(Insert(1))
-            .articles(Create(vec![1]))
+    async fn duplicate_within_window() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2]))
             .build()
             .await?;
-        let created = articles.unwrap().into_iter().next().unwrap();
+        let author_id = users.unwrap()[0].id;
+        let article_id = articles.unwrap()[0].id;
+        let since = Utc::now().naive_utc() - Duration::minutes(1);
 
-        let model = article::ActiveModel {
-            description: Set("".to_owned()),
-            ..created.into()
-        };
+        let result =
+            get_recent_duplicate_article_id(&connection, author_id, "title1", "body", since)
+                .await?;
+        assert_eq!(result, Some(article_id));
 
-        let insert_result = create_article(&connection, model).await;
+        // Same title of other author's article
+        let result =
+            get_recent_duplicate_article_id(&connection, author_id, "title2", "body", since)
+                .await?;
+        assert_eq!(result, None);
 
-        assert!(insert_result.is_err_and(|err| err
-            .to_string()
-            .ends_with("CHECK constraint failed: description")));
+        let result =
+            get_recent_duplicate_article_id(&connection, author_id, "title1", "other", since)
+                .await?;
+        assert_eq!(result, None);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn insert_empty_body() -> Result<(), TestErr> {
-        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+    async fn duplicate_outside_window() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Create(vec![1]))
+            .articles(Insert(vec![1]))
             .build()
             .await?;
-        let created = articles.unwrap().into_iter().next().unwrap();
-
-        let model = article::ActiveModel {
-            body: Set("".to_owned()),
-            ..created.into()
-        };
-
-        let insert_result = create_article(&connection, model).await;
+        let author_id = users.unwrap()[0].id;
+        let since = Utc::now().naive_utc() + Duration::minutes(1);
 
-        assert!(insert_result
-            .is_err_and(|err| err.to_string().ends_with("CHECK constraint failed: body")));
+        let result =
+            get_recent_duplicate_article_id(&connection, author_id, "title1", "body", since)
+                .await?;
+        assert_eq!(result, None);
 
         Ok(())
     }
 }
 
 #[cfg(test)]
-mod test_update_article {
-    use super::update_article;
+mod test_get_related_articles {
+    use super::get_related_articles;
     use crate::tests::{
         Operation::{Insert, Migration},
         TestData, TestDataBuilder, TestErr,
     };
-    use chrono::Local;
-    use entity::entities::article;
-    use sea_orm::ActiveModelTrait;
-    use uuid::Uuid;
+    use std::vec;
 
     #[tokio::test]
-    async fn update_existing_data() -> Result<(), TestErr> {
+    async fn ranked_by_shared_tags() -> Result<(), TestErr> {
         let (connection, TestData { articles, .. }) = TestDataBuilder::new()
-            .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1, 2, 2, 2]))
+            .tags(Insert(4))
+            .article_tags(Insert(vec![
+                (1, 1),
+                (1, 2),
+                (1, 3),
+                (2, 1),
+                (2, 2),
+                (2, 3),
+                (3, 1),
+                (3, 2),
+                (3, 4),
+                (4, 4),
+                (5, 1),
+            ]))
+            .favorited_articles(Insert(vec![(2, 1), (2, 2)]))
+            .followers(Migration)
             .build()
             .await?;
-        let model = articles.unwrap().into_iter().nth(3).unwrap();
-
-        let expected = article::Model {
-            body: "body".to_owned(),
-            description: "description".to_owned(),
-            ..model
-        };
-
-        let update_model = article::ActiveModel::from(expected.clone()).reset_all();
-        let updated = update_article(&connection, update_model).await?;
-        assert_eq!(expected, updated);
-
-        Ok(())
-    }
+        let article_id = articles.unwrap()[0].id;
 
-    #[tokio::test]
-    async fn update_not_existing_data() -> Result<(), TestErr> {
-        let (connection, _) = TestDataBuilder::new()
-            .users(Migration)
-            .articles(Migration)
-            .build()
-            .await?;
+        let result = get_related_articles(&connection, article_id, 20, None).await?;
+        let slugs: Vec<&str> = result.iter().map(|art| art.slug.as_str()).collect();
 
-        let expected = article::Model {
-            id: Uuid::new_v4(),
-            slug: "slug".to_owned(),
-            title: "slug".to_owned(),
-            description: "slug".to_owned(),
-            body: "slug".to_owned(),
-            author_id: Uuid::new_v4(),
-            created_at: Some(Local::now().naive_local()),
-            updated_at: Some(Local::now().naive_local()),
-        };
+        // Second article shares three tags, third one two tags, more recent fifth one single tag
+        assert_eq!(slugs, ["title2", "title3", "title5"]);
+        assert_eq!(result[0].favorites_count, 2);
 
-        let update_model = article::ActiveModel::from(expected).reset_all();
-        let result = update_article(&connection, update_model).await;
-        assert!(
-            result.is_err_and(|err| err.to_string().ends_with("None of the records are updated"))
-        );
+        let result = get_related_articles(&connection, article_id, 1, None).await?;
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].slug, "title2");
 
         Ok(())
     }
-}
-
-#[cfg(test)]
-mod test_delete_article {
-    use super::delete_article;
-    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
-    use entity::entities::{article, prelude::Article};
 
     #[tokio::test]
-    async fn delete_existing_data() -> Result<(), TestErr> {
+    async fn no_tags_no_related() -> Result<(), TestErr> {
         let (connection, TestData { articles, .. }) = TestDataBuilder::new()
-            .users(Insert(2))
-            .articles(Insert(vec![1, 2]))
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1]))
+            .tags(Insert(1))
+            .article_tags(Insert(vec![(2, 1)]))
+            .favorited_articles(Migration)
+            .followers(Migration)
             .build()
             .await?;
-        let actives = TestDataBuilder::activate_models::<Article, article::ActiveModel>(&articles);
-        let model = actives.into_iter().next().unwrap();
+        let article_id = articles.unwrap()[0].id;
 
-        let delete_result = delete_article(&connection, model).await?;
-        assert_eq!(delete_result.rows_affected, 1_u64);
+        let result = get_related_articles(&connection, article_id, 20, None).await?;
+        assert!(result.is_empty());
 
         Ok(())
     }
 }
 
 #[cfg(test)]
-#[cfg(feature = "seed")]
-mod test_empty_article_table {
-    use super::empty_article_table;
+mod test_get_articles_by_slugs {
+    use super::{get_article_by_slug, get_articles_by_slugs};
     use crate::tests::{
         Operation::{Insert, Migration},
         TestDataBuilder, TestErr,
     };
-    use entity::entities::{article, prelude::Article};
-    use sea_orm::EntityTrait;
+    use std::vec;
 
     #[tokio::test]
-    async fn delete_existing_articles() -> Result<(), TestErr> {
+    async fn existing_and_missing_slugs() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new()
-            .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2, 1]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1), (3, 2)]))
+            .followers(Migration)
+            .favorited_articles(Migration)
             .build()
             .await?;
 
-        let delete_result = empty_article_table(&connection).await?;
-        assert_eq!(delete_result.rows_affected, 5_u64);
-
-        let expected: Vec<article::Model> = Vec::new();
-        let result = Article::find().all(&connection).await?;
+        let slugs: Vec<String> = ["title3", "missing", "title1", "title3"]
+            .map(str::to_owned)
+            .to_vec();
+        let result = get_articles_by_slugs(&connection, &slugs, None).await?;
+
+        let expected = vec![
+            get_article_by_slug(&connection, "title3", None)
+                .await?
+                .unwrap(),
+            get_article_by_slug(&connection, "title1", None)
+                .await?
+                .unwrap(),
+        ];
         assert_eq!(result, expected);
 
         Ok(())
     }
 
     #[tokio::test]
-    async fn delete_empty_table() -> Result<(), TestErr> {
+    async fn empty_slugs() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new()
-            .users(Migration)
-            .articles(Migration)
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .favorited_articles(Migration)
             .build()
             .await?;
 
-        let delete_result = empty_article_table(&connection).await?;
-        assert_eq!(delete_result.rows_affected, 0_u64);
-
-        let expected: Vec<article::Model> = Vec::new();
-        let result = Article::find().all(&connection).await?;
-        assert_eq!(result, expected);
+        let result = get_articles_by_slugs(&connection, &[], None).await?;
+        assert!(result.is_empty());
 
         Ok(())
     }
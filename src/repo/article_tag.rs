@@ -1,27 +1,82 @@
+use chrono::Utc;
 use entity::entities::{article_tag, prelude::ArticleTag, tag};
-#[cfg(feature = "seed")]
-use sea_orm::DeleteResult;
+use migration::OnConflict;
 use sea_orm::{
-    query::*, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, InsertResult, RelationTrait,
-    TryInsertResult,
+    query::*, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr,
+    DeleteResult, EntityTrait, InsertResult, RelationTrait, TryInsertResult,
 };
+use std::collections::HashMap;
 use uuid::Uuid;
 
-/// Insert `article tags` for the provided `ActiveModel`.
+/// Insert `article tags` for the provided `ActiveModel`. Creation time
+/// set to current time when not provided.
 /// Returns `TryInsertResult` on success, otherwise returns an `database error`.
 /// See [`TryInsertResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.TryInsertResult.html)
 /// documentation for more details.
-pub async fn create_article_tags(
-    db: &DatabaseConnection,
+pub async fn create_article_tags<C: ConnectionTrait>(
+    db: &C,
     article_tags: Vec<article_tag::ActiveModel>,
 ) -> Result<TryInsertResult<InsertResult<article_tag::ActiveModel>>, DbErr> {
+    let now = Utc::now().naive_utc();
+    let article_tags = article_tags.into_iter().map(|mut article_tag| {
+        if article_tag.created_at.is_not_set() {
+            article_tag.created_at = Set(Some(now));
+        }
+        article_tag
+    });
+
     ArticleTag::insert_many(article_tags)
         .on_empty_do_nothing()
         .exec(db)
         .await
 }
 
-/// Insert `article tag` for the provided `ActiveModel`.
+/// Attach `tag` to `article`. Ignore already attached tag.
+/// Returns `TryInsertResult` on success, otherwise returns an `database error`.
+/// See [`TryInsertResult`](https://docs.rs/sea-orm/latest/sea_orm/enum.TryInsertResult.html)
+/// documentation for more details.
+pub async fn add_article_tag(
+    db: &DatabaseConnection,
+    article_id: Uuid,
+    tag_id: Uuid,
+) -> Result<TryInsertResult<InsertResult<article_tag::ActiveModel>>, DbErr> {
+    let article_tag = article_tag::ActiveModel {
+        article_id: Set(article_id),
+        tag_id: Set(tag_id),
+        created_at: Set(Some(Utc::now().naive_utc())),
+    };
+
+    ArticleTag::insert_many([article_tag])
+        .on_conflict(
+            OnConflict::columns([article_tag::Column::ArticleId, article_tag::Column::TagId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .on_empty_do_nothing()
+        .exec(db)
+        .await
+}
+
+/// Detach `tag` from `article` by deleting matching `article tag` record. Tag itself is not
+/// deleted, not attached tag is ignored.
+/// Returns `DeleteResult` with affected rows count on success, otherwise
+/// returns an `database error`.
+/// See [`DeleteResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.DeleteResult.html)
+/// documentation for more details.
+pub async fn remove_article_tag<C: ConnectionTrait>(
+    db: &C,
+    article_id: Uuid,
+    tag_id: Uuid,
+) -> Result<DeleteResult, DbErr> {
+    ArticleTag::delete_many()
+        .filter(article_tag::Column::ArticleId.eq(article_id))
+        .filter(article_tag::Column::TagId.eq(tag_id))
+        .exec(db)
+        .await
+}
+
+/// Insert `article tag` for the provided `ActiveModel`. Creation time
+/// set to current time when not provided.
 /// Returns `InsertResult` with last inserted id on success, otherwise
 /// returns an `database error`.
 /// Empty input produce error as not allowed on database level.
@@ -30,29 +85,61 @@ pub async fn create_article_tags(
 #[cfg(any(test, feature = "seed"))]
 pub async fn insert_article_tag(
     db: &DatabaseConnection,
-    article_tag: article_tag::ActiveModel,
+    mut article_tag: article_tag::ActiveModel,
 ) -> Result<InsertResult<article_tag::ActiveModel>, DbErr> {
+    if article_tag.created_at.is_not_set() {
+        article_tag.created_at = Set(Some(Utc::now().naive_utc()));
+    }
     ArticleTag::insert(article_tag).exec(db).await
 }
 
-/// Fetch `tag names` for the provided article.
-/// Returns `list of tag names` on success, otherwise returns an `database error`.
-#[allow(dead_code)]
-pub async fn get_article_tags(
-    db: &DatabaseConnection,
+/// Fetch `tag ids` attached to the provided article.
+/// Returns `list of tag ids` on success, otherwise returns an `database error`.
+pub async fn get_article_tag_ids<C: ConnectionTrait>(
+    db: &C,
     article_id: Uuid,
-) -> Result<Vec<String>, DbErr> {
+) -> Result<Vec<Uuid>, DbErr> {
     ArticleTag::find()
-        .join(JoinType::LeftJoin, article_tag::Relation::Tag.def())
         .filter(article_tag::Column::ArticleId.eq(article_id))
         .select_only()
-        .column(tag::Column::TagName)
-        .group_by(tag::Column::TagName)
-        .into_tuple::<String>()
+        .column(article_tag::Column::TagId)
+        .into_tuple::<Uuid>()
         .all(db)
         .await
 }
 
+/// Fetch `tag names` attached to each of the provided articles, ordered by name.
+/// Single query used for whole list of articles. Articles without tags are absent in result.
+/// Returns map of article id to its tag names on success, otherwise returns an `database error`.
+pub async fn get_article_tags_for_ids(
+    db: &DatabaseConnection,
+    article_ids: Vec<Uuid>,
+) -> Result<HashMap<Uuid, Vec<String>>, DbErr> {
+    if article_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows = ArticleTag::find()
+        .join(JoinType::InnerJoin, article_tag::Relation::Tag.def())
+        .filter(article_tag::Column::ArticleId.is_in(article_ids))
+        .select_only()
+        .column(article_tag::Column::ArticleId)
+        .column(tag::Column::TagName)
+        .order_by_asc(tag::Column::TagName)
+        .into_tuple::<(Uuid, String)>()
+        .all(db)
+        .await?;
+
+    let mut tags_by_article: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for (article_id, tag_name) in rows {
+        tags_by_article
+            .entry(article_id)
+            .or_default()
+            .push(tag_name);
+    }
+    Ok(tags_by_article)
+}
+
 /// Delete all existing `article tag records` from database.
 /// Returns `DeleteResult` with affected rows count on success, otherwise
 /// returns an `database error`.
@@ -113,10 +200,11 @@ mod test_create_article_tags {
         let actives =
             TestDataBuilder::activate_models::<ArticleTag, article_tag::ActiveModel>(&article_tags);
 
-        let existing = article_tags.as_ref().unwrap().iter().nth(1).unwrap();
+        let existing = article_tags.as_ref().unwrap().get(1).unwrap();
         let model = article_tag::ActiveModel {
             article_id: Set(existing.article_id),
             tag_id: Set(existing.tag_id),
+            ..Default::default()
         };
 
         insert_article_tag(&connection, model).await?;
@@ -175,6 +263,7 @@ mod test_insert_article_tag {
         let model = article_tag::ActiveModel {
             article_id: Set(article_id),
             tag_id: Set(tag_id),
+            ..Default::default()
         };
 
         let last_id = (article_id, tag_id);
@@ -199,6 +288,7 @@ mod test_insert_article_tag {
         let model = article_tag::ActiveModel {
             article_id: Set(Uuid::new_v4()),
             tag_id: Set(tag_id),
+            ..Default::default()
         };
 
         let insert_result = insert_article_tag(&connection, model).await;
@@ -223,6 +313,7 @@ mod test_insert_article_tag {
         let model = article_tag::ActiveModel {
             article_id: Set(article_id),
             tag_id: Set(Uuid::new_v4()),
+            ..Default::default()
         };
 
         let insert_result = insert_article_tag(&connection, model).await;
@@ -256,67 +347,126 @@ mod test_insert_article_tag {
 }
 
 #[cfg(test)]
-mod test_get_article_tags {
-    use super::get_article_tags;
+mod test_add_article_tag {
+    use super::{add_article_tag, get_article_tags_for_ids};
     use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
     use std::vec;
-    use uuid::Uuid;
 
     #[tokio::test]
-    async fn tags_of_existing_article() -> Result<(), TestErr> {
-        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+    async fn new_and_attached_tag() -> Result<(), TestErr> {
+        let (connection, TestData { articles, tags, .. }) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
-            .tags(Insert(3))
-            .article_tags(Insert(vec![(1, 1), (2, 1), (2, 2), (3, 3)]))
+            .articles(Insert(vec![1]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1)]))
             .build()
             .await?;
+        let article_id = articles.unwrap()[0].id;
+        let tags = tags.unwrap();
 
-        let article_id = articles.as_ref().unwrap()[1].id;
-        let result = get_article_tags(&connection, article_id).await?;
+        add_article_tag(&connection, article_id, tags[1].id).await?;
+        // Already attached tag is ignored
+        add_article_tag(&connection, article_id, tags[0].id).await?;
+
+        let result = get_article_tags_for_ids(&connection, vec![article_id]).await?;
         let expected = vec!["tag_name1".to_owned(), "tag_name2".to_owned()];
-        assert_eq!(result, expected);
+        assert_eq!(result[&article_id], expected);
 
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod test_remove_article_tag {
+    use super::{get_article_tags_for_ids, remove_article_tag};
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use entity::entities::prelude::Tag;
+    use sea_orm::{EntityTrait, PaginatorTrait};
+    use std::vec;
 
     #[tokio::test]
-    async fn article_with_no_tags() -> Result<(), TestErr> {
-        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+    async fn linked_and_not_linked_tag() -> Result<(), TestErr> {
+        let (connection, TestData { articles, tags, .. }) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .articles(Insert(vec![1]))
             .tags(Insert(3))
-            .article_tags(Insert(vec![(1, 1), (2, 1), (2, 2), (3, 3)]))
+            .article_tags(Insert(vec![(1, 1), (1, 2)]))
             .build()
             .await?;
+        let article_id = articles.unwrap()[0].id;
+        let tags = tags.unwrap();
 
-        let article_id = articles.as_ref().unwrap()[4].id;
-        let result = get_article_tags(&connection, article_id).await?;
-        let expected: Vec<String> = vec![];
-        assert_eq!(result, expected);
+        let result = remove_article_tag(&connection, article_id, tags[0].id).await?;
+        assert_eq!(result.rows_affected, 1);
+
+        let result = remove_article_tag(&connection, article_id, tags[2].id).await?;
+        assert_eq!(result.rows_affected, 0);
+
+        let result = get_article_tags_for_ids(&connection, vec![article_id]).await?;
+        assert_eq!(result[&article_id], vec!["tag_name2".to_owned()]);
+        assert_eq!(Tag::find().count(&connection).await?, 3);
 
         Ok(())
     }
+}
+
+#[cfg(test)]
+mod test_get_article_tag_ids {
+    use super::get_article_tag_ids;
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use std::vec;
 
     #[tokio::test]
-    async fn tags_of_non_existing_article() -> Result<(), TestErr> {
-        let (connection, _) = TestDataBuilder::new()
+    async fn tag_ids_of_article() -> Result<(), TestErr> {
+        let (connection, TestData { articles, tags, .. }) = TestDataBuilder::new()
             .users(Insert(1))
-            .articles(Insert(vec![1, 1, 1, 1, 1]))
+            .articles(Insert(vec![1, 1]))
             .tags(Insert(3))
-            .article_tags(Insert(vec![(1, 1), (2, 1), (2, 2), (3, 3)]))
+            .article_tags(Insert(vec![(1, 1), (2, 1), (2, 3)]))
             .build()
             .await?;
 
-        let article_id = Uuid::new_v4();
-        let result = get_article_tags(&connection, article_id).await?;
-        let expected: Vec<String> = vec![];
+        let tags = tags.unwrap();
+        let article_id = articles.as_ref().unwrap()[1].id;
+        let mut result = get_article_tag_ids(&connection, article_id).await?;
+        result.sort();
+        let mut expected = vec![tags[0].id, tags[2].id];
+        expected.sort();
         assert_eq!(result, expected);
 
         Ok(())
     }
 }
 
+#[cfg(test)]
+mod test_get_article_tags_for_ids {
+    use super::get_article_tags_for_ids;
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+
+    #[tokio::test]
+    async fn grouped_by_article() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(2, 2), (1, 2), (2, 1)]))
+            .build()
+            .await?;
+        let ids: Vec<_> = articles.unwrap().into_iter().map(|art| art.id).collect();
+
+        let result = get_article_tags_for_ids(&connection, ids.clone()).await?;
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[&ids[0]], vec!["tag_name2"]);
+        assert_eq!(result[&ids[1]], vec!["tag_name1", "tag_name2"]);
+
+        assert!(get_article_tags_for_ids(&connection, vec![])
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "seed")]
 mod test_empty_article_tag_table {
@@ -0,0 +1,188 @@
+use entity::entities::{block, prelude::Block};
+use sea_orm::{
+    sea_query::{Expr, IntoColumnRef, OnConflict, Query, SimpleExpr},
+    ColumnTrait, DatabaseConnection, DbErr, DeleteResult, EntityTrait, QueryFilter, Set,
+};
+use uuid::Uuid;
+
+/// Insert `block` record, so content of user with `blocked_id` is hidden from user with `user_id`.
+/// Blocking already blocked user is no-op.
+/// Returns `()` on success, otherwise returns an `database error`.
+pub async fn block_user(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    blocked_id: Uuid,
+) -> Result<(), DbErr> {
+    let model = block::ActiveModel {
+        user_id: Set(user_id),
+        blocked_id: Set(blocked_id),
+    };
+    Block::insert(model)
+        .on_conflict(
+            OnConflict::columns([block::Column::UserId, block::Column::BlockedId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec(db)
+        .await
+        .map(|_| ())
+}
+
+/// Delete `block` record of user with `user_id` for user with `blocked_id`.
+/// Returns `DeleteResult` with affected rows count on success, otherwise
+/// returns an `database error`.
+/// See [`DeleteResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.DeleteResult.html)
+/// documentation for more details.
+pub async fn unblock_user(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    blocked_id: Uuid,
+) -> Result<DeleteResult, DbErr> {
+    Block::delete_many()
+        .filter(block::Column::UserId.eq(user_id))
+        .filter(block::Column::BlockedId.eq(blocked_id))
+        .exec(db)
+        .await
+}
+
+/// Build condition excluding rows with `author_col` among users blocked by current user.
+/// Always true for anonymous requests.
+pub fn author_not_blocked(
+    current_user_id: Option<Uuid>,
+    author_col: impl IntoColumnRef,
+) -> SimpleExpr {
+    match current_user_id {
+        Some(user_id) => Expr::col(author_col).not_in_subquery(
+            Query::select()
+                .column(block::Column::BlockedId)
+                .from(Block)
+                .and_where(block::Column::UserId.eq(user_id))
+                .to_owned(),
+        ),
+        None => Expr::val(true).into(),
+    }
+}
+
+#[cfg(test)]
+mod test_block_user {
+    use super::{block_user, unblock_user};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use entity::entities::prelude::Block;
+    use sea_orm::EntityTrait;
+
+    #[tokio::test]
+    async fn block_twice_and_unblock() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let users = users.unwrap();
+        block_user(&connection, users[0].id, users[1].id).await?;
+        block_user(&connection, users[0].id, users[1].id).await?;
+        assert_eq!(Block::find().all(&connection).await?.len(), 1);
+
+        let delete_result = unblock_user(&connection, users[0].id, users[1].id).await?;
+        assert_eq!(delete_result.rows_affected, 1);
+        assert!(Block::find().all(&connection).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn block_self() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let user_id = users.unwrap()[0].id;
+        assert!(block_user(&connection, user_id, user_id).await.is_err());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_author_not_blocked {
+    use super::{block_user, unblock_user};
+    use crate::repo::{
+        article::{get_articles_feed, get_articles_page, ArticleSort, TagMatch},
+        comment::get_comments_by_article_id,
+    };
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use sea_orm::{DatabaseConnection, DbErr};
+    use uuid::Uuid;
+
+    /// Count listed articles, feed articles and comments of provided article visible to user.
+    async fn visible_content(
+        db: &DatabaseConnection,
+        user_id: Uuid,
+        article_id: Uuid,
+    ) -> Result<(usize, usize, usize), DbErr> {
+        let articles = get_articles_page(
+            db,
+            &[],
+            TagMatch::Any,
+            None,
+            None,
+            None,
+            ArticleSort::Updated,
+            20,
+            None,
+            Some(user_id),
+        )
+        .await?
+        .items;
+        let feed = get_articles_feed(db, &[], TagMatch::Any, 20, None, user_id).await?;
+        let comments =
+            get_comments_by_article_id(db, article_id, None, None, Some(user_id)).await?;
+        Ok((articles.len(), feed.len(), comments.len()))
+    }
+
+    #[tokio::test]
+    async fn blocked_content_hidden_for_blocker_only() -> Result<(), TestErr> {
+        // user2 writes article 2 and comments article 1 of user1, user1 and user3 follow user2
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(3))
+            .articles(Insert(vec![1, 2]))
+            .comments(Insert(vec![(1, 1), (2, 1)]))
+            .followers(Insert(vec![(2, 1), (2, 3)]))
+            .favorited_articles(Migration)
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+        let users = users.unwrap();
+        let article_id = articles.unwrap()[0].id;
+
+        let content = visible_content(&connection, users[0].id, article_id).await?;
+        assert_eq!(content, (2, 1, 2));
+
+        block_user(&connection, users[0].id, users[1].id).await?;
+        let content = visible_content(&connection, users[0].id, article_id).await?;
+        assert_eq!(content, (1, 0, 1));
+        let content = visible_content(&connection, users[2].id, article_id).await?;
+        assert_eq!(content, (2, 1, 2));
+
+        unblock_user(&connection, users[0].id, users[1].id).await?;
+        let content = visible_content(&connection, users[0].id, article_id).await?;
+        assert_eq!(content, (2, 1, 2));
+
+        Ok(())
+    }
+}
@@ -1,18 +1,36 @@
-use super::user::{author_followed_by_current_user, Profile};
-use entity::entities::{comment, prelude::Comment, user};
+use super::{
+    block::author_not_blocked,
+    follower::get_followed_user_ids,
+    time::serialize_utc,
+    user::{author_followed_by_current_user, Profile},
+};
+use chrono::Utc;
+use entity::entities::{
+    comment,
+    prelude::{Comment, User},
+    user,
+};
+use migration::{Expr, SimpleExpr};
 use sea_orm::{
-    entity::prelude::DateTime, query::*, ColumnTrait, DatabaseConnection, DbErr, DeleteResult,
-    EntityTrait, FromQueryResult, QueryFilter, RelationTrait,
+    entity::prelude::DateTime,
+    query::*,
+    ActiveValue::{Set, Unchanged},
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, DeleteResult, EntityTrait,
+    FromQueryResult, PaginatorTrait, QueryFilter, RelationTrait,
 };
 use serde::Serialize;
 use uuid::Uuid;
 
+const DEFAULT_COMMENTS_LIMIT: u64 = 50;
+const DEFAULT_COMMENTS_OFFSET: u64 = 0;
+
 /// Insert `comment` for the provided `ActiveModel`.
 /// Returns `InsertResult` with last inserted id on success, otherwise
 /// returns an `database error`.
 /// Empty input produce error as not allowed on database level.
 /// See [`InsertResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.InsertResult.html)
 /// documentation for more details.
+#[cfg(any(test, feature = "seed"))]
 pub async fn insert_comment(
     db: &DatabaseConnection,
     comment: comment::ActiveModel,
@@ -20,8 +38,41 @@ pub async fn insert_comment(
     Comment::insert(comment).exec(db).await
 }
 
+/// Insert `comment` for the provided `ActiveModel` authored by user with provided id. Enriched
+/// comment built from inserted row and author profile, without refetch of the comment: author
+/// is current user, thus comment is editable and author is not followed.
+/// Returns inserted `comment` on success, otherwise returns an `database error`.
+pub async fn insert_comment_returning(
+    db: &DatabaseConnection,
+    comment: comment::ActiveModel,
+    current_user_id: Uuid,
+) -> Result<CommentWithAuthor, DbErr> {
+    let inserted = Comment::insert(comment).exec_with_returning(db).await?;
+
+    let author = User::find_by_id(current_user_id)
+        .column_as(Expr::val(false), "following")
+        .into_model::<Profile>()
+        .one(db)
+        .await?
+        .ok_or(DbErr::RecordNotFound(format!(
+            "Author of inserted comment {} not found",
+            inserted.id
+        )))?;
+
+    Ok(CommentWithAuthor {
+        id: inserted.id,
+        body: inserted.body,
+        created_at: inserted.created_at,
+        updated_at: inserted.updated_at,
+        author_id: inserted.author_id,
+        author,
+        can_edit: true,
+    })
+}
+
 /// Fetch `comment` with additional info (see ArticleWithAuthor for details) for the provided `id`.
-/// Optional identifier used to determine whether the logged in user is a follower of the author.
+/// Optional identifier used to determine whether the logged in user is a follower of the author
+/// and whether the comment can be edited by that user.
 /// Returns optional `comment` on success, otherwise returns an `database error`.
 pub async fn get_comment_by_id(
     db: &DatabaseConnection,
@@ -37,34 +88,99 @@ pub async fn get_comment_by_id(
             author_followed_by_current_user(current_user_id),
             "following",
         )
+        .column_as(
+            comment_authored_by_current_user(current_user_id),
+            "can_edit",
+        )
         .into_model::<CommentWithAuthor>()
         .one(db)
         .await
 }
 
+/// Fetch `comment` model (without additional info) for the provided `id`.
+/// Returns optional `comment` on success, otherwise returns an `database error`.
+pub async fn get_comment_model_by_id(
+    db: &DatabaseConnection,
+    id: Uuid,
+) -> Result<Option<comment::Model>, DbErr> {
+    Comment::find_by_id(id).one(db).await
+}
+
 /// Fetch `comments` with additional info (see ArticleWithAuthor for details) for the provided `article id`.
-/// Optional identifier used to determine whether the logged in user is a follower of the author.
+/// Optional identifier used to determine whether the logged in user is a follower of the author
+/// and whether the comment can be edited by that user. Comments of authors blocked by that user
+/// are excluded. Newest comments first, limited by limit (default is 50) and offset parameters.
 /// Returns list of `comments` on success, otherwise returns an `database error`.
 pub async fn get_comments_by_article_id(
     db: &DatabaseConnection,
     article_id: Uuid,
+    limit: Option<u64>,
+    offset: Option<u64>,
     current_user_id: Option<Uuid>,
 ) -> Result<Vec<CommentWithAuthor>, DbErr> {
-    Comment::find()
+    let mut comments = Comment::find()
         .join(JoinType::LeftJoin, comment::Relation::User.def())
         .filter(comment::Column::ArticleId.eq(article_id))
+        .filter(author_not_blocked(
+            current_user_id,
+            comment::Column::AuthorId,
+        ))
         .column(user::Column::Username)
         .column(user::Column::Bio)
         .column(user::Column::Image)
+        .column_as(Expr::val(false), "following")
         .column_as(
-            author_followed_by_current_user(current_user_id),
-            "following",
+            comment_authored_by_current_user(current_user_id),
+            "can_edit",
         )
+        .order_by_desc(comment::Column::CreatedAt)
+        .order_by_desc(comment::Column::Id)
+        .limit(limit.unwrap_or(DEFAULT_COMMENTS_LIMIT))
+        .offset(offset.unwrap_or(DEFAULT_COMMENTS_OFFSET))
         .into_model::<CommentWithAuthor>()
         .all(db)
+        .await?;
+
+    // Following status resolved with single query for whole list
+    let author_ids = comments.iter().map(|cmt| cmt.author_id).collect();
+    let followed = get_followed_user_ids(db, current_user_id, author_ids).await?;
+    comments
+        .iter_mut()
+        .for_each(|cmt| cmt.author.following = followed.contains(&cmt.author_id));
+
+    Ok(comments)
+}
+
+/// Count `comments` for the provided `article id`.
+/// Returns quantity of `comments` on success, otherwise returns an `database error`.
+pub async fn count_comments_by_article_id(
+    db: &DatabaseConnection,
+    article_id: Uuid,
+) -> Result<u64, DbErr> {
+    Comment::find()
+        .filter(comment::Column::ArticleId.eq(article_id))
+        .count(db)
         .await
 }
 
+/// Update body of `comment` with provided id, time of update set to current time.
+/// Returns updated `comment` on success, `RecordNotUpdated` for non existing comment,
+/// otherwise returns an `database error`.
+pub async fn update_comment(
+    db: &DatabaseConnection,
+    id: Uuid,
+    body: String,
+) -> Result<comment::Model, DbErr> {
+    let comment_model = comment::ActiveModel {
+        id: Unchanged(id),
+        body: Set(body),
+        updated_at: Set(Some(Utc::now().naive_utc())),
+        ..Default::default()
+    };
+
+    Comment::update(comment_model).exec(db).await
+}
+
 /// Delete `comment` for the provided id.
 /// Returns `DeleteResult` with affected rows count on success, otherwise
 /// returns an `database error`.
@@ -77,6 +193,21 @@ pub async fn delete_comment(
     Comment::delete_by_id(comment_id).exec(db).await
 }
 
+/// Delete all `comments` written by user with provided `author_id`.
+/// Returns `DeleteResult` with affected rows count on success, otherwise
+/// returns an `database error`.
+/// See [`DeleteResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.DeleteResult.html)
+/// documentation for more details.
+pub async fn delete_comments_by_author<C: ConnectionTrait>(
+    db: &C,
+    author_id: Uuid,
+) -> Result<DeleteResult, DbErr> {
+    Comment::delete_many()
+        .filter(comment::Column::AuthorId.eq(author_id))
+        .exec(db)
+        .await
+}
+
 /// Delete all existing `comment records` from database.
 /// Returns `DeleteResult` with affected rows count on success, otherwise
 /// returns an `database error`.
@@ -87,14 +218,28 @@ pub async fn empty_comment_table(db: &DatabaseConnection) -> Result<DeleteResult
     Comment::delete_many().exec(db).await
 }
 
+/// Returns expression for determine whether the logged in user is the author of the comment.
+/// Return `false` if user id is not specified.
+fn comment_authored_by_current_user(user_id: Option<Uuid>) -> SimpleExpr {
+    match user_id {
+        Some(id) => comment::Column::AuthorId.eq(id),
+        None => false.into(),
+    }
+}
+
 #[derive(Debug, Serialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct CommentWithAuthor {
     pub id: Uuid,
     pub body: String,
+    #[serde(serialize_with = "serialize_utc")]
     pub created_at: Option<DateTime>,
+    #[serde(serialize_with = "serialize_utc")]
     pub updated_at: Option<DateTime>,
+    #[serde(skip)]
+    pub author_id: Uuid,
     pub author: Profile,
+    pub can_edit: bool,
 }
 
 impl FromQueryResult for CommentWithAuthor {
@@ -104,7 +249,9 @@ impl FromQueryResult for CommentWithAuthor {
             body: res.try_get(pre, "body")?,
             created_at: res.try_get(pre, "created_at")?,
             updated_at: res.try_get(pre, "updated_at")?,
+            author_id: res.try_get(pre, "author_id")?,
             author: Profile::from_query_result(res, pre)?,
+            can_edit: res.try_get(pre, "can_edit")?,
         })
     }
 }
@@ -113,7 +260,7 @@ impl FromQueryResult for CommentWithAuthor {
 mod test_insert_comment {
     use super::insert_comment;
     use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
-    use chrono::Local;
+    use chrono::Utc;
     use entity::entities::{comment, prelude::Comment};
     use sea_orm::Set;
     use std::vec;
@@ -142,8 +289,8 @@ mod test_insert_comment {
             body: Set("body".to_owned()),
             author_id: Set(author_id),
             article_id: Set(article_id),
-            created_at: Set(Some(Local::now().naive_local())),
-            updated_at: Set(Some(Local::now().naive_local())),
+            created_at: Set(Some(Utc::now().naive_utc())),
+            updated_at: Set(Some(Utc::now().naive_utc())),
         };
 
         let insert_result = insert_comment(&connection, model).await?;
@@ -168,8 +315,8 @@ mod test_insert_comment {
             body: Set("body".to_owned()),
             author_id: Set(Uuid::new_v4()),
             article_id: Set(article_id),
-            created_at: Set(Some(Local::now().naive_local())),
-            updated_at: Set(Some(Local::now().naive_local())),
+            created_at: Set(Some(Utc::now().naive_utc())),
+            updated_at: Set(Some(Utc::now().naive_utc())),
         };
 
         let insert_result = insert_comment(&connection, model).await;
@@ -195,8 +342,8 @@ mod test_insert_comment {
             body: Set("body".to_owned()),
             author_id: Set(author_id),
             article_id: Set(Uuid::new_v4()),
-            created_at: Set(Some(Local::now().naive_local())),
-            updated_at: Set(Some(Local::now().naive_local())),
+            created_at: Set(Some(Utc::now().naive_utc())),
+            updated_at: Set(Some(Utc::now().naive_utc())),
         };
 
         let insert_result = insert_comment(&connection, model).await;
@@ -227,11 +374,58 @@ mod test_insert_comment {
     }
 }
 
+#[cfg(test)]
+mod test_insert_comment_returning {
+    use super::{get_comment_by_id, insert_comment_returning};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use entity::entities::comment;
+    use sea_orm::Set;
+    use std::vec;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn matches_fetched_comment() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                articles, users, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .comments(Migration)
+            .followers(Insert(vec![(1, 2)]))
+            .build()
+            .await?;
+
+        let author_id = users.unwrap()[1].id;
+        let model = comment::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            body: Set("body".to_owned()),
+            author_id: Set(author_id),
+            article_id: Set(articles.unwrap()[0].id),
+            ..Default::default()
+        };
+
+        let result = insert_comment_returning(&connection, model, author_id).await?;
+        let fetched = get_comment_by_id(&connection, result.id, Some(author_id)).await?;
+        assert_eq!(Some(result), fetched);
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test_get_comment_by_id {
     use super::{get_comment_by_id, CommentWithAuthor};
     use crate::repo::user::Profile;
-    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
     use std::vec;
     use uuid::Uuid;
 
@@ -263,6 +457,8 @@ mod test_get_comment_by_id {
             },
             created_at: comment.created_at,
             updated_at: comment.updated_at,
+            author_id: author.id,
+            can_edit: false,
         };
 
         let result = get_comment_by_id(&connection, comment.id, None).await?;
@@ -286,13 +482,81 @@ mod test_get_comment_by_id {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn can_edit_by_viewer() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, comments, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .comments(Insert(vec![(1, 1)]))
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let users = users.unwrap();
+        let comment_id = comments.unwrap()[0].id;
+
+        let author_view = get_comment_by_id(&connection, comment_id, Some(users[0].id)).await?;
+        assert!(author_view.unwrap().can_edit);
+
+        let other_view = get_comment_by_id(&connection, comment_id, Some(users[1].id)).await?;
+        assert!(!other_view.unwrap().can_edit);
+
+        let anonymous_view = get_comment_by_id(&connection, comment_id, None).await?;
+        assert!(!anonymous_view.unwrap().can_edit);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_comment_model_by_id {
+    use super::get_comment_model_by_id;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn existing_and_missing() -> Result<(), TestErr> {
+        let (connection, TestData { comments, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .comments(Insert(vec![(2, 1)]))
+            .followers(Migration)
+            .build()
+            .await?;
+        let comment = comments.unwrap().remove(0);
+
+        let result = get_comment_model_by_id(&connection, comment.id).await?;
+        assert_eq!(result, Some(comment));
+
+        let result = get_comment_model_by_id(&connection, Uuid::new_v4()).await?;
+        assert_eq!(result, None);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test_get_comments_by_article_id {
     use super::{get_comments_by_article_id, CommentWithAuthor};
-    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
-    use std::vec;
+    use crate::repo::user::{update_user, DEFAULT_AVATAR_URL};
+    use crate::repo::user_cache::UserCache;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use entity::entities::user;
+    use sea_orm::Set;
+    use serial_test::serial;
+    use std::{env, vec};
     use uuid::Uuid;
 
     #[tokio::test]
@@ -305,7 +569,7 @@ mod test_get_comments_by_article_id {
             .await?;
 
         let article = articles.unwrap().into_iter().next().unwrap();
-        let result = get_comments_by_article_id(&connection, article.id, None).await?;
+        let result = get_comments_by_article_id(&connection, article.id, None, None, None).await?;
         assert_eq!(result.len(), 2);
 
         Ok(())
@@ -320,12 +584,244 @@ mod test_get_comments_by_article_id {
             .build()
             .await?;
 
-        let result = get_comments_by_article_id(&connection, Uuid::new_v4(), None).await?;
+        let result =
+            get_comments_by_article_id(&connection, Uuid::new_v4(), None, None, None).await?;
         let expected: Vec<CommentWithAuthor> = vec![];
         assert_eq!(result, expected);
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn own_comments_not_followed() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .comments(Insert(vec![(1, 1), (2, 1)]))
+            .followers(Insert(vec![(2, 1)]))
+            .build()
+            .await?;
+        let current_user = users.unwrap().into_iter().next().unwrap();
+        let article = articles.unwrap().into_iter().next().unwrap();
+
+        let result =
+            get_comments_by_article_id(&connection, article.id, None, None, Some(current_user.id))
+                .await?;
+        assert_eq!(result.len(), 2);
+        for comment in result {
+            assert_eq!(
+                comment.author.following,
+                comment.author_id != current_user.id
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn can_edit_by_viewer() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .comments(Insert(vec![(1, 1), (2, 1)]))
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let users = users.unwrap();
+        let article_id = articles.unwrap()[0].id;
+
+        let result =
+            get_comments_by_article_id(&connection, article_id, None, None, Some(users[0].id))
+                .await?;
+        let editable: Vec<bool> = result.iter().map(|cmnt| cmnt.can_edit).collect();
+        assert_eq!(editable.iter().filter(|&&can_edit| can_edit).count(), 1);
+
+        let result = get_comments_by_article_id(&connection, article_id, None, None, None).await?;
+        assert!(result.iter().all(|cmnt| !cmnt.can_edit));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn author_without_image_gets_default_avatar() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                users, articles, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .comments(Insert(vec![(2, 1)]))
+            .build()
+            .await?;
+
+        let commenter = users.unwrap().into_iter().nth(1).unwrap();
+        let mut commenter: user::ActiveModel = commenter.into();
+        commenter.image = Set(None);
+        update_user(&connection, &UserCache::default(), commenter).await?;
+
+        let default_avatar = "https://example.com/default.png";
+        env::set_var(DEFAULT_AVATAR_URL, default_avatar);
+        let article_id = articles.unwrap()[0].id;
+        let result = get_comments_by_article_id(&connection, article_id, None, None, None).await;
+        env::remove_var(DEFAULT_AVATAR_URL);
+
+        let result = result?;
+        assert_eq!(result[0].author.image.as_deref(), Some(default_avatar));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn newest_first() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .comments(Insert(vec![(1, 1), (2, 1), (1, 1)]))
+            .build()
+            .await?;
+        let article_id = articles.unwrap()[0].id;
+
+        let result = get_comments_by_article_id(&connection, article_id, None, None, None).await?;
+        let bodies: Vec<&str> = result.iter().map(|cmt| cmt.body.as_str()).collect();
+        assert_eq!(bodies, vec!["comment3", "comment2", "comment1"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn limited_page() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .comments(Insert(vec![(1, 1), (2, 1), (1, 1), (2, 1)]))
+            .build()
+            .await?;
+        let article_id = articles.unwrap()[0].id;
+
+        let result =
+            get_comments_by_article_id(&connection, article_id, Some(2), None, None).await?;
+        let bodies: Vec<&str> = result.iter().map(|cmt| cmt.body.as_str()).collect();
+        assert_eq!(bodies, vec!["comment4", "comment3"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn offset_page() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .comments(Insert(vec![(1, 1), (2, 1), (1, 1), (2, 1)]))
+            .build()
+            .await?;
+        let article_id = articles.unwrap()[0].id;
+
+        let result =
+            get_comments_by_article_id(&connection, article_id, Some(2), Some(3), None).await?;
+        let bodies: Vec<&str> = result.iter().map(|cmt| cmt.body.as_str()).collect();
+        assert_eq!(bodies, vec!["comment1"]);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_count_comments_by_article_id {
+    use super::count_comments_by_article_id;
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use std::vec;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn count_article_comments() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2, 2]))
+            .comments(Insert(vec![(1, 1), (2, 1), (1, 2), (1, 1)]))
+            .build()
+            .await?;
+        let articles = articles.unwrap();
+
+        assert_eq!(
+            count_comments_by_article_id(&connection, articles[0].id).await?,
+            3
+        );
+        assert_eq!(
+            count_comments_by_article_id(&connection, articles[1].id).await?,
+            1
+        );
+        assert_eq!(
+            count_comments_by_article_id(&connection, articles[2].id).await?,
+            0
+        );
+        assert_eq!(
+            count_comments_by_article_id(&connection, Uuid::new_v4()).await?,
+            0
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_update_comment {
+    use super::update_comment;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn update_existing_comment() -> Result<(), TestErr> {
+        let (connection, TestData { comments, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .comments(Insert(vec![(1, 1), (1, 1)]))
+            .build()
+            .await?;
+        let comment = comments.unwrap().into_iter().nth(1).unwrap();
+
+        let result = update_comment(&connection, comment.id, "edited".to_owned()).await?;
+
+        assert_eq!(result.body, "edited");
+        assert_eq!(result.created_at, comment.created_at);
+        assert_ne!(result.updated_at, comment.updated_at);
+        assert_eq!(result.author_id, comment.author_id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_not_existing_comment() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Migration)
+            .articles(Migration)
+            .comments(Migration)
+            .build()
+            .await?;
+
+        let result = update_comment(&connection, Uuid::new_v4(), "edited".to_owned()).await;
+        assert!(
+            result.is_err_and(|err| err.to_string().ends_with("None of the records are updated"))
+        );
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
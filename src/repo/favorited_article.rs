@@ -1,17 +1,74 @@
-use entity::entities::{favorited_article, prelude::FavoritedArticle};
-use sea_orm::{DatabaseConnection, DbErr, DeleteResult, EntityTrait, InsertResult};
+use super::{
+    article::{get_articles_page, ArticleSort, ArticleWithAuthor, TagMatch},
+    user::get_user_by_id,
+    user_cache::UserCache,
+};
+use chrono::Utc;
+use entity::entities::{
+    article, favorited_article,
+    prelude::{Article, FavoritedArticle},
+};
+use sea_orm::{
+    sea_query::OnConflict, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    DbErr, DeleteResult, EntityTrait, InsertResult, QueryFilter, QuerySelect, TryInsertResult,
+};
+use uuid::Uuid;
+
+/// Outcome of favoriting article by user.
+#[derive(Debug, PartialEq)]
+pub enum FavoriteOutcome {
+    /// Article of another user favorited, author with `author_id` worth notifying.
+    NotifyAuthor { author_id: Uuid },
+    /// Own article favorited by its author.
+    SelfFavorited,
+    /// Article already favorited by user, nothing changed.
+    AlreadyFavorited,
+}
 
-/// Insert `favorite article` for the provided `ActiveModel`.
-/// Returns `InsertResult` with last inserted id on success, otherwise
-/// returns an `database error`.
+/// Insert `favorite article` for the provided `ActiveModel`. Creation time
+/// set to current time when not provided. Favoriting already favorited article is no-op.
+/// Returns `FavoriteOutcome` telling whether author of the article should be notified
+/// on success, otherwise returns an `database error`.
 /// Empty input produce error as not allowed on database level.
-/// See [`InsertResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.InsertResult.html)
-/// documentation for more details.
 pub async fn favorite_article(
     db: &DatabaseConnection,
-    favorite_article: favorited_article::ActiveModel,
-) -> Result<InsertResult<favorited_article::ActiveModel>, DbErr> {
-    FavoritedArticle::insert(favorite_article).exec(db).await
+    mut favorite_article: favorited_article::ActiveModel,
+) -> Result<FavoriteOutcome, DbErr> {
+    if favorite_article.created_at.is_not_set() {
+        favorite_article.created_at = Set(Some(Utc::now().naive_utc()));
+    }
+    let insert_result = FavoritedArticle::insert(favorite_article)
+        .on_conflict(
+            OnConflict::columns([
+                favorited_article::Column::ArticleId,
+                favorited_article::Column::UserId,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .do_nothing()
+        .exec(db)
+        .await?;
+
+    let TryInsertResult::Inserted(InsertResult {
+        last_insert_id: (article_id, user_id),
+    }) = insert_result
+    else {
+        return Ok(FavoriteOutcome::AlreadyFavorited);
+    };
+
+    let author_id = Article::find_by_id(article_id)
+        .select_only()
+        .column(article::Column::AuthorId)
+        .into_tuple::<Uuid>()
+        .one(db)
+        .await?
+        .ok_or(DbErr::RecordNotFound(article_id.to_string()))?;
+    if author_id == user_id {
+        Ok(FavoriteOutcome::SelfFavorited)
+    } else {
+        Ok(FavoriteOutcome::NotifyAuthor { author_id })
+    }
 }
 
 /// Delete `favorite article` for the provided `ActiveModel`.
@@ -26,6 +83,57 @@ pub async fn unfavorite_article(
     FavoritedArticle::delete(favorite_article).exec(db).await
 }
 
+/// Delete all `favorited article` records of the user with provided `id`.
+/// Returns `DeleteResult` with affected rows count on success, otherwise
+/// returns an `database error`.
+/// See [`DeleteResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.DeleteResult.html)
+/// documentation for more details.
+pub async fn delete_favorites_by_user<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+) -> Result<DeleteResult, DbErr> {
+    FavoritedArticle::delete_many()
+        .filter(favorited_article::Column::UserId.eq(user_id))
+        .exec(db)
+        .await
+}
+
+/// Fetch `articles` favorited by user with provided `id` with additional info (see
+/// ArticleWithAuthor for details). Selected the same way as articles listed by name of user
+/// who liked them, so both share visibility rules and ordering. Limit response by limit and
+/// offset parameters. Optional identifier used to determine whether the logged in user is a
+/// follower of the author and whether the article is liked by that user.
+/// Returns page of `articles` and total number of favorited articles on success, otherwise
+/// returns an `database error`.
+pub async fn get_favorited_articles(
+    db: &DatabaseConnection,
+    user_cache: &UserCache,
+    user_id: Uuid,
+    limit: u64,
+    offset: Option<u64>,
+    current_user_id: Option<Uuid>,
+) -> Result<(Vec<ArticleWithAuthor>, u64), DbErr> {
+    let Some(user) = get_user_by_id(db, user_cache, user_id).await? else {
+        return Ok((vec![], 0));
+    };
+
+    let page = get_articles_page(
+        db,
+        &[],
+        TagMatch::All,
+        None,
+        Some(&user.username),
+        None,
+        ArticleSort::Updated,
+        limit,
+        offset,
+        current_user_id,
+    )
+    .await?;
+
+    Ok((page.items, page.total))
+}
+
 /// Delete all existing `favorited article` records from database.
 /// Returns `DeleteResult` with affected rows count on success, otherwise
 /// returns an `database error`.
@@ -38,10 +146,13 @@ pub async fn empty_favorited_article_table(db: &DatabaseConnection) -> Result<De
 
 #[cfg(test)]
 mod test_favorite_article {
-    use super::favorite_article;
-    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use super::{favorite_article, FavoriteOutcome};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
     use entity::entities::{favorited_article, prelude::FavoritedArticle};
-    use sea_orm::Set;
+    use sea_orm::{EntityTrait, Set};
     use std::vec;
     use uuid::Uuid;
 
@@ -60,16 +171,21 @@ mod test_favorite_article {
             .await?;
 
         let article_id = articles.as_ref().unwrap()[1].id;
+        let author_id = users.as_ref().unwrap()[0].id;
         let user_id = users.as_ref().unwrap()[2].id;
 
         let model = favorited_article::ActiveModel {
             article_id: Set(article_id),
             user_id: Set(user_id),
+            ..Default::default()
         };
 
-        let last_id = (article_id, user_id);
-        let insert_result = favorite_article(&connection, model).await?;
-        assert_eq!(insert_result.last_insert_id, last_id);
+        let outcome = favorite_article(&connection, model).await?;
+        assert_eq!(outcome, FavoriteOutcome::NotifyAuthor { author_id });
+        let favorite = FavoritedArticle::find_by_id((article_id, user_id))
+            .one(&connection)
+            .await?;
+        assert!(favorite.is_some());
 
         Ok(())
     }
@@ -88,6 +204,7 @@ mod test_favorite_article {
         let model = favorited_article::ActiveModel {
             article_id: Set(Uuid::new_v4()),
             user_id: Set(user_id),
+            ..Default::default()
         };
 
         let insert_result = favorite_article(&connection, model).await;
@@ -111,6 +228,7 @@ mod test_favorite_article {
         let model = favorited_article::ActiveModel {
             article_id: Set(article_id),
             user_id: Set(Uuid::new_v4()),
+            ..Default::default()
         };
 
         let insert_result = favorite_article(&connection, model).await;
@@ -140,10 +258,62 @@ mod test_favorite_article {
         >(&favorited_articles);
         let model = actives.into_iter().next().unwrap();
 
-        let insert_result = favorite_article(&connection, model).await;
-        assert!(insert_result.is_err_and(|err| err.to_string().ends_with(
-            "UNIQUE constraint failed: favorited_article.article_id, favorited_article.user_id"
-        )));
+        let outcome = favorite_article(&connection, model).await?;
+        assert_eq!(outcome, FavoriteOutcome::AlreadyFavorited);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_twice() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                articles, users, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Migration)
+            .build()
+            .await?;
+
+        let model = favorited_article::ActiveModel {
+            article_id: Set(articles.unwrap()[0].id),
+            user_id: Set(users.unwrap()[0].id),
+            ..Default::default()
+        };
+
+        favorite_article(&connection, model.clone()).await?;
+        favorite_article(&connection, model).await?;
+        assert_eq!(FavoritedArticle::find().all(&connection).await?.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn favorite_own_article() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                articles, users, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Migration)
+            .build()
+            .await?;
+
+        let model = favorited_article::ActiveModel {
+            article_id: Set(articles.unwrap()[0].id),
+            user_id: Set(users.unwrap()[0].id),
+            ..Default::default()
+        };
+
+        let outcome = favorite_article(&connection, model).await?;
+        assert_eq!(outcome, FavoriteOutcome::SelfFavorited);
+        assert_eq!(FavoritedArticle::find().all(&connection).await?.len(), 1);
 
         Ok(())
     }
@@ -181,6 +351,137 @@ mod test_unfavorite_article {
     }
 }
 
+#[cfg(test)]
+mod test_delete_favorites_by_user {
+    use super::delete_favorites_by_user;
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use entity::entities::prelude::FavoritedArticle;
+    use sea_orm::EntityTrait;
+    use std::vec;
+
+    #[tokio::test]
+    async fn delete_existing_data() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1, 2]))
+            .favorited_articles(Insert(vec![(1, 1), (2, 1), (3, 1), (1, 2)]))
+            .build()
+            .await?;
+
+        let users = users.unwrap();
+        let delete_result = delete_favorites_by_user(&connection, users[0].id).await?;
+        assert_eq!(delete_result.rows_affected, 3_u64);
+
+        let remaining = FavoritedArticle::find().all(&connection).await?;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].user_id, users[1].id);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_favorited_articles {
+    use super::get_favorited_articles;
+    use crate::repo::user_cache::UserCache;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn page_and_total() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 2, 1, 2, 1]))
+            .favorited_articles(Insert(vec![(1, 1), (2, 1), (3, 1), (4, 1), (5, 2)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let users = users.unwrap();
+
+        let (articles, total) = get_favorited_articles(
+            &connection,
+            &UserCache::default(),
+            users[0].id,
+            3,
+            None,
+            None,
+        )
+        .await?;
+        let titles: Vec<String> = articles.into_iter().map(|artcl| artcl.title).collect();
+        assert_eq!(titles, vec!["title4", "title3", "title2"]);
+        assert_eq!(total, 4);
+
+        let (articles, total) = get_favorited_articles(
+            &connection,
+            &UserCache::default(),
+            users[0].id,
+            3,
+            Some(3),
+            None,
+        )
+        .await?;
+        let titles: Vec<String> = articles.into_iter().map(|artcl| artcl.title).collect();
+        assert_eq!(titles, vec!["title1"]);
+        assert_eq!(total, 4);
+
+        let (articles, total) = get_favorited_articles(
+            &connection,
+            &UserCache::default(),
+            users[0].id,
+            20,
+            None,
+            Some(users[0].id),
+        )
+        .await?;
+        assert!(articles.iter().all(|artcl| artcl.favorited));
+        assert_eq!(total, 4);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn no_favorites() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Insert(vec![(1, 1)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .followers(Migration)
+            .build()
+            .await?;
+        let users = users.unwrap();
+
+        let result = get_favorited_articles(
+            &connection,
+            &UserCache::default(),
+            users[1].id,
+            20,
+            None,
+            None,
+        )
+        .await?;
+        assert_eq!(result, (vec![], 0));
+        let result = get_favorited_articles(
+            &connection,
+            &UserCache::default(),
+            Uuid::new_v4(),
+            20,
+            None,
+            None,
+        )
+        .await?;
+        assert_eq!(result, (vec![], 0));
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "seed")]
 mod test_empty_favorited_article_table {
@@ -1,5 +1,15 @@
-use entity::entities::{follower, prelude::Follower};
-use sea_orm::{DatabaseConnection, DbErr, DeleteResult, EntityTrait, InsertResult};
+use super::user::{author_followed_by_current_user, Profile};
+use entity::entities::{
+    follower,
+    prelude::{Follower, User},
+    user,
+};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, DeleteResult, EntityTrait,
+    InsertResult, JoinType, QueryFilter, QueryOrder, QuerySelect, RelationTrait,
+};
+use std::collections::HashSet;
+use uuid::Uuid;
 
 /// Insert `follower` for the provided `ActiveModel`.
 /// Returns `InsertResult` with last inserted id on success, otherwise
@@ -25,6 +35,101 @@ pub async fn delete_follower(
     Follower::delete(follower).exec(db).await
 }
 
+/// Delete all `follower` records of user with provided id, both where the user is followed and
+/// where the user is a follower, so no follow edges of the user remain.
+/// Returns `DeleteResult` with affected rows count on success, otherwise
+/// returns an `database error`.
+/// See [`DeleteResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.DeleteResult.html)
+/// documentation for more details.
+pub async fn delete_all_follows_for<C: ConnectionTrait>(
+    db: &C,
+    user_id: Uuid,
+) -> Result<DeleteResult, DbErr> {
+    Follower::delete_many()
+        .filter(
+            follower::Column::UserId
+                .eq(user_id)
+                .or(follower::Column::FollowerId.eq(user_id)),
+        )
+        .exec(db)
+        .await
+}
+
+/// Fetch `profiles` of users following user with provided id.
+/// Optional identifier used to determine whether the logged in user is a follower of the profile.
+/// Ordered by username. Returns list of `profiles` on success, otherwise returns an `database error`.
+pub async fn get_followers(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    current_user_id: Option<Uuid>,
+) -> Result<Vec<Profile>, DbErr> {
+    User::find()
+        .join(JoinType::InnerJoin, follower::Relation::User2.def().rev())
+        .filter(follower::Column::UserId.eq(user_id))
+        .column_as(
+            author_followed_by_current_user(current_user_id),
+            "following",
+        )
+        .order_by_asc(user::Column::Username)
+        .into_model::<Profile>()
+        .all(db)
+        .await
+}
+
+/// Fetch `profiles` of users followed by user with provided id.
+/// Optional identifier used to determine whether the logged in user is a follower of the profile.
+/// Ordered by username, limited by optional limit and offset parameters (not limited by default).
+/// Returns list of `profiles` on success, otherwise returns an `database error`.
+pub async fn get_following(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    limit: Option<u64>,
+    offset: Option<u64>,
+    current_user_id: Option<Uuid>,
+) -> Result<Vec<Profile>, DbErr> {
+    User::find()
+        .join(JoinType::InnerJoin, follower::Relation::User1.def().rev())
+        .filter(follower::Column::FollowerId.eq(user_id))
+        .column_as(
+            author_followed_by_current_user(current_user_id),
+            "following",
+        )
+        .order_by_asc(user::Column::Username)
+        .limit(limit)
+        .offset(offset)
+        .into_model::<Profile>()
+        .all(db)
+        .await
+}
+
+/// Fetch ids of users, among provided ones, followed by user with provided `follower_id`.
+/// Single query used for whole list, so following status of list items is resolved without
+/// subquery per row. No query made when follower is not specified or no users provided.
+/// Returns set of followed user ids on success, otherwise returns an `database error`.
+pub async fn get_followed_user_ids(
+    db: &DatabaseConnection,
+    follower_id: Option<Uuid>,
+    user_ids: Vec<Uuid>,
+) -> Result<HashSet<Uuid>, DbErr> {
+    let Some(follower_id) = follower_id else {
+        return Ok(HashSet::new());
+    };
+    if user_ids.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    let followed = Follower::find()
+        .select_only()
+        .column(follower::Column::UserId)
+        .filter(follower::Column::FollowerId.eq(follower_id))
+        .filter(follower::Column::UserId.is_in(user_ids))
+        .into_tuple::<Uuid>()
+        .all(db)
+        .await?;
+
+    Ok(followed.into_iter().collect())
+}
+
 /// Delete all existing `follower records` from database.
 /// Returns `DeleteResult` with affected rows count on success, otherwise
 /// returns an `database error`.
@@ -158,6 +263,139 @@ mod test_delete_follower {
     }
 }
 
+#[cfg(test)]
+mod test_delete_all_follows_for {
+    use super::delete_all_follows_for;
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use entity::entities::prelude::Follower;
+    use sea_orm::EntityTrait;
+
+    #[tokio::test]
+    async fn both_sides_removed() -> Result<(), TestErr> {
+        // user1 follows user2 and user3, user2 and user4 follow user1, user3 follows user2
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(4))
+            .followers(Insert(vec![(2, 1), (3, 1), (1, 2), (1, 4), (2, 3)]))
+            .build()
+            .await?;
+        let users = users.unwrap();
+
+        let delete_result = delete_all_follows_for(&connection, users[0].id).await?;
+        assert_eq!(delete_result.rows_affected, 4_u64);
+
+        let remaining = Follower::find().all(&connection).await?;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].user_id, users[1].id);
+        assert_eq!(remaining[0].follower_id, users[2].id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn user_without_follows() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(3))
+            .followers(Insert(vec![(1, 2)]))
+            .build()
+            .await?;
+
+        let delete_result = delete_all_follows_for(&connection, users.unwrap()[2].id).await?;
+        assert_eq!(delete_result.rows_affected, 0_u64);
+        assert_eq!(Follower::find().all(&connection).await?.len(), 1);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_followers {
+    use super::{get_followers, get_following};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+
+    #[tokio::test]
+    async fn user_with_several_followers() -> Result<(), TestErr> {
+        // user2, user3 and user4 follow user1, user1 follows user3
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(4))
+            .followers(Insert(vec![(1, 4), (1, 2), (1, 3), (3, 1)]))
+            .build()
+            .await?;
+        let users = users.unwrap();
+
+        let result = get_followers(&connection, users[0].id, Some(users[0].id)).await?;
+        let usernames: Vec<&str> = result.iter().map(|prf| prf.username.as_str()).collect();
+        assert_eq!(usernames, vec!["username2", "username3", "username4"]);
+        let following: Vec<bool> = result.iter().map(|prf| prf.following).collect();
+        assert_eq!(following, vec![false, true, false]);
+
+        let result = get_following(&connection, users[0].id, None, None, None).await?;
+        let usernames: Vec<&str> = result.iter().map(|prf| prf.username.as_str()).collect();
+        assert_eq!(usernames, vec!["username3"]);
+        assert!(!result[0].following);
+
+        let result = get_following(&connection, users[1].id, None, None, Some(users[0].id)).await?;
+        let usernames: Vec<&str> = result.iter().map(|prf| prf.username.as_str()).collect();
+        assert_eq!(usernames, vec!["username1"]);
+
+        let result = get_following(&connection, users[1].id, Some(1), Some(1), None).await?;
+        assert!(result.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn user_without_followers() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .followers(Migration)
+            .build()
+            .await?;
+        let users = users.unwrap();
+
+        assert!(get_followers(&connection, users[0].id, None)
+            .await?
+            .is_empty());
+        assert!(
+            get_following(&connection, users[0].id, None, None, Some(users[1].id))
+                .await?
+                .is_empty()
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_followed_user_ids {
+    use super::get_followed_user_ids;
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use std::collections::HashSet;
+
+    #[tokio::test]
+    async fn followed_among_provided() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(4))
+            .followers(Insert(vec![(2, 1), (3, 1), (4, 2)]))
+            .build()
+            .await?;
+        let ids: Vec<_> = users.unwrap().into_iter().map(|usr| usr.id).collect();
+
+        let result = get_followed_user_ids(&connection, Some(ids[0]), vec![ids[1], ids[3]]).await?;
+        assert_eq!(result, HashSet::from([ids[1]]));
+
+        let result = get_followed_user_ids(&connection, None, ids.clone()).await?;
+        assert!(result.is_empty());
+
+        let result = get_followed_user_ids(&connection, Some(ids[0]), vec![]).await?;
+        assert!(result.is_empty());
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "seed")]
 mod test_empty_follower_table {
@@ -0,0 +1,173 @@
+use entity::entities::{
+    article, article_tag, favorited_article,
+    prelude::{Article, ArticleTag, FavoritedArticle, Tag, User},
+    tag, user,
+};
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QuerySelect,
+    QueryTrait, TransactionTrait,
+};
+
+/// Fetch join rows referencing not existing records: `favorited articles` of missing article
+/// or user, `article tags` of missing article or tag. Such rows could be left by data created
+/// before foreign keys were enforced or by partial delete.
+/// Returns `DanglingReport` on success, otherwise returns an `database error`.
+pub async fn find_dangling_rows(db: &DatabaseConnection) -> Result<DanglingReport, DbErr> {
+    let favorited_articles = FavoritedArticle::find()
+        .filter(dangling_favorited_article())
+        .all(db)
+        .await?;
+    let article_tags = ArticleTag::find()
+        .filter(dangling_article_tag())
+        .all(db)
+        .await?;
+
+    Ok(DanglingReport {
+        favorited_articles,
+        article_tags,
+    })
+}
+
+/// Delete join rows referencing not existing records (see find_dangling_rows for details)
+/// in single transaction.
+/// Returns number of deleted rows on success, otherwise returns an `database error`.
+pub async fn purge_dangling_rows(db: &DatabaseConnection) -> Result<u64, DbErr> {
+    let txn = db.begin().await?;
+
+    let favorited_res = FavoritedArticle::delete_many()
+        .filter(dangling_favorited_article())
+        .exec(&txn)
+        .await?;
+    let article_tag_res = ArticleTag::delete_many()
+        .filter(dangling_article_tag())
+        .exec(&txn)
+        .await?;
+
+    txn.commit().await?;
+    Ok(favorited_res.rows_affected + article_tag_res.rows_affected)
+}
+
+/// Returns condition for `favorited article` of missing article or user.
+fn dangling_favorited_article() -> Condition {
+    Condition::any()
+        .add(
+            favorited_article::Column::ArticleId.not_in_subquery(
+                Article::find()
+                    .select_only()
+                    .column(article::Column::Id)
+                    .into_query(),
+            ),
+        )
+        .add(
+            favorited_article::Column::UserId.not_in_subquery(
+                User::find()
+                    .select_only()
+                    .column(user::Column::Id)
+                    .into_query(),
+            ),
+        )
+}
+
+/// Returns condition for `article tag` of missing article or tag.
+fn dangling_article_tag() -> Condition {
+    Condition::any()
+        .add(
+            article_tag::Column::ArticleId.not_in_subquery(
+                Article::find()
+                    .select_only()
+                    .column(article::Column::Id)
+                    .into_query(),
+            ),
+        )
+        .add(
+            article_tag::Column::TagId.not_in_subquery(
+                Tag::find()
+                    .select_only()
+                    .column(tag::Column::Id)
+                    .into_query(),
+            ),
+        )
+}
+
+/// Struct describing join rows referencing not existing records.
+#[derive(Debug, Default, PartialEq)]
+pub struct DanglingReport {
+    pub favorited_articles: Vec<favorited_article::Model>,
+    pub article_tags: Vec<article_tag::Model>,
+}
+
+#[cfg(test)]
+mod test_dangling_rows {
+    use super::{find_dangling_rows, purge_dangling_rows, DanglingReport};
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use entity::entities::{favorited_article, prelude::FavoritedArticle};
+    use sea_orm::{ConnectionTrait, EntityTrait, PaginatorTrait, Set};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn find_and_purge_dangling_favorite() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Insert(vec![(1, 2)]))
+            .tags(Insert(1))
+            .article_tags(Insert(vec![(1, 1)]))
+            .build()
+            .await?;
+        let user_id = users.unwrap()[0].id;
+
+        // Favorite of missing article could be inserted only with foreign keys disabled
+        connection
+            .execute_unprepared("PRAGMA foreign_keys = OFF")
+            .await?;
+        let dangling_article_id = Uuid::new_v4();
+        FavoritedArticle::insert(favorited_article::ActiveModel {
+            article_id: Set(dangling_article_id),
+            user_id: Set(user_id),
+            ..Default::default()
+        })
+        .exec(&connection)
+        .await?;
+        connection
+            .execute_unprepared("PRAGMA foreign_keys = ON")
+            .await?;
+
+        let report = find_dangling_rows(&connection).await?;
+        assert_eq!(report.favorited_articles.len(), 1);
+        assert_eq!(report.favorited_articles[0].article_id, dangling_article_id);
+        assert!(report.article_tags.is_empty());
+
+        assert_eq!(purge_dangling_rows(&connection).await?, 1);
+        assert_eq!(
+            find_dangling_rows(&connection).await?,
+            DanglingReport::default()
+        );
+        // Valid favorite kept
+        assert_eq!(FavoritedArticle::find().count(&connection).await?, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn nothing_dangling() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .favorited_articles(Insert(vec![(1, 1)]))
+            .tags(Migration)
+            .article_tags(Migration)
+            .build()
+            .await?;
+
+        assert_eq!(
+            find_dangling_rows(&connection).await?,
+            DanglingReport::default()
+        );
+        assert_eq!(purge_dangling_rows(&connection).await?, 0);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,12 @@
 pub mod article;
 pub mod article_tag;
+pub mod block;
 pub mod comment;
 pub mod favorited_article;
 pub mod follower;
+pub mod maintenance;
+pub mod reaction;
 pub mod tag;
+pub mod time;
 pub mod user;
+pub mod user_cache;
@@ -0,0 +1,250 @@
+use entity::entities::{prelude::Reaction, reaction};
+use migration::OnConflict;
+use sea_orm::{
+    query::*, ColumnTrait, DatabaseConnection, DbErr, DeleteResult, EntityTrait, FromQueryResult,
+    InsertResult, QueryFilter, TryInsertResult,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+/// Insert `reaction` for the provided `ActiveModel`. Only one reaction of each kind allowed
+/// per article and user, already existing reaction is ignored.
+/// Returns `TryInsertResult` on success, otherwise returns an `database error`.
+/// Empty kind produce error as not allowed on database level.
+/// See [`TryInsertResult`](https://docs.rs/sea-orm/latest/sea_orm/enum.TryInsertResult.html)
+/// documentation for more details.
+pub async fn add_reaction(
+    db: &DatabaseConnection,
+    reaction: reaction::ActiveModel,
+) -> Result<TryInsertResult<InsertResult<reaction::ActiveModel>>, DbErr> {
+    Reaction::insert_many([reaction])
+        .on_conflict(
+            OnConflict::columns([
+                reaction::Column::ArticleId,
+                reaction::Column::UserId,
+                reaction::Column::Kind,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .on_empty_do_nothing()
+        .exec(db)
+        .await
+}
+
+/// Delete `reaction` for the provided `ActiveModel`.
+/// Returns `DeleteResult` with affected rows count on success, otherwise
+/// returns an `database error`.
+/// See [`DeleteResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.DeleteResult.html)
+/// documentation for more details.
+pub async fn remove_reaction(
+    db: &DatabaseConnection,
+    reaction: reaction::ActiveModel,
+) -> Result<DeleteResult, DbErr> {
+    Reaction::delete(reaction).exec(db).await
+}
+
+/// Count `reactions` of the article with provided `id`, grouped by kind. Ordered by kind.
+/// Returns vec of reaction counts on success, otherwise returns an `database error`.
+pub async fn count_reactions_by_kind(
+    db: &DatabaseConnection,
+    article_id: Uuid,
+) -> Result<Vec<ReactionCount>, DbErr> {
+    Reaction::find()
+        .select_only()
+        .column(reaction::Column::Kind)
+        .column_as(reaction::Column::UserId.count(), "count")
+        .filter(reaction::Column::ArticleId.eq(article_id))
+        .group_by(reaction::Column::Kind)
+        .order_by_asc(reaction::Column::Kind)
+        .into_model::<ReactionCount>()
+        .all(db)
+        .await
+}
+
+#[derive(Debug, Serialize, PartialEq, FromQueryResult)]
+pub struct ReactionCount {
+    pub kind: String,
+    pub count: i64,
+}
+
+#[cfg(test)]
+mod test_add_reaction {
+    use super::add_reaction;
+    use crate::tests::{execute_migration, Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use entity::entities::{prelude::Reaction, reaction};
+    use sea_orm::{EntityTrait, PaginatorTrait, Set, TryInsertResult};
+    use std::vec;
+
+    #[tokio::test]
+    async fn insert_not_exist_data() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                articles, users, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .build()
+            .await?;
+        execute_migration(&connection, "m20231201_000009_create_reaction_table").await?;
+
+        let article_id = articles.unwrap()[0].id;
+        let user_id = users.unwrap()[0].id;
+
+        let model = reaction::ActiveModel {
+            article_id: Set(article_id),
+            user_id: Set(user_id),
+            kind: Set("clap".to_owned()),
+        };
+
+        let insert_result = add_reaction(&connection, model).await?;
+        assert!(matches!(insert_result, TryInsertResult::Inserted(_)));
+
+        let stored = Reaction::find_by_id((article_id, user_id, "clap".to_owned()))
+            .one(&connection)
+            .await?;
+        assert!(stored.is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn insert_existing_data() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                articles, users, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .build()
+            .await?;
+        execute_migration(&connection, "m20231201_000009_create_reaction_table").await?;
+
+        let model = reaction::ActiveModel {
+            article_id: Set(articles.unwrap()[0].id),
+            user_id: Set(users.unwrap()[0].id),
+            kind: Set("clap".to_owned()),
+        };
+
+        add_reaction(&connection, model.clone()).await?;
+        // Repeated reaction is ignored
+        let insert_result = add_reaction(&connection, model).await?;
+        assert!(matches!(insert_result, TryInsertResult::Conflicted));
+        assert_eq!(Reaction::find().count(&connection).await?, 1);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_remove_reaction {
+    use super::{add_reaction, remove_reaction};
+    use crate::tests::{execute_migration, Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use entity::entities::reaction;
+    use sea_orm::Set;
+    use std::vec;
+
+    #[tokio::test]
+    async fn delete_existing_data() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                articles, users, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .build()
+            .await?;
+        execute_migration(&connection, "m20231201_000009_create_reaction_table").await?;
+
+        let model = reaction::ActiveModel {
+            article_id: Set(articles.unwrap()[0].id),
+            user_id: Set(users.unwrap()[0].id),
+            kind: Set("clap".to_owned()),
+        };
+        add_reaction(&connection, model.clone()).await?;
+
+        let delete_result = remove_reaction(&connection, model.clone()).await?;
+        assert_eq!(delete_result.rows_affected, 1_u64);
+
+        let delete_result = remove_reaction(&connection, model).await?;
+        assert_eq!(delete_result.rows_affected, 0_u64);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_count_reactions_by_kind {
+    use super::{add_reaction, count_reactions_by_kind, ReactionCount};
+    use crate::tests::{execute_migration, Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use entity::entities::reaction;
+    use sea_orm::Set;
+    use std::vec;
+
+    #[tokio::test]
+    async fn count_existing_reactions() -> Result<(), TestErr> {
+        let (
+            connection,
+            TestData {
+                articles, users, ..
+            },
+        ) = TestDataBuilder::new()
+            .users(Insert(2))
+            .articles(Insert(vec![1, 1]))
+            .build()
+            .await?;
+        execute_migration(&connection, "m20231201_000009_create_reaction_table").await?;
+
+        let articles = articles.unwrap();
+        let users = users.unwrap();
+        for (article, user, kind) in [
+            (0, 0, "clap"),
+            (0, 1, "clap"),
+            (0, 0, "heart"),
+            (1, 0, "clap"),
+        ] {
+            let model = reaction::ActiveModel {
+                article_id: Set(articles[article].id),
+                user_id: Set(users[user].id),
+                kind: Set(kind.to_owned()),
+            };
+            add_reaction(&connection, model).await?;
+        }
+
+        let result = count_reactions_by_kind(&connection, articles[0].id).await?;
+        let expected = vec![
+            ReactionCount {
+                kind: "clap".to_owned(),
+                count: 2,
+            },
+            ReactionCount {
+                kind: "heart".to_owned(),
+                count: 1,
+            },
+        ];
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn no_reactions() -> Result<(), TestErr> {
+        let (connection, TestData { articles, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .build()
+            .await?;
+        execute_migration(&connection, "m20231201_000009_create_reaction_table").await?;
+
+        let result = count_reactions_by_kind(&connection, articles.unwrap()[0].id).await?;
+        assert!(result.is_empty());
+
+        Ok(())
+    }
+}
@@ -1,27 +1,36 @@
-use entity::entities::{prelude::Tag, tag};
-use migration::{Alias, Expr, OnConflict};
-#[cfg(feature = "seed")]
-use sea_orm::DeleteResult;
+use entity::entities::{
+    article_tag,
+    prelude::{ArticleTag, Tag},
+    tag,
+};
+use migration::{Alias, Expr, Func, OnConflict, SimpleExpr};
 use sea_orm::{
-    DatabaseConnection, DbErr, EntityTrait, InsertResult, QueryFilter, QuerySelect, TryInsertResult,
+    ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, DeleteResult,
+    EntityTrait, InsertResult, JoinType, Order, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, QueryTrait, RelationTrait, TryInsertResult,
 };
+use std::str::FromStr;
 use uuid::Uuid;
 
-/// Insert `tags` for the provided `ActiveModel`s. Ignore models with existing tag names.
+const TAG_IDS_ATTEMPTS: usize = 3;
+
+/// Insert `tags` for the provided `ActiveModel`s. Ignore models with existing tag names,
+/// compared case insensitively (`Rust` and `rust` is the same tag).
 /// Returns `Inserted(InsertResult)` with last inserted id on success, otherwise
 /// returns an `database error`.
 /// Empty input produce `Empty` result.
 /// See [`TryInsertResult`](https://docs.rs/sea-orm/latest/sea_orm/enum.TryInsertResult.html)
 /// documentation for more details.
-pub async fn create_tags(
-    db: &DatabaseConnection,
+pub async fn create_tags<C: ConnectionTrait>(
+    db: &C,
     tags: Vec<tag::ActiveModel>,
 ) -> Result<TryInsertResult<InsertResult<tag::ActiveModel>>, DbErr> {
     // Filter empty tag names
     let tags = tags.into_iter().filter(|model| !model.is_empty());
     Tag::insert_many(tags)
         .on_conflict(
-            OnConflict::column(tag::Column::TagName)
+            // Lowercased tag name, generated by database (see tag migrations)
+            OnConflict::column(Alias::new("tag_name_ci"))
                 .do_nothing()
                 .to_owned(),
         )
@@ -45,33 +54,167 @@ pub async fn insert_tag(
     Tag::insert(tag).exec(db).await
 }
 
-/// Fetch `tag ids` for the provided `tag names`. Ignore not existing tag names.
+/// Fetch `tag ids` for the provided `tag names`, compared case insensitively. Ignore not existing tag names.
 /// Returns `list of tag names` on success, otherwise returns an `database error`.
 /// Empty input produce empty result.
-pub async fn get_tags_ids(db: &DatabaseConnection, tags: Vec<String>) -> Result<Vec<Uuid>, DbErr> {
+pub async fn get_tags_ids<C: ConnectionTrait>(
+    db: &C,
+    tags: Vec<String>,
+) -> Result<Vec<Uuid>, DbErr> {
     // Filter empty tag names
     let tags: Vec<String> = tags.into_iter().filter(|tg| !tg.is_empty()).collect();
     if tags.is_empty() {
         return Ok(Vec::new());
     };
     Tag::find()
-        .filter(Expr::expr(Expr::col(tag::Column::TagName).cast_as(Alias::new("text"))).is_in(tags))
+        .filter(tag_name_in(tags))
         .into_tuple::<Uuid>()
         .all(db)
         .await
 }
 
-/// Fetch all `tag names` from database.
+/// Returns expression for determine whether the tag name is one of provided `tag names`,
+/// compared case insensitively. Lowercase tag name generated by database (`tag_name_ci`
+/// column) is compared, so its unique index is used.
+pub fn tag_name_in(tags: impl IntoIterator<Item = String>) -> SimpleExpr {
+    Expr::col((tag::Entity, Alias::new("tag_name_ci")))
+        .is_in(tags.into_iter().map(|tg| Func::lower(Expr::val(tg))))
+}
+
+/// Insert not existing `tags` for the provided `tag names` and fetch ids of all of them.
+/// Tag created by concurrent request may be momentarily invisible, thus insert and fetch
+/// are retried until every requested tag is found, so no tag is silently dropped.
+/// Returns `list of tag ids` on success, otherwise returns an `database error`, including
+/// the case when some tags are still missing after all attempts.
+/// Empty input produce empty result.
+pub async fn create_and_get_tags_ids<C: ConnectionTrait>(
+    db: &C,
+    tags: Vec<String>,
+) -> Result<Vec<Uuid>, DbErr> {
+    let mut tags: Vec<String> = tags.into_iter().filter(|tg| !tg.is_empty()).collect();
+    tags.sort_by_key(|tg| tg.to_lowercase());
+    tags.dedup_by_key(|tg| tg.to_lowercase());
+
+    for _ in 0..TAG_IDS_ATTEMPTS {
+        let tag_models = tags
+            .iter()
+            .map(|tg| tag::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                tag_name: Set(tg.to_owned()),
+            })
+            .collect();
+        create_tags(db, tag_models).await?;
+
+        let ids = get_tags_ids(db, tags.clone()).await?;
+        if ids.len() == tags.len() {
+            return Ok(ids);
+        }
+    }
+
+    Err(DbErr::RecordNotFound(format!(
+        "Unable to fetch all tags of {tags:?}"
+    )))
+}
+
+/// Fetch id of `tag` with provided name, inserting it when not exist. Name is trimmed.
+/// Returns `tag id` on success, otherwise returns an `database error`, including the case
+/// of empty name.
+pub async fn get_or_create_tag(db: &DatabaseConnection, name: &str) -> Result<Uuid, DbErr> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DbErr::Custom("Tag name must not be empty".to_owned()));
+    }
+
+    create_and_get_tags_ids(db, vec![name.to_owned()])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(DbErr::RecordNotFound(format!("Unable to fetch tag {name}")))
+}
+
+/// Delete `tag` with provided id when it is not attached to any article.
+/// Returns `DeleteResult` with affected rows count on success, otherwise
+/// returns an `database error`.
+/// See [`DeleteResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.DeleteResult.html)
+/// documentation for more details.
+pub async fn delete_tag_if_orphaned<C: ConnectionTrait>(
+    db: &C,
+    tag_id: Uuid,
+) -> Result<DeleteResult, DbErr> {
+    Tag::delete_many()
+        .filter(tag::Column::Id.eq(tag_id))
+        .filter(
+            tag::Column::Id.not_in_subquery(
+                ArticleTag::find()
+                    .select_only()
+                    .column(article_tag::Column::TagId)
+                    .into_query(),
+            ),
+        )
+        .exec(db)
+        .await
+}
+
+/// Fetch `tag names` from database ordered according to `sort` (see TagSort for details), tag
+/// name breaks ties, so order is stable between requests. Limit response by optional limit
+/// and offset parameters, all tags fetched when not provided.
 /// Returns `list of tag names` on success, otherwise returns an `database error`.
-pub async fn get_tags(db: &DatabaseConnection) -> Result<Vec<String>, DbErr> {
+pub async fn get_tags(
+    db: &DatabaseConnection,
+    sort: TagSort,
+    limit: Option<u64>,
+    offset: Option<u64>,
+) -> Result<Vec<String>, DbErr> {
     Tag::find()
         .select_only()
         .column(tag::Column::TagName)
+        .apply_if((sort == TagSort::Usage).then_some(()), |query, _| {
+            query
+                .join(JoinType::LeftJoin, article_tag::Relation::Tag.def().rev())
+                .group_by(tag::Column::Id)
+                .group_by(tag::Column::TagName)
+                .order_by(
+                    Expr::col((ArticleTag, article_tag::Column::ArticleId)).count(),
+                    Order::Desc,
+                )
+        })
+        .order_by_asc(tag::Column::TagName)
+        // Offset without limit is not supported by every backend, thus use largest one
+        .limit(limit.or(offset.map(|_| i64::MAX as u64)))
+        .offset(offset)
         .into_tuple::<String>()
         .all(db)
         .await
 }
 
+/// Order of `tags` listing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TagSort {
+    /// Alphabetically by tag name.
+    #[default]
+    Name,
+    /// Most used first, by number of articles tagged.
+    Usage,
+}
+
+impl FromStr for TagSort {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "name" => Ok(TagSort::Name),
+            "usage" => Ok(TagSort::Usage),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Count all `tags` in database. Useful for limit/offset pagination.
+/// Returns quantity of `tags` on success, otherwise returns an `database error`.
+pub async fn get_tags_count(db: &DatabaseConnection) -> Result<u64, DbErr> {
+    Tag::find().count(db).await
+}
+
 /// Delete all existing `tag records` from database.
 /// Returns `DeleteResult` with affected rows count on success, otherwise
 /// returns an `database error`.
@@ -85,10 +228,13 @@ pub async fn empty_tag_table(db: &DatabaseConnection) -> Result<DeleteResult, Db
 #[cfg(test)]
 mod test_create_tags {
     use super::{create_tags, insert_tag};
-    use crate::tests::{Operation::Create, TestData, TestDataBuilder, TestErr};
+    use crate::tests::{
+        Operation::{Create, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
     use entity::entities::{prelude::Tag, tag};
     use sea_orm::{
-        Set,
+        EntityTrait, Set,
         TryInsertResult::{Conflicted, Empty, Inserted},
     };
     use std::vec;
@@ -117,7 +263,7 @@ mod test_create_tags {
             TestDataBuilder::new().tags(Create(5)).build().await?;
         let actives = TestDataBuilder::activate_models::<Tag, tag::ActiveModel>(&tags);
 
-        let id = tags.as_ref().unwrap().iter().nth(1).unwrap().id;
+        let id = tags.as_ref().unwrap().get(1).unwrap().id;
         let model = tag::ActiveModel {
             id: Set(id),
             tag_name: Set("tag_name99".to_owned()),
@@ -157,6 +303,29 @@ mod test_create_tags {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn insert_case_variant() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new().tags(Migration).build().await?;
+
+        for name in ["Rust", "rust"] {
+            let model = tag::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                tag_name: Set(name.to_owned()),
+            };
+            create_tags(&connection, vec![model]).await?;
+        }
+
+        let names: Vec<String> = Tag::find()
+            .all(&connection)
+            .await?
+            .into_iter()
+            .map(|tg| tg.tag_name)
+            .collect();
+        assert_eq!(names, ["Rust"]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn insert_empty_collection() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new().tags(Create(5)).build().await?;
@@ -226,9 +395,10 @@ mod test_insert_tag {
 
         let insert_result = insert_tag(&connection, model2).await;
 
+        // Either tag_name or its lowercased tag_name_ci unique constraint
         assert!(insert_result.is_err_and(|err| err
             .to_string()
-            .ends_with("UNIQUE constraint failed: tag.tag_name")));
+            .contains("UNIQUE constraint failed: tag.tag_name")));
 
         Ok(())
     }
@@ -278,7 +448,6 @@ mod test_get_tags_ids {
             .as_ref()
             .unwrap()
             .iter()
-            .cloned()
             .map(|model| model.id)
             .collect();
 
@@ -323,14 +492,181 @@ mod test_get_tags_ids {
     }
 }
 
+#[cfg(test)]
+mod test_create_and_get_tags_ids {
+    use super::create_and_get_tags_ids;
+    use crate::tests::{Operation::Insert, TestDataBuilder, TestErr};
+    use entity::entities::prelude::Tag;
+    use sea_orm::EntityTrait;
+
+    #[tokio::test]
+    async fn new_and_existing_tags() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new().tags(Insert(2)).build().await?;
+
+        let input = vec![
+            "tag_name1".to_owned(),
+            "new_tag".to_owned(),
+            "new_tag".to_owned(),
+            "".to_owned(),
+        ];
+        let result = create_and_get_tags_ids(&connection, input).await?;
+        assert_eq!(result.len(), 2);
+
+        let tags = Tag::find().all(&connection).await?;
+        assert_eq!(tags.len(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn case_variants_collapse() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new().tags(Insert(2)).build().await?;
+
+        let first = create_and_get_tags_ids(&connection, vec!["Rust".to_owned()]).await?;
+        let input = vec!["rust".to_owned(), "RUST".to_owned(), "TAG_NAME1".to_owned()];
+        let second = create_and_get_tags_ids(&connection, input).await?;
+        assert_eq!(second.len(), 2);
+        assert!(second.contains(&first[0]));
+
+        let tag = Tag::find_by_id(first[0]).one(&connection).await?.unwrap();
+        assert_eq!(tag.tag_name, "Rust");
+        assert_eq!(Tag::find().all(&connection).await?.len(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn empty_input() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new().tags(Insert(2)).build().await?;
+
+        let result = create_and_get_tags_ids(&connection, vec![]).await?;
+        assert!(result.is_empty());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_or_create_tag {
+    use super::get_or_create_tag;
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use entity::entities::prelude::Tag;
+    use sea_orm::{EntityTrait, PaginatorTrait};
+
+    #[tokio::test]
+    async fn existing_tag() -> Result<(), TestErr> {
+        let (connection, TestData { tags, .. }) =
+            TestDataBuilder::new().tags(Insert(2)).build().await?;
+
+        let result = get_or_create_tag(&connection, " tag_name1 ").await?;
+        assert_eq!(result, tags.unwrap()[0].id);
+        assert_eq!(Tag::find().count(&connection).await?, 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn new_tag() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new().tags(Insert(2)).build().await?;
+
+        let result = get_or_create_tag(&connection, "new_tag").await?;
+        let created = Tag::find_by_id(result).one(&connection).await?.unwrap();
+        assert_eq!(created.tag_name, "new_tag");
+        assert_eq!(Tag::find().count(&connection).await?, 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn empty_name() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new().tags(Insert(2)).build().await?;
+
+        let result = get_or_create_tag(&connection, "  ").await;
+        assert!(result.is_err());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_delete_tag_if_orphaned {
+    use super::delete_tag_if_orphaned;
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use entity::entities::prelude::Tag;
+    use sea_orm::{EntityTrait, PaginatorTrait};
+    use std::vec;
+
+    #[tokio::test]
+    async fn attached_and_orphaned_tag() -> Result<(), TestErr> {
+        let (connection, TestData { tags, .. }) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1]))
+            .tags(Insert(2))
+            .article_tags(Insert(vec![(1, 1)]))
+            .build()
+            .await?;
+        let tags = tags.unwrap();
+
+        let result = delete_tag_if_orphaned(&connection, tags[0].id).await?;
+        assert_eq!(result.rows_affected, 0);
+
+        let result = delete_tag_if_orphaned(&connection, tags[1].id).await?;
+        assert_eq!(result.rows_affected, 1);
+        assert_eq!(Tag::find().count(&connection).await?, 1);
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test_get_tags {
-    use super::get_tags;
+    use super::{get_tags, get_tags_count, TagSort};
     use crate::tests::{
         Operation::{Create, Insert},
         TestData, TestDataBuilder, TestErr,
     };
 
+    #[tokio::test]
+    async fn sorted_by_name() -> Result<(), TestErr> {
+        // tag_name10.. sort before tag_name2, unlike insertion order
+        let (connection, _) = TestDataBuilder::new().tags(Insert(12)).build().await?;
+
+        let result = get_tags(&connection, TagSort::Name, None, None).await?;
+        assert_eq!(result.len(), 12);
+        assert!(result.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(result[1], "tag_name10");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sorted_by_usage() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(1))
+            .articles(Insert(vec![1, 1, 1]))
+            .tags(Insert(5))
+            .article_tags(Insert(vec![(1, 1), (1, 2), (2, 2), (3, 2), (1, 3), (2, 3)]))
+            .build()
+            .await?;
+
+        let result = get_tags(&connection, TagSort::Usage, None, None).await?;
+        assert_eq!(
+            result,
+            [
+                "tag_name2",
+                "tag_name3",
+                "tag_name1",
+                "tag_name4",
+                "tag_name5"
+            ]
+        );
+
+        let result = get_tags(&connection, TagSort::Usage, Some(2), Some(1)).await?;
+        assert_eq!(result, ["tag_name3", "tag_name1"]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn get_existing_tags() -> Result<(), TestErr> {
         let (connection, TestData { tags, .. }) =
@@ -341,7 +677,7 @@ mod test_get_tags {
             .map(|model| model.tag_name)
             .collect();
 
-        let result = get_tags(&connection).await?;
+        let result = get_tags(&connection, TagSort::Name, None, None).await?;
         assert_eq!(result, expected);
 
         Ok(())
@@ -351,18 +687,34 @@ mod test_get_tags {
     async fn get_empty_list() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new().tags(Create(1)).build().await?;
         let expected: Vec<String> = Vec::new();
-        let result = get_tags(&connection).await?;
+        let result = get_tags(&connection, TagSort::Name, None, None).await?;
 
         assert_eq!(result, expected);
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn get_page_of_tags() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new().tags(Insert(5)).build().await?;
+
+        let result = get_tags(&connection, TagSort::Name, Some(2), Some(1)).await?;
+        assert_eq!(result, ["tag_name2", "tag_name3"]);
+
+        let result = get_tags(&connection, TagSort::Name, None, Some(4)).await?;
+        assert_eq!(result, ["tag_name5"]);
+
+        let count = get_tags_count(&connection).await?;
+        assert_eq!(count, 5);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 #[cfg(feature = "seed")]
 mod test_empty_tag_table {
-    use super::{empty_tag_table, get_tags};
+    use super::{empty_tag_table, get_tags, TagSort};
     use crate::tests::{
         Operation::{Create, Insert},
         TestData, TestDataBuilder, TestErr,
@@ -375,7 +727,7 @@ mod test_empty_tag_table {
         let expected: Vec<String> = Vec::new();
 
         let delete_result = empty_tag_table(&connection).await?;
-        let result = get_tags(&connection).await?;
+        let result = get_tags(&connection, TagSort::Name, None, None).await?;
         assert_eq!(delete_result.rows_affected, tags.unwrap().len() as u64);
         assert_eq!(result, expected);
 
@@ -388,7 +740,7 @@ mod test_empty_tag_table {
         let expected: Vec<String> = Vec::new();
 
         let delete_result = empty_tag_table(&connection).await?;
-        let result = get_tags(&connection).await?;
+        let result = get_tags(&connection, TagSort::Name, None, None).await?;
         assert_eq!(delete_result.rows_affected, expected.len() as u64);
         assert_eq!(result, expected);
 
@@ -0,0 +1,46 @@
+use chrono::SecondsFormat;
+use sea_orm::entity::prelude::DateTime;
+use serde::Serializer;
+
+/// Serialize optional naive time, stored as UTC, as RFC3339 string with milliseconds and
+/// trailing `Z`, e.g. `2024-01-02T03:04:05.678Z`. Missing time serialized as `null`.
+pub fn serialize_utc<S: Serializer>(
+    time: &Option<DateTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match time {
+        Some(time) => {
+            serializer.serialize_str(&time.and_utc().to_rfc3339_opts(SecondsFormat::Millis, true))
+        }
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod test_serialize_utc {
+    use super::serialize_utc;
+    use chrono::NaiveDate;
+    use sea_orm::entity::prelude::DateTime;
+    use serde::Serialize;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct Timestamped {
+        #[serde(serialize_with = "serialize_utc")]
+        time: Option<DateTime>,
+    }
+
+    #[test]
+    fn rfc3339_with_z() {
+        let time = NaiveDate::from_ymd_opt(2024, 1, 2)
+            .unwrap()
+            .and_hms_milli_opt(3, 4, 5, 678)
+            .unwrap();
+
+        let value = serde_json::to_value(Timestamped { time: Some(time) }).unwrap();
+        assert_eq!(value, json!({"time": "2024-01-02T03:04:05.678Z"}));
+
+        let value = serde_json::to_value(Timestamped { time: None }).unwrap();
+        assert_eq!(value, json!({ "time": null }));
+    }
+}
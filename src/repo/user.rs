@@ -1,17 +1,30 @@
-use crate::middleware::auth::create_token;
+use super::{
+    article::delete_articles_by_author, comment::delete_comments_by_author,
+    favorited_article::delete_favorites_by_user, follower::delete_all_follows_for,
+    user_cache::UserCache,
+};
+use crate::{
+    api::error::{user_unique_err, ApiErr},
+    middleware::auth::create_token,
+};
 use entity::entities::{
     follower,
     prelude::{Follower, User},
     user,
 };
-use migration::SimpleExpr;
-#[cfg(feature = "seed")]
-use sea_orm::DeleteResult;
+use migration::{Expr, Func, LikeExpr, SimpleExpr};
+#[cfg(any(test, feature = "seed"))]
+use sea_orm::InsertResult;
 use sea_orm::{
-    prelude::Uuid, query::*, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, FromQueryResult,
-    InsertResult, QueryFilter,
+    prelude::Uuid, query::*, ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait,
+    DatabaseConnection, DbErr, DeleteResult, EntityTrait, FromQueryResult, QueryFilter,
+    TransactionTrait,
 };
 use serde::Serialize;
+use std::env;
+
+const DEFAULT_PAGE_OFFSET: u64 = 0;
+pub const DEFAULT_AVATAR_URL: &str = "DEFAULT_AVATAR_URL";
 
 /// Fetch `user` for the provided `email`.
 /// Returns optional `user` on success, otherwise returns an `database error`.
@@ -37,13 +50,33 @@ pub async fn get_user_by_username(
         .await
 }
 
-/// Fetch `user` for the provided `id`.
+/// Fetch `user` whose email or username matches the provided `login`. Email match
+/// takes precedence when login is email of one user and username of another.
+/// Returns optional `user` on success, otherwise returns an `database error`.
+pub async fn get_user_by_login(
+    db: &DatabaseConnection,
+    login: &str,
+) -> Result<Option<user::Model>, DbErr> {
+    User::find()
+        .filter(
+            user::Column::Email
+                .eq(login)
+                .or(user::Column::Username.eq(login)),
+        )
+        .order_by_desc(user::Column::Email.eq(login))
+        .one(db)
+        .await
+}
+
+/// Fetch `user` for the provided `id`. Served from provided in-memory cache when it is enabled
+/// (see UserCache for details).
 /// Returns optional `user` on success, otherwise returns an `database error`.
 pub async fn get_user_by_id(
     db: &DatabaseConnection,
+    user_cache: &UserCache,
     id: Uuid,
 ) -> Result<Option<user::Model>, DbErr> {
-    User::find_by_id(id).one(db).await
+    user_cache.get_or_load(db, id).await
 }
 
 /// Fetch `user` with token for the provided `id`.
@@ -64,6 +97,7 @@ pub async fn get_user_with_token_by_id(
 /// Empty username, empty email produces error as not allowed on database level.
 /// See [`InsertResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.InsertResult.html)
 /// documentation for more details.
+#[cfg(any(test, feature = "seed"))]
 pub async fn create_user(
     db: &DatabaseConnection,
     user: user::ActiveModel,
@@ -71,14 +105,112 @@ pub async fn create_user(
     User::insert(user).exec(db).await
 }
 
-/// Update `user` for the provided `ActiveModel`.
+/// Register `user` for the provided `ActiveModel` in single transaction: user inserted and
+/// returned with token, or nothing stored when insert fails (e.g. existing username or email)
+/// or token can not be created.
+/// Returns `user` with token on success, otherwise returns an `api error`, unique violations
+/// mapped to dedicated errors (see user_unique_err).
+pub async fn register_user_tx(
+    db: &DatabaseConnection,
+    user: user::ActiveModel,
+) -> Result<UserWithToken, ApiErr> {
+    let txn = db.begin().await?;
+    let model = user.insert(&txn).await.map_err(user_unique_err)?;
+    let user_with_token = match UserWithToken::try_from(model) {
+        Ok(user_with_token) => user_with_token,
+        Err(err) => {
+            txn.rollback().await?;
+            return Err(err.into());
+        }
+    };
+    txn.commit().await?;
+
+    Ok(user_with_token)
+}
+
+/// Update `user` for the provided `ActiveModel`. Cached user is invalidated, whether or not
+/// cache is enabled, so it is never served after the update.
 /// Returns `user` on success, otherwise returns an `database error`.
 /// Reject models with non existing username or email.
 pub async fn update_user(
     db: &DatabaseConnection,
+    user_cache: &UserCache,
     user: user::ActiveModel,
 ) -> Result<user::Model, DbErr> {
-    User::update(user).exec(db).await
+    let id = user.id.clone().take();
+    let res = User::update(user).exec(db).await;
+    if let Some(id) = id {
+        user_cache.invalidate(id);
+    }
+    res
+}
+
+/// Update only provided fields of `user` with the provided `id`. Clearable fields
+/// (bio, image) are set to null by `Some(None)`, omitted fields are left unchanged.
+/// Returns updated `user` on success, `RecordNotFound` error for non existing user,
+/// otherwise returns an `database error`.
+pub async fn apply_user_patch(
+    db: &DatabaseConnection,
+    user_cache: &UserCache,
+    id: Uuid,
+    patch: UserPatch,
+) -> Result<user::Model, DbErr> {
+    let user = get_user_by_id(db, user_cache, id)
+        .await?
+        .ok_or(DbErr::RecordNotFound(format!("user {id}")))?;
+
+    let mut user_model: user::ActiveModel = user.into();
+    if let Some(email) = patch.email {
+        user_model.email = Set(email);
+    }
+    if let Some(username) = patch.username {
+        user_model.username = Set(username);
+    }
+    if let Some(password) = patch.password {
+        user_model.password = Set(password);
+    }
+    if let Some(bio) = patch.bio {
+        user_model.bio = Set(bio);
+    }
+    if let Some(image) = patch.image {
+        user_model.image = Set(image);
+    }
+
+    update_user(db, user_cache, user_model).await
+}
+
+/// Delete `user` with the provided `id`. Cached user is not invalidated, as deletion may be
+/// part of not yet committed transaction, caller invalidates it once deletion is committed.
+/// Returns `DeleteResult` with affected rows count on success, otherwise
+/// returns an `database error`.
+/// See [`DeleteResult`](https://docs.rs/sea-orm/latest/sea_orm/struct.DeleteResult.html)
+/// documentation for more details.
+pub async fn delete_user<C: ConnectionTrait>(db: &C, id: Uuid) -> Result<DeleteResult, DbErr> {
+    User::delete_by_id(id).exec(db).await
+}
+
+/// Delete account of `user` with the provided `id` in single transaction: articles, comments,
+/// favorites and follows (both directions) of the user are deleted, then the user itself.
+/// Nothing is deleted when any step fails. Remaining references (reactions, blocks) are
+/// removed by database cascade. Cached user is invalidated after commit, so concurrent read
+/// never caches the user again.
+/// Returns `DeleteResult` of the user row (0 affected rows for non existing user) on success,
+/// otherwise returns an `database error`.
+pub async fn delete_user_tx(
+    db: &DatabaseConnection,
+    user_cache: &UserCache,
+    id: Uuid,
+) -> Result<DeleteResult, DbErr> {
+    let txn = db.begin().await?;
+    delete_articles_by_author(&txn, id).await?;
+    delete_comments_by_author(&txn, id).await?;
+    delete_favorites_by_user(&txn, id).await?;
+    delete_all_follows_for(&txn, id).await?;
+    let res = delete_user(&txn, id).await?;
+    txn.commit().await?;
+    user_cache.invalidate(id);
+
+    Ok(res)
 }
 
 /// Fetch `profile` for the provided `username`. Optional identifier used
@@ -100,6 +232,88 @@ pub async fn get_profile_by_username(
         .await
 }
 
+/// Fetch `profiles` whose username or bio starts with provided query, case insensitive.
+/// Optional identifier used to determine whether the logged in user is a follower of the profile.
+/// Limit response by limit and offset parameters. Ordered by username. Empty query produce empty result.
+/// Returns list of `profiles` on success, otherwise returns an `database error`.
+pub async fn search_users(
+    db: &DatabaseConnection,
+    query: &str,
+    limit: u64,
+    offset: Option<u64>,
+    current_user_id: Option<Uuid>,
+) -> Result<Vec<Profile>, DbErr> {
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let escaped = query
+        .to_lowercase()
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    let starts_with = |col: user::Column| {
+        Expr::expr(Func::lower(Expr::col(col)))
+            .like(LikeExpr::new(format!("{escaped}%")).escape('\\'))
+    };
+
+    User::find()
+        .filter(
+            Condition::any()
+                .add(starts_with(user::Column::Username))
+                .add(starts_with(user::Column::Bio)),
+        )
+        .column_as(
+            author_followed_by_current_user(current_user_id),
+            "following",
+        )
+        .order_by_asc(user::Column::Username)
+        .limit(limit)
+        .offset(offset.or(Some(DEFAULT_PAGE_OFFSET)))
+        .into_model::<Profile>()
+        .all(db)
+        .await
+}
+
+/// Fetch `profiles` of users who both follow and are followed by user with provided id.
+/// Optional identifier used to determine whether the logged in user is a follower of the profile.
+/// Ordered by username. Returns list of `profiles` on success, otherwise returns an `database error`.
+pub async fn get_mutual_follows(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    current_user_id: Option<Uuid>,
+) -> Result<Vec<Profile>, DbErr> {
+    User::find()
+        .filter(
+            // users following provided user
+            user::Column::Id.in_subquery(
+                Follower::find()
+                    .select_only()
+                    .column(follower::Column::FollowerId)
+                    .filter(follower::Column::UserId.eq(user_id))
+                    .into_query(),
+            ),
+        )
+        .filter(
+            // users followed by provided user
+            user::Column::Id.in_subquery(
+                Follower::find()
+                    .select_only()
+                    .column(follower::Column::UserId)
+                    .filter(follower::Column::FollowerId.eq(user_id))
+                    .into_query(),
+            ),
+        )
+        .column_as(
+            author_followed_by_current_user(current_user_id),
+            "following",
+        )
+        .order_by_asc(user::Column::Username)
+        .into_model::<Profile>()
+        .all(db)
+        .await
+}
+
 /// Returns expression for determine whether the logged in
 /// user is a follower of the profile. Return `false` if user id is not specified.
 pub fn author_followed_by_current_user(user_id: Option<Uuid>) -> SimpleExpr {
@@ -136,8 +350,19 @@ pub struct UserWithToken {
     pub image: Option<String>,
 }
 
+/// Struct describing partial update of user. `None` leaves field unchanged,
+/// `Some(None)` clears clearable field.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserPatch {
+    pub email: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub bio: Option<Option<String>>,
+    pub image: Option<Option<String>>,
+}
+
 /// Struct describing data about author of article (comment, etc...)
-#[derive(Clone, Debug, Default, PartialEq, FromQueryResult, Eq, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
 pub struct Profile {
     pub username: String,
     pub bio: Option<String>,
@@ -145,29 +370,47 @@ pub struct Profile {
     pub following: bool,
 }
 
-impl FromQueryResult for UserWithToken {
+/// Single place where `Profile` is read from query result, so article authors, comment
+/// authors and profiles share the default avatar (see DEFAULT_AVATAR_URL) for missing image.
+impl FromQueryResult for Profile {
     fn from_query_result(res: &sea_orm::QueryResult, pre: &str) -> Result<Self, sea_orm::DbErr> {
-        let id: Uuid = res.try_get(pre, "id")?;
-
+        let image: Option<String> = res.try_get(pre, "image")?;
         Ok(Self {
-            token: create_token(&id).unwrap(),
-            email: res.try_get(pre, "email")?,
             username: res.try_get(pre, "username")?,
             bio: res.try_get(pre, "bio")?,
-            image: res.try_get(pre, "image")?,
+            image: image.or_else(get_default_avatar_url),
+            following: res.try_get(pre, "following")?,
         })
     }
 }
 
-impl From<user::Model> for UserWithToken {
-    fn from(model: user::Model) -> Self {
-        Self {
-            token: create_token(&model.id).unwrap(),
+/// Return DEFAULT_AVATAR_URL from environment varibles, no default image when not set or empty
+fn get_default_avatar_url() -> Option<String> {
+    env::var(DEFAULT_AVATAR_URL)
+        .ok()
+        .filter(|url| !url.is_empty())
+}
+
+impl FromQueryResult for UserWithToken {
+    fn from_query_result(res: &sea_orm::QueryResult, pre: &str) -> Result<Self, sea_orm::DbErr> {
+        let model = user::Model::from_query_result(res, pre)?;
+        Self::try_from(model).map_err(|err| DbErr::Custom(format!("Unable to create token: {err}")))
+    }
+}
+
+/// Single place where `UserWithToken` is constructed, so login, registration
+/// and current user responses always share the same shape. Fails when token can not be created.
+impl TryFrom<user::Model> for UserWithToken {
+    type Error = jsonwebtoken::errors::Error;
+
+    fn try_from(model: user::Model) -> Result<Self, Self::Error> {
+        Ok(Self {
+            token: create_token(&model.id)?,
             email: model.email,
             username: model.username,
             bio: model.bio,
             image: model.image,
-        }
+        })
     }
 }
 
@@ -233,9 +476,67 @@ mod test_get_user_by_username {
     }
 }
 
+#[cfg(test)]
+mod test_get_user_by_login {
+    use super::get_user_by_login;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use entity::entities::{prelude::User, user};
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    #[tokio::test]
+    async fn by_email_or_username() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) =
+            TestDataBuilder::new().users(Insert(3)).build().await?;
+        let expected = users.unwrap().into_iter().nth(1).unwrap();
+
+        let result = get_user_by_login(&connection, "email2").await?;
+        assert_eq!(result, Some(expected.clone()));
+
+        let result = get_user_by_login(&connection, "username2").await?;
+        assert_eq!(result, Some(expected));
+
+        let result = get_user_by_login(&connection, "unknown").await?;
+        assert_eq!(result, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn email_match_first() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) =
+            TestDataBuilder::new().users(Insert(2)).build().await?;
+        let users = users.unwrap();
+
+        // Second user takes email of the first one as username
+        let mut second: user::ActiveModel = users[1].clone().into();
+        second.username = Set("email1".to_owned());
+        second.update(&connection).await?;
+
+        let result = get_user_by_login(&connection, "email1").await?;
+        assert_eq!(result, Some(users[0].clone()));
+        assert_eq!(User::find().all(&connection).await?.len(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn no_users() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new().users(Migration).build().await?;
+
+        let result = get_user_by_login(&connection, "email1").await?;
+        assert_eq!(result, None);
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test_get_user_by_id {
     use super::get_user_by_id;
+    use crate::repo::user_cache::UserCache;
     use crate::tests::{
         Operation::{Insert, Migration},
         TestData, TestDataBuilder, TestErr,
@@ -248,7 +549,7 @@ mod test_get_user_by_id {
             TestDataBuilder::new().users(Insert(5)).build().await?;
         let expected = users.unwrap().into_iter().nth(2).unwrap();
 
-        let result = get_user_by_id(&connection, expected.id).await?;
+        let result = get_user_by_id(&connection, &UserCache::default(), expected.id).await?;
         assert_eq!(result, Some(expected));
 
         Ok(())
@@ -258,7 +559,7 @@ mod test_get_user_by_id {
     async fn get_non_existing_user() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new().users(Migration).build().await?;
 
-        let result = get_user_by_id(&connection, Uuid::new_v4()).await?;
+        let result = get_user_by_id(&connection, &UserCache::default(), Uuid::new_v4()).await?;
         assert_eq!(result, None);
 
         Ok(())
@@ -286,7 +587,7 @@ mod test_get_user_with_token_by_id {
         let expected_id = expected_model.id;
         let expected = UserWithToken {
             token: create_token(&expected_id).unwrap(),
-            ..expected_model.into()
+            ..expected_model.try_into().unwrap()
         };
 
         let result = get_user_with_token_by_id(&connection, expected_id).await?;
@@ -306,6 +607,36 @@ mod test_get_user_with_token_by_id {
     }
 }
 
+#[cfg(test)]
+mod test_register_user_tx {
+    use super::register_user_tx;
+    use crate::api::error::ApiErr;
+    use crate::tests::{Operation::Create, TestData, TestDataBuilder, TestErr};
+    use entity::entities::{prelude::User, user};
+    use sea_orm::{EntityTrait, PaginatorTrait, Set};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn register_and_reject_duplicate() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) =
+            TestDataBuilder::new().users(Create(1)).build().await?;
+        let actives = TestDataBuilder::activate_models::<User, user::ActiveModel>(&users);
+        let mut model = actives.into_iter().next().unwrap();
+
+        let result = register_user_tx(&connection, model.clone()).await?;
+        assert_eq!(result.username, "username1");
+        assert!(!result.token.is_empty());
+
+        model.id = Set(Uuid::new_v4());
+        model.username = Set("other_username".to_owned());
+        let result = register_user_tx(&connection, model).await;
+        assert_eq!(result, Err(ApiErr::EmailTaken));
+        assert_eq!(User::find().count(&connection).await?, 1);
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test_create_user {
     use super::create_user;
@@ -450,12 +781,14 @@ mod test_create_user {
 #[cfg(test)]
 mod test_update_user {
     use super::update_user;
+    use crate::repo::user_cache::UserCache;
     use crate::tests::{
         Operation::{Insert, Migration},
         TestData, TestDataBuilder, TestErr,
     };
     use entity::entities::user;
-    use sea_orm::ActiveModelTrait;
+    use sea_orm::{ActiveModelTrait, Set};
+    use std::time::Duration;
     use uuid::Uuid;
 
     #[tokio::test]
@@ -474,12 +807,30 @@ mod test_update_user {
         };
 
         let update_model = user::ActiveModel::from(expected.clone()).reset_all();
-        let updated = update_user(&connection, update_model).await?;
+        let updated = update_user(&connection, &UserCache::default(), update_model).await?;
         assert_eq!(expected, updated);
 
         Ok(())
     }
 
+    #[tokio::test]
+    async fn update_invalidates_cached_user() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) =
+            TestDataBuilder::new().users(Insert(1)).build().await?;
+        let user = users.unwrap().into_iter().next().unwrap();
+        let cache = UserCache::new(Some(Duration::from_secs(60)));
+
+        cache.get_or_load(&connection, user.id).await?;
+        let mut update_model: user::ActiveModel = user.clone().into();
+        update_model.bio = Set(Some("updated bio".to_owned()));
+        update_user(&connection, &cache, update_model).await?;
+
+        let cached = cache.get_or_load(&connection, user.id).await?;
+        assert_eq!(cached.unwrap().bio, Some("updated bio".to_owned()));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn update_not_existing_data() -> Result<(), TestErr> {
         let (connection, _) = TestDataBuilder::new().users(Migration).build().await?;
@@ -494,7 +845,7 @@ mod test_update_user {
         };
 
         let update_model = user::ActiveModel::from(expected).reset_all();
-        let result = update_user(&connection, update_model).await;
+        let result = update_user(&connection, &UserCache::default(), update_model).await;
         assert!(
             result.is_err_and(|err| err.to_string().ends_with("None of the records are updated"))
         );
@@ -503,6 +854,94 @@ mod test_update_user {
     }
 }
 
+#[cfg(test)]
+mod test_apply_user_patch {
+    use super::{apply_user_patch, UserPatch};
+    use crate::repo::user_cache::UserCache;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+    use sea_orm::DbErr;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn patch_single_field() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) =
+            TestDataBuilder::new().users(Insert(2)).build().await?;
+        let user = users.unwrap().into_iter().nth(1).unwrap();
+
+        let patch = UserPatch {
+            username: Some("renamed".to_owned()),
+            ..Default::default()
+        };
+        let result = apply_user_patch(&connection, &UserCache::default(), user.id, patch).await?;
+
+        let mut expected = user;
+        expected.username = "renamed".to_owned();
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn patch_multiple_fields() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) =
+            TestDataBuilder::new().users(Insert(1)).build().await?;
+        let user = users.unwrap().into_iter().next().unwrap();
+
+        let patch = UserPatch {
+            email: Some("new email".to_owned()),
+            bio: Some(Some("new bio".to_owned())),
+            image: Some(Some("new image".to_owned())),
+            ..Default::default()
+        };
+        let result = apply_user_patch(&connection, &UserCache::default(), user.id, patch).await?;
+
+        let mut expected = user;
+        expected.email = "new email".to_owned();
+        expected.bio = Some("new bio".to_owned());
+        expected.image = Some("new image".to_owned());
+        assert_eq!(result, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn clear_clearable_field() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) =
+            TestDataBuilder::new().users(Insert(1)).build().await?;
+        let user = users.unwrap().into_iter().next().unwrap();
+
+        let patch = UserPatch {
+            image: Some(None),
+            ..Default::default()
+        };
+        let result = apply_user_patch(&connection, &UserCache::default(), user.id, patch).await?;
+
+        assert_eq!(result.image, None);
+        assert_eq!(result.bio, user.bio);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn patch_not_existing_user() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new().users(Migration).build().await?;
+
+        let result = apply_user_patch(
+            &connection,
+            &UserCache::default(),
+            Uuid::new_v4(),
+            UserPatch::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(DbErr::RecordNotFound(_))));
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test_get_profile_by_username {
     use super::{get_profile_by_username, Profile};
@@ -589,6 +1028,127 @@ mod test_author_followed_by_current_user {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn get_own_profile() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .followers(Insert(vec![(2, 1)]))
+            .build()
+            .await?;
+        let users = users.unwrap();
+
+        let result = get_profile_by_username(&connection, "username1", Some(users[0].id)).await?;
+        assert!(!result.unwrap().following);
+
+        let result = get_profile_by_username(&connection, "username2", Some(users[0].id)).await?;
+        assert!(result.unwrap().following);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_search_users {
+    use super::search_users;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+
+    #[tokio::test]
+    async fn username_prefix_match() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(12))
+            .followers(Insert(vec![(10, 1)]))
+            .build()
+            .await?;
+
+        let current_user_id = users.unwrap()[0].id;
+        let result =
+            search_users(&connection, "UserName1", 20, None, Some(current_user_id)).await?;
+        let usernames: Vec<&str> = result.iter().map(|prf| prf.username.as_str()).collect();
+
+        assert_eq!(
+            usernames,
+            vec!["username1", "username10", "username11", "username12"]
+        );
+        assert!(!result[0].following);
+        assert!(result[1].following);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn no_match() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(5))
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let result = search_users(&connection, "name", 20, None, None).await?;
+        assert!(result.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn empty_query() -> Result<(), TestErr> {
+        let (connection, _) = TestDataBuilder::new()
+            .users(Insert(5))
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let result = search_users(&connection, "", 20, None, None).await?;
+        assert!(result.is_empty());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_get_mutual_follows {
+    use super::get_mutual_follows;
+    use crate::tests::{
+        Operation::{Insert, Migration},
+        TestData, TestDataBuilder, TestErr,
+    };
+
+    #[tokio::test]
+    async fn only_mutual_returned() -> Result<(), TestErr> {
+        // user1 and user2 follow each other, user3 only follows user1,
+        // user1 follows user4 without being followed back
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(4))
+            .followers(Insert(vec![(1, 2), (2, 1), (1, 3), (4, 1), (2, 4)]))
+            .build()
+            .await?;
+        let users = users.unwrap();
+
+        let result = get_mutual_follows(&connection, users[0].id, Some(users[3].id)).await?;
+        let usernames: Vec<&str> = result.iter().map(|prf| prf.username.as_str()).collect();
+
+        assert_eq!(usernames, vec!["username2"]);
+        assert!(result[0].following);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn no_mutuals() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = TestDataBuilder::new()
+            .users(Insert(2))
+            .followers(Migration)
+            .build()
+            .await?;
+
+        let result = get_mutual_follows(&connection, users.unwrap()[0].id, None).await?;
+        assert!(result.is_empty());
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -653,7 +1213,8 @@ mod test_user_with_token_from_user {
             image: Some("image".to_owned()),
             password: "password".to_owned(),
         }
-        .into();
+        .try_into()
+        .unwrap();
 
         let expected = UserWithToken {
             token,
@@ -680,7 +1241,8 @@ mod test_user_with_token_from_user {
             image: None,
             password: "password".to_owned(),
         }
-        .into();
+        .try_into()
+        .unwrap();
 
         let expected = UserWithToken {
             token,
@@ -0,0 +1,220 @@
+use entity::entities::{prelude::User, user};
+use sea_orm::{prelude::Uuid, ConnectionTrait, DbErr, EntityTrait};
+use std::{
+    collections::BTreeMap,
+    env,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+const USER_CACHE: &str = "USER_CACHE";
+const USER_CACHE_TTL_SECS: &str = "USER_CACHE_TTL_SECS";
+const DEFAULT_USER_CACHE_TTL_SECS: u64 = 60;
+
+/// In-memory cache of `users` by id used by get_user_by_id, entries expire after time to live.
+/// Shared by application as extension, disabled when time to live not provided (see
+/// USER_CACHE). Every invalidation bumps generation of the cache, so user loaded before
+/// invalidation (e.g. concurrently with update) is not stored after it and stale data never
+/// outlives an update.
+#[derive(Debug, Default)]
+pub struct UserCache {
+    ttl: Option<Duration>,
+    state: Mutex<CacheState>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    generation: u64,
+    entries: BTreeMap<Uuid, (Instant, user::Model)>,
+}
+
+impl UserCache {
+    /// Create cache keeping users for `ttl`, `None` disables caching.
+    pub fn new(ttl: Option<Duration>) -> Self {
+        Self {
+            ttl,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Create cache enabled with USER_CACHE, keeping users for USER_CACHE_TTL_SECS.
+    pub fn from_env() -> Self {
+        Self::new(user_cache_enabled().then(get_user_cache_ttl))
+    }
+
+    /// Fetch `user` for the provided `id` from cache, loaded from database when missing or
+    /// cached longer than time to live. Always loaded from database when cache disabled.
+    /// Returns optional `user` on success, otherwise returns an `database error`.
+    pub async fn get_or_load<C: ConnectionTrait>(
+        &self,
+        db: &C,
+        id: Uuid,
+    ) -> Result<Option<user::Model>, DbErr> {
+        let Some(ttl) = self.ttl else {
+            return User::find_by_id(id).one(db).await;
+        };
+
+        let generation = {
+            let state = self.state.lock().unwrap();
+            match state.entries.get(&id) {
+                Some((cached_at, user)) if cached_at.elapsed() < ttl => {
+                    return Ok(Some(user.clone()))
+                }
+                _ => state.generation,
+            }
+        };
+
+        let user = User::find_by_id(id).one(db).await?;
+
+        let mut state = self.state.lock().unwrap();
+        if state.generation == generation {
+            match &user {
+                Some(user) => state.entries.insert(id, (Instant::now(), user.clone())),
+                None => state.entries.remove(&id),
+            };
+        }
+        Ok(user)
+    }
+
+    /// Remove cached `user` with provided `id`, must be called once the user is changed.
+    pub fn invalidate(&self, id: Uuid) {
+        let mut state = self.state.lock().unwrap();
+        state.generation += 1;
+        state.entries.remove(&id);
+    }
+}
+
+/// Return USER_CACHE from environment varibles, disabled by default
+fn user_cache_enabled() -> bool {
+    env::var(USER_CACHE)
+        .map(|val| val.trim() == "1" || val.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Return USER_CACHE_TTL_SECS from environment varibles, 60 seconds by default
+fn get_user_cache_ttl() -> Duration {
+    let secs = env::var(USER_CACHE_TTL_SECS)
+        .ok()
+        .and_then(|val| val.parse::<u64>().ok())
+        .unwrap_or(DEFAULT_USER_CACHE_TTL_SECS);
+    Duration::from_secs(secs)
+}
+
+#[cfg(test)]
+mod test_user_cache {
+    use super::UserCache;
+    use crate::tests::{Operation::Insert, TestData, TestDataBuilder, TestErr};
+    use axum::async_trait;
+    use sea_orm::{
+        ConnectionTrait, DatabaseConnection, DbBackend, DbErr, ExecResult, QueryResult, Statement,
+    };
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    /// Connection counting executed queries.
+    struct CountingConnection {
+        inner: DatabaseConnection,
+        queries: AtomicUsize,
+    }
+
+    impl CountingConnection {
+        fn queries(&self) -> usize {
+            self.queries.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl ConnectionTrait for CountingConnection {
+        fn get_database_backend(&self) -> DbBackend {
+            self.inner.get_database_backend()
+        }
+
+        async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            self.inner.execute(stmt).await
+        }
+
+        async fn execute_unprepared(&self, sql: &str) -> Result<ExecResult, DbErr> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            self.inner.execute_unprepared(sql).await
+        }
+
+        async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            self.inner.query_one(stmt).await
+        }
+
+        async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            self.inner.query_all(stmt).await
+        }
+    }
+
+    async fn counting_connection() -> Result<(CountingConnection, TestData), TestErr> {
+        let (inner, data) = TestDataBuilder::new().users(Insert(1)).build().await?;
+        let connection = CountingConnection {
+            inner,
+            queries: AtomicUsize::new(0),
+        };
+        Ok((connection, data))
+    }
+
+    #[tokio::test]
+    async fn cached_user_served_without_query() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = counting_connection().await?;
+        let user = users.unwrap().into_iter().next().unwrap();
+        let cache = UserCache::new(Some(TTL));
+
+        let first = cache.get_or_load(&connection, user.id).await?;
+        let second = cache.get_or_load(&connection, user.id).await?;
+        assert_eq!(first, Some(user.clone()));
+        assert_eq!(second, Some(user));
+        assert_eq!(connection.queries(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn invalidated_user_reloaded() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = counting_connection().await?;
+        let user = users.unwrap().into_iter().next().unwrap();
+        let cache = UserCache::new(Some(TTL));
+
+        cache.get_or_load(&connection, user.id).await?;
+        cache.invalidate(user.id);
+        cache.get_or_load(&connection, user.id).await?;
+        assert_eq!(connection.queries(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn expired_user_reloaded() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = counting_connection().await?;
+        let user = users.unwrap().into_iter().next().unwrap();
+        let cache = UserCache::new(Some(Duration::ZERO));
+
+        cache.get_or_load(&connection, user.id).await?;
+        cache.get_or_load(&connection, user.id).await?;
+        assert_eq!(connection.queries(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn disabled_cache_always_queries() -> Result<(), TestErr> {
+        let (connection, TestData { users, .. }) = counting_connection().await?;
+        let user = users.unwrap().into_iter().next().unwrap();
+        let cache = UserCache::default();
+
+        cache.get_or_load(&connection, user.id).await?;
+        cache.get_or_load(&connection, user.id).await?;
+        assert_eq!(connection.queries(), 2);
+
+        Ok(())
+    }
+}
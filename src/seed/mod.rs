@@ -1,7 +1,9 @@
 use crate::repo::article::{create_article, empty_article_table};
 use crate::repo::article_tag::{empty_article_tag_table, insert_article_tag};
 use crate::repo::comment::{empty_comment_table, insert_comment};
-use crate::repo::favorited_article::{empty_favorited_article_table, favorite_article};
+use crate::repo::favorited_article::{
+    empty_favorited_article_table, favorite_article, FavoriteOutcome,
+};
 use crate::repo::follower::{create_follower, empty_follower_table};
 use crate::repo::tag::{empty_tag_table, insert_tag};
 use crate::repo::user::{create_user, empty_user_table};
@@ -177,12 +179,16 @@ async fn seed_favorited_article(
         .populate_async(
             "src/seed/fixtures/favorited_article.yml",
             |model: favorited_article::Model| async move {
+                let id = (model.article_id, model.user_id);
                 let mut active_model: favorited_article::ActiveModel = model.into();
                 active_model = active_model.reset_all();
 
                 let res = favorite_article(db, active_model).await.unwrap();
 
-                Ok(format!("{:?}", res.last_insert_id))
+                match res {
+                    FavoriteOutcome::AlreadyFavorited => Ok("already favorited".to_owned()),
+                    _ => Ok(format!("{:?}", id)),
+                }
             },
         )
         .await?;
@@ -1,5 +1,5 @@
 use crate::api::error::ApiErr;
-use chrono::{Duration, Local};
+use chrono::{Duration, Utc};
 use entity::entities::{
     article, article_tag, comment, favorited_article, follower,
     prelude::{Article, ArticleTag, Comment, FavoritedArticle, Follower, Tag, User},
@@ -206,8 +206,7 @@ impl TestDataBuilder {
                 .iter()
                 .enumerate()
                 .map(|(idx, val)| {
-                    let current_time =
-                        (Local::now() + Duration::seconds(idx as i64 + 1)).naive_local();
+                    let current_time = (Utc::now() + Duration::seconds(idx as i64 + 1)).naive_utc();
 
                     match self.users.as_ref().unwrap() {
                         Operation::Insert(users) | Operation::Create(users) => article::Model {
@@ -219,6 +218,8 @@ impl TestDataBuilder {
                             author_id: users[*val - 1].id,
                             created_at: Some(current_time),
                             updated_at: Some(current_time),
+                            published: true,
+                            deleted_at: None,
                         },
                         _ => unreachable!(),
                     }
@@ -298,8 +299,7 @@ impl TestDataBuilder {
                 .iter()
                 .enumerate()
                 .map(|(idx, (author, article))| {
-                    let current_time =
-                        (Local::now() + Duration::seconds(idx as i64 + 1)).naive_local();
+                    let current_time = (Utc::now() + Duration::seconds(idx as i64 + 1)).naive_utc();
 
                     match (
                         self.users.as_ref().unwrap(),
@@ -428,6 +428,7 @@ impl TestDataBuilder {
                             article_tag::Model {
                                 article_id: artcls[*article - 1].id,
                                 tag_id: tgs[*tag - 1].id,
+                                created_at: Some(Utc::now().naive_utc()),
                             }
                         }
                         _ => unreachable!(),
@@ -568,6 +569,7 @@ impl TestDataBuilder {
                             favorited_article::Model {
                                 article_id: artcls[*article - 1].id,
                                 user_id: usrs[*user - 1].id,
+                                created_at: Some(Utc::now().naive_utc()),
                             }
                         }
                         _ => unreachable!(),
@@ -644,7 +646,11 @@ impl TestDataBuilder {
         let articles = self
             .exec::<Article, article::ActiveModel>(
                 &connection,
-                vec!["m20231030_000002_create_article_table"],
+                vec![
+                    "m20231030_000002_create_article_table",
+                    "m20231228_000012_add_article_published",
+                    "m20240110_000015_add_article_deleted_at",
+                ],
                 &self.articles,
             )
             .await?;
@@ -660,7 +666,10 @@ impl TestDataBuilder {
         let tags = self
             .exec::<Tag, tag::ActiveModel>(
                 &connection,
-                vec!["m20231030_000004_create_tag_table"],
+                vec![
+                    "m20231030_000004_create_tag_table",
+                    "m20240102_000013_add_tag_name_ci",
+                ],
                 &self.tags,
             )
             .await?;
@@ -668,7 +677,10 @@ impl TestDataBuilder {
         let article_tags = self
             .exec::<ArticleTag, article_tag::ActiveModel>(
                 &connection,
-                vec!["m20231030_000005_create_article_tag_table"],
+                vec![
+                    "m20231030_000005_create_article_tag_table",
+                    "m20231220_000011_add_article_tag_created_at",
+                ],
                 &self.article_tags,
             )
             .await?;
@@ -676,7 +688,10 @@ impl TestDataBuilder {
         let followers = self
             .exec::<Follower, follower::ActiveModel>(
                 &connection,
-                vec!["m20231101_000006_create_follower_table"],
+                vec![
+                    "m20231101_000006_create_follower_table",
+                    "m20240105_000014_create_block_table",
+                ],
                 &self.followers,
             )
             .await?;
@@ -684,7 +699,10 @@ impl TestDataBuilder {
         let favorited_articles = self
             .exec::<FavoritedArticle, favorited_article::ActiveModel>(
                 &connection,
-                vec!["m20231104_000007_create_favorited_article_table"],
+                vec![
+                    "m20231104_000007_create_favorited_article_table",
+                    "m20231215_000010_add_favorited_article_created_at",
+                ],
                 &self.favorited_articles,
             )
             .await?;